@@ -8,7 +8,7 @@
 
 use std::sync::Arc;
 use pallet_ico_rpc::FullIco;
-use parachain_template_runtime::{opaque::Block, AccountId, Balance, Index as Nonce, PoolId, CurrencyId};
+use parachain_template_runtime::{opaque::Block, AccountId, Balance, BlockNumber, Index as Nonce, PoolId, CurrencyId};
 
 use sc_client_api::AuxStore;
 pub use sc_rpc::{DenyUnsafe, SubscriptionTaskExecutor};
@@ -47,8 +47,10 @@ where
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + Sync + Send + 'static,
 	C::Api: pallet_farm_rpc::FarmRuntimeApi<Block, AccountId, PoolId, Balance>,
-	C::Api: pallet_ico_rpc_runtime_api::IcoAmountApi<Block, AccountId, CurrencyId, Nonce, Balance>,
+	C::Api: pallet_farm_extend_rpc::FarmExtendRuntimeApi<Block, AccountId, PoolId, Balance>,
+	C::Api: pallet_ico_rpc_runtime_api::IcoAmountApi<Block, AccountId, CurrencyId, Nonce, Balance, BlockNumber>,
 {
+	use pallet_farm_extend_rpc::{FarmExtend, FarmExtendApiServer};
 	use pallet_farm_rpc::{Farm, FarmApiServer};
 	use pallet_ico_rpc::{FullIco, IcoApiServer};
 
@@ -60,6 +62,7 @@ where
 	// local
 	module.merge(FullIco::new(client.clone(), deny_unsafe).into_rpc())?;
 	module.merge(Farm::new(client.clone(), deny_unsafe).into_rpc())?;
+	module.merge(FarmExtend::new(client.clone(), deny_unsafe).into_rpc())?;
 
 	module.merge(System::new(client.clone(), pool, deny_unsafe).into_rpc())?;
 	module.merge(TransactionPayment::new(client).into_rpc())?;
@@ -24,7 +24,10 @@ use frame_support::{
 use frame_system::{ensure_signed, pallet_prelude::*};
 use scale_info::TypeInfo;
 use sp_core::U256;
-use sp_runtime::{traits::AccountIdConversion, ArithmeticError, RuntimeDebug};
+use sp_runtime::{
+	traits::{AccountIdConversion, SaturatedConversion, Saturating},
+	ArithmeticError, RuntimeDebug,
+};
 use sp_std::vec::Vec;
 
 #[cfg(feature = "std")]
@@ -62,6 +65,16 @@ impl Pair {
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct LiquidityInfo(pub Balance, pub Balance, pub AssetId);
 
+/// The sums of the prices of a pair at every block, scaled by `math::PRICE_PRECISION`.
+#[derive(Encode, Decode, Eq, PartialEq, Copy, Default, Clone, RuntimeDebug, TypeInfo)]
+pub struct CumulativePrice<BlockNumber> {
+	/// The price of asset 0 in asset 1.
+	pub price_0_cumulative: U256,
+	/// The price of asset 1 in asset 0.
+	pub price_1_cumulative: U256,
+	pub block_number: BlockNumber,
+}
+
 // Re-export pallet items so that they can be accessed from the crate namespace.
 use crate::math::LIQUIDITY_DECIMALS;
 use dico_currencies::{currencies_trait::CurrenciesHandler, DicoAssetMetadata};
@@ -101,6 +114,10 @@ pub mod pallet {
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
 
+		/// The minimum period of the time weighted average prices.
+		#[pallet::constant]
+		type TwapPeriod: Get<Self::BlockNumber>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -161,6 +178,23 @@ pub mod pallet {
 	#[pallet::getter(fn next_liquidity_id)]
 	pub type NextLiquidityId<T: Config> = StorageValue<_, AssetId, ValueQuery>;
 
+	/// The cumulative prices of the pair when its reserves changed last time.
+	#[pallet::storage]
+	#[pallet::getter(fn price_cumulative)]
+	pub type PriceCumulative<T: Config> =
+		StorageMap<_, Blake2_128Concat, Pair, CumulativePrice<T::BlockNumber>, OptionQuery>;
+
+	/// The older and the newer observations of the cumulative prices, at least `TwapPeriod` apart.
+	#[pallet::storage]
+	#[pallet::getter(fn price_observations)]
+	pub type PriceObservations<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		Pair,
+		(CumulativePrice<T::BlockNumber>, CumulativePrice<T::BlockNumber>),
+		OptionQuery,
+	>;
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// call id:601
@@ -178,91 +212,15 @@ pub mod pallet {
 			amount_b_min: Balance,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-
-			ensure!(asset_a != asset_b, Error::<T>::MustBeDifferentAsset);
-			ensure!(
-				amount_a_desired != 0 && amount_b_desired != 0,
-				Error::<T>::MustAddNonZeroAmount
-			);
-			ensure!(
-				asset_a <= T::LiquidityAssetIdBase::get() && asset_b <= T::LiquidityAssetIdBase::get(),
-				Error::<T>::MustBeNonLiquidAsset
-			);
-
-			let pair = Self::pair_for(asset_a, asset_b);
-			let mut liquidity_info = Liquidity::<T>::get(pair).unwrap_or_default();
-
-			let (asset_0_amount, asset_1_amount, liquidity_id) =
-				(&mut liquidity_info.0, &mut liquidity_info.1, &mut liquidity_info.2);
-
-			let (reserve_a, reserve_b) = Self::get_reserves(asset_a, asset_b).unwrap_or((0u128, 0u128));
-
-			let amount_in = math::calc_amount_in(
-				to_u256!(reserve_a),
-				to_u256!(reserve_b),
-				to_u256!(amount_a_desired),
-				to_u256!(amount_b_desired),
-				to_u256!(amount_a_min),
-				to_u256!(amount_b_min),
-			)?;
-			let (amount_a, amount_b) = amount_in.ok_or(Error::<T>::InsufficientAmount)?;
-			let (amount_a, amount_b) = (to_balance!(amount_a)?, to_balance!(amount_b)?);
-
-			let module_account_id = Self::account_id();
-
-			if liquidity_id.is_zero() {
-				*liquidity_id = Self::create_liquidity_asset(asset_a, asset_b)?;
-			}
-
-			let new_liquidity_id = *liquidity_id;
-
-			let total_liquidity: Balance = T::Currency::total_issuance(*liquidity_id);
-
-			if total_liquidity.is_zero() && math::MINIMUM_LIQUIDITY > 0u128 {
-				// permanently lock the first MINIMUM_LIQUIDITY assets
-				T::Currency::deposit(*liquidity_id, &module_account_id, math::MINIMUM_LIQUIDITY)?;
-			}
-
-			let add_liquidity = math::calc_liquidity_add(
-				to_u256!(reserve_a),
-				to_u256!(reserve_b),
-				to_u256!(amount_a),
-				to_u256!(amount_b),
-				to_u256!(total_liquidity),
-				to_u256!(math::MINIMUM_LIQUIDITY),
-			)?;
-			let add_liquidity = to_balance!(add_liquidity)?;
-
-			ensure!(add_liquidity > Balance::zero(), Error::<T>::InsufficientMintLiquidity);
-
-			T::Currency::transfer(asset_a, &who, &module_account_id, amount_a)?;
-			T::Currency::transfer(asset_b, &who, &module_account_id, amount_b)?;
-
-			T::Currency::deposit(*liquidity_id, &who, add_liquidity)?;
-
-			let (add_amount_0, add_amount_1) = if pair.0 == asset_a {
-				(amount_a, amount_b)
-			} else {
-				(amount_b, amount_a)
-			};
-
-			*asset_0_amount = asset_0_amount
-				.checked_add(add_amount_0)
-				.ok_or(ArithmeticError::Overflow)?;
-			*asset_1_amount = asset_1_amount
-				.checked_add(add_amount_1)
-				.ok_or(ArithmeticError::Overflow)?;
-
-			Liquidity::<T>::insert(pair, liquidity_info);
-
-			Self::deposit_event(Event::LiquidityAdded(
-				who,
-				new_liquidity_id,
+			Self::do_add_liquidity(
+				&who,
 				asset_a,
 				asset_b,
-				amount_a,
-				amount_b,
-			));
+				amount_a_desired,
+				amount_b_desired,
+				amount_a_min,
+				amount_b_min,
+			)?;
 
 			Ok(().into())
 		}
@@ -285,6 +243,7 @@ pub mod pallet {
 
 			Liquidity::<T>::try_mutate(pair, |maybe_liquidity_info| -> DispatchResultWithPostInfo {
 				let liquidity_info = maybe_liquidity_info.as_mut().ok_or(Error::<T>::LiquidityNotFind)?;
+				Self::update_price_cumulative(pair, liquidity_info.0, liquidity_info.1);
 
 				let (asset_0_amount, asset_1_amount, liquidity_id) =
 					(&mut liquidity_info.0, &mut liquidity_info.1, &mut liquidity_info.2);
@@ -403,6 +362,105 @@ pub mod pallet {
 }
 
 impl<T: Config> Pallet<T> {
+	/// Add liquidity of the pair for `who`, returns the liquidity asset and the liquidity minted.
+	pub fn do_add_liquidity(
+		who: &T::AccountId,
+		asset_a: AssetId,
+		asset_b: AssetId,
+		amount_a_desired: Balance,
+		amount_b_desired: Balance,
+		amount_a_min: Balance,
+		amount_b_min: Balance,
+	) -> sp_std::result::Result<(AssetId, Balance), DispatchErrorWithPostInfo> {
+		ensure!(asset_a != asset_b, Error::<T>::MustBeDifferentAsset);
+		ensure!(
+			amount_a_desired != 0 && amount_b_desired != 0,
+			Error::<T>::MustAddNonZeroAmount
+		);
+		ensure!(
+			asset_a <= T::LiquidityAssetIdBase::get() && asset_b <= T::LiquidityAssetIdBase::get(),
+			Error::<T>::MustBeNonLiquidAsset
+		);
+
+		let pair = Self::pair_for(asset_a, asset_b);
+		let mut liquidity_info = Liquidity::<T>::get(pair).unwrap_or_default();
+		Self::update_price_cumulative(pair, liquidity_info.0, liquidity_info.1);
+
+		let (asset_0_amount, asset_1_amount, liquidity_id) =
+			(&mut liquidity_info.0, &mut liquidity_info.1, &mut liquidity_info.2);
+
+		let (reserve_a, reserve_b) = Self::get_reserves(asset_a, asset_b).unwrap_or((0u128, 0u128));
+
+		let amount_in = math::calc_amount_in(
+			to_u256!(reserve_a),
+			to_u256!(reserve_b),
+			to_u256!(amount_a_desired),
+			to_u256!(amount_b_desired),
+			to_u256!(amount_a_min),
+			to_u256!(amount_b_min),
+		)?;
+		let (amount_a, amount_b) = amount_in.ok_or(Error::<T>::InsufficientAmount)?;
+		let (amount_a, amount_b) = (to_balance!(amount_a)?, to_balance!(amount_b)?);
+
+		let module_account_id = Self::account_id();
+
+		if liquidity_id.is_zero() {
+			*liquidity_id = Self::create_liquidity_asset(asset_a, asset_b)?;
+		}
+
+		let new_liquidity_id = *liquidity_id;
+
+		let total_liquidity: Balance = T::Currency::total_issuance(*liquidity_id);
+
+		if total_liquidity.is_zero() && math::MINIMUM_LIQUIDITY > 0u128 {
+			// permanently lock the first MINIMUM_LIQUIDITY assets
+			T::Currency::deposit(*liquidity_id, &module_account_id, math::MINIMUM_LIQUIDITY)?;
+		}
+
+		let add_liquidity = math::calc_liquidity_add(
+			to_u256!(reserve_a),
+			to_u256!(reserve_b),
+			to_u256!(amount_a),
+			to_u256!(amount_b),
+			to_u256!(total_liquidity),
+			to_u256!(math::MINIMUM_LIQUIDITY),
+		)?;
+		let add_liquidity = to_balance!(add_liquidity)?;
+
+		ensure!(add_liquidity > Balance::zero(), Error::<T>::InsufficientMintLiquidity);
+
+		T::Currency::transfer(asset_a, who, &module_account_id, amount_a)?;
+		T::Currency::transfer(asset_b, who, &module_account_id, amount_b)?;
+
+		T::Currency::deposit(*liquidity_id, who, add_liquidity)?;
+
+		let (add_amount_0, add_amount_1) = if pair.0 == asset_a {
+			(amount_a, amount_b)
+		} else {
+			(amount_b, amount_a)
+		};
+
+		*asset_0_amount = asset_0_amount
+			.checked_add(add_amount_0)
+			.ok_or(ArithmeticError::Overflow)?;
+		*asset_1_amount = asset_1_amount
+			.checked_add(add_amount_1)
+			.ok_or(ArithmeticError::Overflow)?;
+
+		Liquidity::<T>::insert(pair, liquidity_info);
+
+		Self::deposit_event(Event::LiquidityAdded(
+			who.clone(),
+			new_liquidity_id,
+			asset_a,
+			asset_b,
+			amount_a,
+			amount_b,
+		));
+
+		Ok((new_liquidity_id, add_liquidity))
+	}
+
 	fn account_id() -> T::AccountId {
 		T::PalletId::get().into_account_truncating()
 	}
@@ -494,6 +552,76 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// The time weighted average price of `asset_a` in `asset_b`, scaled by
+	/// `math::PRICE_PRECISION`.
+	pub fn get_twap(asset_a: AssetId, asset_b: AssetId) -> Option<U256> {
+		let pair = Self::pair_for(asset_a, asset_b);
+		let (older, _) = Self::price_observations(pair)?;
+		let liquidity_info = Self::get_liquidity(pair)?;
+		let now = frame_system::Pallet::<T>::block_number();
+		let current = Self::current_price_cumulative(pair, liquidity_info.0, liquidity_info.1, now);
+
+		let elapsed = now.saturating_sub(older.block_number);
+		if elapsed.is_zero() {
+			return None;
+		}
+		let (current, older) = if pair.0 == asset_a {
+			(current.price_0_cumulative, older.price_0_cumulative)
+		} else {
+			(current.price_1_cumulative, older.price_1_cumulative)
+		};
+		current
+			.checked_sub(older)?
+			.checked_div(U256::from(elapsed.saturated_into::<u128>()))
+	}
+
+	/// The cumulative prices at `now`, with the reserves since the last change.
+	fn current_price_cumulative(
+		pair: Pair,
+		reserve_0: Balance,
+		reserve_1: Balance,
+		now: T::BlockNumber,
+	) -> CumulativePrice<T::BlockNumber> {
+		let mut cumulative = Self::price_cumulative(pair).unwrap_or(CumulativePrice {
+			price_0_cumulative: U256::zero(),
+			price_1_cumulative: U256::zero(),
+			block_number: now,
+		});
+
+		let elapsed = now.saturating_sub(cumulative.block_number);
+		if !elapsed.is_zero() && !reserve_0.is_zero() && !reserve_1.is_zero() {
+			let elapsed = U256::from(elapsed.saturated_into::<u128>());
+			let precision = U256::from(math::PRICE_PRECISION);
+			let price_0 = precision.saturating_mul(to_u256!(reserve_1)) / to_u256!(reserve_0);
+			let price_1 = precision.saturating_mul(to_u256!(reserve_0)) / to_u256!(reserve_1);
+			cumulative.price_0_cumulative = cumulative
+				.price_0_cumulative
+				.saturating_add(price_0.saturating_mul(elapsed));
+			cumulative.price_1_cumulative = cumulative
+				.price_1_cumulative
+				.saturating_add(price_1.saturating_mul(elapsed));
+		}
+		cumulative.block_number = now;
+		cumulative
+	}
+
+	/// Accumulate the prices of the reserves before they change.
+	fn update_price_cumulative(pair: Pair, reserve_0: Balance, reserve_1: Balance) {
+		let now = frame_system::Pallet::<T>::block_number();
+		let cumulative = Self::current_price_cumulative(pair, reserve_0, reserve_1, now);
+		PriceCumulative::<T>::insert(pair, cumulative);
+
+		PriceObservations::<T>::mutate(pair, |observations| match observations {
+			Some((older, newer)) => {
+				if now.saturating_sub(newer.block_number) >= T::TwapPeriod::get() {
+					*older = *newer;
+					*newer = cumulative;
+				}
+			}
+			None => *observations = Some((cumulative, cumulative)),
+		});
+	}
+
 	/// Performs a chained `get_amount_out` calculation for pairs of transactions of any path
 	/// length.
 	fn get_amounts_out(
@@ -550,6 +678,7 @@ impl<T: Config> Pallet<T> {
 
 			Liquidity::<T>::try_mutate(pair, |maybe_liquidity_info| -> DispatchResultWithPostInfo {
 				let liquidity_info = maybe_liquidity_info.as_mut().ok_or(Error::<T>::LiquidityNotFind)?;
+				Self::update_price_cumulative(pair, liquidity_info.0, liquidity_info.1);
 
 				let (asset_0_amount, asset_1_amount, _liquidity_id) =
 					(&mut liquidity_info.0, &mut liquidity_info.1, &mut liquidity_info.2);
@@ -11,6 +11,8 @@ pub const TEST_DECIMAL: u128 = 10_000_000_000u128;
 
 pub const MINIMUM_LIQUIDITY: u128 = 0u128;
 pub const LIQUIDITY_DECIMALS: u8 = 10;
+/// The precision of the cumulative prices.
+pub const PRICE_PRECISION: u128 = 1_000_000_000_000_000_000u128;
 
 /// Given a certain amount of asset A and a trading pair reserve,
 /// return an equal amount of other asset B.
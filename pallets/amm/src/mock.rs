@@ -137,6 +137,7 @@ impl Config for Test {
 	type Currency = Currency;
 	type CurrenciesHandler = Currency;
 	type PalletId = AMMPalletId;
+	type TwapPeriod = frame_support::traits::ConstU64<10>;
 	type WeightInfo = ();
 }
 
@@ -481,3 +481,40 @@ fn swap_assets_for_exact_assets2_should_work() {
 		.into()]);
 	});
 }
+
+#[test]
+fn get_twap_should_work() {
+	new_test_ext().execute_with(|| {
+		preset_conditions();
+
+		assert_ok!(AMM::add_liquidity(
+			Origin::signed(ALICE),
+			DICO,
+			USDT,
+			100_000_000_000_000,
+			100_000_000_000_000,
+			0,
+			0
+		));
+		assert_eq!(AMM::get_twap(DICO, USDT), None);
+
+		System::set_block_number(21);
+		assert_eq!(AMM::get_twap(DICO, USDT), Some(U256::from(math::PRICE_PRECISION)));
+
+		assert_ok!(AMM::swap_exact_assets_for_assets(
+			Origin::signed(BOB),
+			10_000_000_000_000,
+			0,
+			vec![DICO, USDT]
+		));
+		let pair = AMM::pair_for(DICO, USDT);
+		let (older, newer) = PriceObservations::<Test>::get(pair).unwrap();
+		assert_eq!(older.block_number, 1);
+		assert_eq!(newer.block_number, 21);
+
+		// price_0: 826671736919990909, price_1: 1209669999999993464
+		System::set_block_number(41);
+		assert_eq!(AMM::get_twap(DICO, USDT), Some(U256::from(913335868459995454u128)));
+		assert_eq!(AMM::get_twap(USDT, DICO), Some(U256::from(1104834999999996732u128)));
+	});
+}
@@ -34,7 +34,7 @@ pub use frame_support::{
 };
 use frame_system::{self as system, ensure_root, ensure_signed};
 use ico;
-use ico::traits::IcoHandler;
+use ico::traits::{IcoHandler, IcoProposalHandler};
 use orml_traits::{BalanceStatus, MultiCurrency, MultiReservableCurrency};
 use pallet_timestamp;
 use scale_info::TypeInfo;
@@ -79,10 +79,10 @@ pub type MemberCount = u32;
 
 #[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
 #[scale_info(skip_type_params(I))]
-pub enum IcoRawOrigin<AccountId, MulBalance> {
-	/// It has been condoned by a given number of members of the collective from
+pub enum IcoRawOrigin<AccountId, CurrencyId, MulBalance> {
+	/// It has been condoned by a given number of members of the given ico from
 	/// a given total.
-	Members(MulBalance, MulBalance),
+	Members(CurrencyId, u32, MulBalance, MulBalance),
 	/// It has been condoned by a single member of the collective.
 	Member(MulBalance, AccountId),
 	_Phantom(sp_std::marker::PhantomData<(AccountId, MulBalance)>),
@@ -115,12 +115,18 @@ pub mod pallet {
 	#[pallet::config]
 	pub trait Config: frame_system::Config + ico::Config {
 		/// The outer origin type.
-		type RuntimeOrigin: From<IcoRawOrigin<Self::AccountId, MultiBalanceOf<Self>>>
-			+ Into<Result<IcoRawOrigin<Self::AccountId, MultiBalanceOf<Self>>, <Self as Config>::RuntimeOrigin>>;
+		type RuntimeOrigin: From<IcoRawOrigin<Self::AccountId, CurrencyIdOf<Self>, MultiBalanceOf<Self>>>
+			+ Into<
+				Result<
+					IcoRawOrigin<Self::AccountId, CurrencyIdOf<Self>, MultiBalanceOf<Self>>,
+					<Self as Config>::RuntimeOrigin,
+				>,
+			>;
 		/// The outer call dispatch type.
 		type Proposal: Parameter
 			+ Dispatchable<RuntimeOrigin = <Self as Config>::RuntimeOrigin, PostInfo = PostDispatchInfo>
 			+ From<frame_system::Call<Self>>
+			+ From<ico::Call<Self>>
 			+ GetDispatchInfo;
 		/// The outer event type.
 		type RuntimeEvent: From<Event<Self>>
@@ -153,6 +159,12 @@ pub mod pallet {
 	pub type ProposalOf<T: Config> =
 		StorageDoubleMap<_, Blake2_128Concat, CurrencyIdOf<T>, Blake2_128Concat, T::Hash, T::Proposal>;
 
+	/// The ico the proposal is made by the members of.
+	#[pallet::storage]
+	#[pallet::getter(fn proposal_ico_index_of)]
+	pub type ProposalIcoIndexOf<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, CurrencyIdOf<T>, Blake2_128Concat, T::Hash, u32>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn voting)]
 	pub type Voting<T: Config> = StorageDoubleMap<
@@ -206,30 +218,19 @@ pub mod pallet {
 
 			if threshold * ico_total_amount <= user_ico_amount {
 				runtime_print!("dispacth");
-				let result = proposal.dispatch(IcoRawOrigin::Members(user_ico_amount, ico_total_amount).into());
+				let result = proposal
+					.dispatch(IcoRawOrigin::Members(currency_id, ico_index, user_ico_amount, ico_total_amount).into());
 				Self::deposit_event(Event::Executed(proposal_hash, result.map(|_| ()).map_err(|e| e.error)));
 			} else {
-				let mut proposals = Proposals::<T>::get(currency_id);
-				ensure!(
-					proposals.len() + 1 <= T::MaxProposals::get() as usize,
-					Error::<T>::TooManyProposals
-				);
-				proposals.push(proposal_hash);
-				Proposals::<T>::insert(currency_id, proposals);
-
-				let index = Self::proposal_count(currency_id);
-				<ProposalCount<T>>::mutate(currency_id, |i| *i += 1);
-				<ProposalOf<T>>::insert(currency_id, proposal_hash, *proposal);
-				let end = system::Pallet::<T>::block_number() + T::MotionDuration::get();
-				let votes = IcoCollectiveVotes {
-					index,
-					reason: reason,
+				let index = Self::do_propose(
+					currency_id,
+					ico_index,
+					proposal_hash,
+					*proposal,
+					reason,
 					threshold,
-					ayes: vec![(who.clone(), user_ico_amount)],
-					nays: vec![],
-					end,
-				};
-				<Voting<T>>::insert(currency_id, proposal_hash, votes);
+					vec![(who.clone(), user_ico_amount)],
+				)?;
 
 				Self::deposit_event(Event::Proposed(who, index, proposal_hash, threshold));
 			}
@@ -260,6 +261,7 @@ pub mod pallet {
 			let mut voting = Self::voting(currency_id, &proposal).ok_or(Error::<T>::ProposalMissing)?;
 
 			ensure!(voting.index == index, Error::<T>::WrongIndex);
+			Self::ensure_ico_index(currency_id, &proposal, ico_index)?;
 
 			ensure!(
 				system::Pallet::<T>::block_number() <= voting.end,
@@ -334,6 +336,7 @@ pub mod pallet {
 			let voting = Self::voting(currency_id, &proposal_hash).ok_or(Error::<T>::ProposalMissing)?;
 
 			ensure!(voting.index == index, Error::<T>::WrongIndex);
+			Self::ensure_ico_index(currency_id, &proposal_hash, ico_index)?;
 
 			let mut yes_votes = MultiBalanceOf::<T>::from(0u32);
 			let mut no_votes = MultiBalanceOf::<T>::from(0u32);
@@ -357,7 +360,7 @@ pub mod pallet {
 				let proposal = ProposalOf::<T>::get(currency_id, proposal_hash).ok_or(Error::<T>::ProposalMissing)?;
 				Self::deposit_event(Event::Closed(proposal_hash, yes_votes, no_votes));
 				let (proposal_weight, proposal_count) =
-					Self::do_approve_proposal(currency_id, seats, yes_votes, proposal_hash, proposal);
+					Self::do_approve_proposal(currency_id, ico_index, seats, yes_votes, proposal_hash, proposal);
 				return Ok(());
 			} else if disapproved {
 				Self::deposit_event(Event::Closed(proposal_hash, yes_votes, no_votes));
@@ -410,6 +413,8 @@ pub mod pallet {
 		/// The given length bound for the proposal was too low.
 		WrongProposalLength,
 		VoteExpire,
+		/// The proposal belongs to another ico of the currency.
+		WrongIcoIndex,
 	}
 
 	#[pallet::event]
@@ -445,9 +450,46 @@ pub mod pallet {
 		/// A proposal was closed because its threshold was reached or after its duration was up.
 		/// \[proposal_hash, yes, no\]
 		Closed(T::Hash, MultiBalanceOf<T>, MultiBalanceOf<T>),
+		/// The initiator of a milestone ico requested a release, the ico members vote on it.
+		/// \[currency_id, ico_index, proposal_index, proposal_hash, threshold\]
+		ReleaseProposed(CurrencyIdOf<T>, u32, ProposalIndex, T::Hash, Percent),
 	}
 
 	impl<T: Config> Pallet<T> {
+		fn do_propose(
+			currency_id: CurrencyIdOf<T>,
+			ico_index: u32,
+			proposal_hash: T::Hash,
+			proposal: <T as Config>::Proposal,
+			reason: Vec<u8>,
+			threshold: Percent,
+			ayes: Vec<(T::AccountId, MultiBalanceOf<T>)>,
+		) -> Result<ProposalIndex, DispatchError> {
+			let mut proposals = Proposals::<T>::get(currency_id);
+			ensure!(
+				proposals.len() + 1 <= T::MaxProposals::get() as usize,
+				Error::<T>::TooManyProposals
+			);
+			proposals.push(proposal_hash);
+			Proposals::<T>::insert(currency_id, proposals);
+
+			let index = Self::proposal_count(currency_id);
+			<ProposalCount<T>>::mutate(currency_id, |i| *i += 1);
+			<ProposalOf<T>>::insert(currency_id, proposal_hash, proposal);
+			<ProposalIcoIndexOf<T>>::insert(currency_id, proposal_hash, ico_index);
+			let end = system::Pallet::<T>::block_number() + T::MotionDuration::get();
+			let votes = IcoCollectiveVotes {
+				index,
+				reason: reason,
+				threshold,
+				ayes,
+				nays: vec![],
+				end,
+			};
+			<Voting<T>>::insert(currency_id, proposal_hash, votes);
+			Ok(index)
+		}
+
 		fn validate_and_get_proposal(
 			currency_id: CurrencyIdOf<T>,
 			hash: &T::Hash,
@@ -466,6 +508,7 @@ pub mod pallet {
 
 		fn do_approve_proposal(
 			currency_id: CurrencyIdOf<T>,
+			ico_index: u32,
 			seats: MultiBalanceOf<T>,
 			yes_votes: MultiBalanceOf<T>,
 			proposal_hash: T::Hash,
@@ -475,7 +518,7 @@ pub mod pallet {
 
 			let dispatch_weight = proposal.get_dispatch_info().weight;
 
-			let origin = IcoRawOrigin::Members(yes_votes, seats).into();
+			let origin = IcoRawOrigin::Members(currency_id, ico_index, yes_votes, seats).into();
 
 			let result = proposal.dispatch(origin);
 			Self::deposit_event(Event::Executed(proposal_hash, result.map(|_| ()).map_err(|e| e.error)));
@@ -497,6 +540,7 @@ pub mod pallet {
 		fn remove_proposal(currency_id: CurrencyIdOf<T>, proposal_hash: T::Hash) -> u32 {
 			// remove proposal and vote
 			ProposalOf::<T>::remove(currency_id, &proposal_hash);
+			ProposalIcoIndexOf::<T>::remove(currency_id, &proposal_hash);
 			Voting::<T>::remove(currency_id, &proposal_hash);
 			let num_proposals = Proposals::<T>::mutate(currency_id, |proposals| {
 				proposals.retain(|h| h != &proposal_hash);
@@ -504,6 +548,43 @@ pub mod pallet {
 			});
 			num_proposals as u32
 		}
+
+		/// The proposals made before the ico index was recorded accept any index.
+		fn ensure_ico_index(currency_id: CurrencyIdOf<T>, proposal_hash: &T::Hash, ico_index: u32) -> DispatchResult {
+			if let Some(index) = Self::proposal_ico_index_of(currency_id, proposal_hash) {
+				ensure!(index == ico_index, Error::<T>::WrongIcoIndex);
+			}
+			Ok(())
+		}
+	}
+
+	impl<T: Config> IcoProposalHandler<CurrencyIdOf<T>> for Pallet<T> {
+		fn propose_permit_release(
+			currency_id: CurrencyIdOf<T>,
+			index: u32,
+			threshold: Percent,
+			reason: Vec<u8>,
+		) -> DispatchResult {
+			let proposal: <T as Config>::Proposal = ico::Call::<T>::permit_release { currency_id, index }.into();
+			let proposal_hash = T::Hashing::hash_of(&proposal);
+			// The proposal of an earlier request is stale, as a new request is only made when there
+			// is no pending one.
+			if <ProposalOf<T>>::contains_key(currency_id, proposal_hash) {
+				Self::remove_proposal(currency_id, proposal_hash);
+			}
+
+			let proposal_index =
+				Self::do_propose(currency_id, index, proposal_hash, proposal, reason, threshold, vec![])?;
+
+			Self::deposit_event(Event::ReleaseProposed(
+				currency_id,
+				index,
+				proposal_index,
+				proposal_hash,
+				threshold,
+			));
+			Ok(())
+		}
 	}
 
 	pub(crate) fn get_result_weight(result: DispatchResultWithPostInfo) -> Option<Weight> {
@@ -515,7 +596,7 @@ pub mod pallet {
 
 	/// Origin for the dao module.
 	#[pallet::origin]
-	pub type Origin<T> = IcoRawOrigin<<T as frame_system::Config>::AccountId, MultiBalanceOf<T>>;
+	pub type Origin<T> = IcoRawOrigin<<T as frame_system::Config>::AccountId, CurrencyIdOf<T>, MultiBalanceOf<T>>;
 
 	pub struct EnsureProportionAtLeast<T: Config, AccountId, const N: u32, const D: u32>(
 		sp_std::marker::PhantomData<(T, AccountId)>,
@@ -523,8 +604,8 @@ pub mod pallet {
 
 	impl<
 			T: Config,
-			O: Into<Result<IcoRawOrigin<AccountId, MultiBalanceOf<T>>, O>>
-				+ From<IcoRawOrigin<AccountId, MultiBalanceOf<T>>>,
+			O: Into<Result<IcoRawOrigin<AccountId, CurrencyIdOf<T>, MultiBalanceOf<T>>, O>>
+				+ From<IcoRawOrigin<AccountId, CurrencyIdOf<T>, MultiBalanceOf<T>>>,
 			AccountId,
 			const N: u32,
 			const D: u32,
@@ -533,7 +614,7 @@ pub mod pallet {
 		type Success = ();
 		fn try_origin(o: O) -> Result<Self::Success, O> {
 			o.into().and_then(|o| match o {
-				IcoRawOrigin::Members(n, m)
+				IcoRawOrigin::Members(_, _, n, m)
 					if n * D.saturated_into::<MultiBalanceOf<T>>() >= N.saturated_into::<MultiBalanceOf<T>>() * m =>
 				{
 					Ok(())
@@ -550,6 +631,38 @@ pub mod pallet {
 		#[cfg(feature = "runtime-benchmarks")]
 		fn successful_origin() -> O {
 			O::from(IcoRawOrigin::Members(
+				Default::default(),
+				1u32,
+				MultiBalanceOf::<T>::from(1u32),
+				MultiBalanceOf::<T>::from(1u32),
+			))
+		}
+	}
+
+	/// Ensure the origin is the ico members, and succeed with the ico, the yes votes and the total
+	/// votes.
+	pub struct EnsureIcoMembers<T: Config, AccountId>(sp_std::marker::PhantomData<(T, AccountId)>);
+
+	impl<
+			T: Config,
+			O: Into<Result<IcoRawOrigin<AccountId, CurrencyIdOf<T>, MultiBalanceOf<T>>, O>>
+				+ From<IcoRawOrigin<AccountId, CurrencyIdOf<T>, MultiBalanceOf<T>>>,
+			AccountId,
+		> EnsureOrigin<O> for EnsureIcoMembers<T, AccountId>
+	{
+		type Success = (CurrencyIdOf<T>, u32, MultiBalanceOf<T>, MultiBalanceOf<T>);
+		fn try_origin(o: O) -> Result<Self::Success, O> {
+			o.into().and_then(|o| match o {
+				IcoRawOrigin::Members(currency_id, index, n, m) => Ok((currency_id, index, n, m)),
+				r => Err(O::from(r)),
+			})
+		}
+
+		#[cfg(feature = "runtime-benchmarks")]
+		fn successful_origin() -> O {
+			O::from(IcoRawOrigin::Members(
+				Default::default(),
+				1u32,
 				MultiBalanceOf::<T>::from(1u32),
 				MultiBalanceOf::<T>::from(1u32),
 			))
@@ -2,15 +2,15 @@ pub use super::*;
 use dico_treasury;
 use frame_support::{
 	construct_runtime, parameter_types,
-	traits::{Contains, LockIdentifier, Time},
+	traits::{Contains, LockIdentifier, Randomness, Time, UnixTime},
 	PalletId,
 };
 use orml_tokens as tokens;
 use orml_traits::parameter_type_with_key;
-use orml_traits::{DataFeeder, DataProvider};
-use pallet_oracle;
+use orml_traits::{DataFeeder, DataProvider, DataProviderExtended};
+use pallet_oracle::{self, TimestampedValue};
 use pallet_pricedao;
-use pallet_pricedao::Price;
+use pallet_pricedao::{Moment, Price};
 use pallet_randomness_collective_flip;
 use sp_core::H256;
 use sp_runtime::{
@@ -74,6 +74,14 @@ thread_local! {
 	static TIME: RefCell<u32> = RefCell::new(0);
 }
 
+pub struct MockRandomness;
+impl Randomness<Option<H256>, u64> for MockRandomness {
+	fn random(subject: &[u8]) -> (Option<H256>, u64) {
+		let (random, block_number) = RandomnessCollectiveFlip::random(subject);
+		(Some(random), block_number)
+	}
+}
+
 pub struct Timestamp;
 impl Time for Timestamp {
 	type Moment = u32;
@@ -151,6 +159,7 @@ impl pallet_amm::Config for Test {
 	type LiquidityAssetIdBase = LiquidityAssetIdBase;
 	type Currency = Currencies;
 	type PalletId = AMMPalletId;
+	type TwapPeriod = frame_support::traits::ConstU64<10>;
 	type WeightInfo = ();
 }
 
@@ -178,9 +187,27 @@ impl DataFeeder<CurrencyId, Price, AccountId> for MockDataProvider {
 	}
 }
 
+impl DataProviderExtended<CurrencyId, TimestampedValue<Price, Moment>> for MockDataProvider {
+	fn get_no_op(currency_id: &CurrencyId) -> Option<TimestampedValue<Price, Moment>> {
+		Self::get(currency_id).map(|value| TimestampedValue { value, timestamp: 0 })
+	}
+
+	fn get_all_values() -> Vec<(CurrencyId, Option<TimestampedValue<Price, Moment>>)> {
+		vec![]
+	}
+}
+
+pub struct MockUnixTime;
+impl UnixTime for MockUnixTime {
+	fn now() -> core::time::Duration {
+		core::time::Duration::from_millis(<Timestamp as Time>::now() as u64)
+	}
+}
+
 impl pallet_pricedao::Config for Test {
 	type Event = Event;
 	type Source = MockDataProvider;
+	type UnixTime = MockUnixTime;
 	type FeedOrigin = frame_system::EnsureRoot<AccountId>;
 	type UpdateOraclesStorgage = DicoOracle;
 	type BaseCurrency = Balances;
@@ -263,6 +290,11 @@ parameter_types! {
 	pub const InviterRewardProportion: Percent = Percent::from_percent(10u8);
 	pub const InviteeRewardProportion: Percent = Percent::from_percent(10u8);
 	pub const USDCurrencyId: AssetId = kUSD;
+	pub const MaxTwapDeviation: Percent = Percent::from_percent(10u8);
+	pub const CancelIcoFee: Percent = Percent::from_percent(10u8);
+	pub const PowerRewardBoost: Percent = Percent::from_percent(0u8);
+	pub const ProtectionFundId: PalletId = PalletId(*b"dico/ipf");
+	pub const ProtectionFundProportion: Percent = Percent::from_percent(10u8);
 
 }
 
@@ -273,6 +305,7 @@ impl ico::Config for Test {
 	type RejectIcoOrigin = frame_system::EnsureRoot<AccountId>;
 	type PermitReleaseOrigin = frame_system::EnsureRoot<AccountId>;
 	type TerminateIcoOrigin = frame_system::EnsureRoot<AccountId>;
+	type SetRewardEconomicsOrigin = frame_system::EnsureRoot<AccountId>;
 	type OnSlash = ();
 	type MultiCurrency = Currencies; //
 	type NativeCurrency = Balances; //
@@ -291,7 +324,28 @@ impl ico::Config for Test {
 	type InviteeRewardProportion = InviteeRewardProportion;
 	type PriceData = PriceDao;
 	type KycHandler = Kyc;
+	type TierHandler = ();
 	type USDCurrencyId = USDCurrencyId;
+	type MaxAllowlistProofLength = frame_support::traits::ConstU32<32>;
+	type MaxPriceAge = frame_support::traits::ConstU64<60_000>;
+	type MaxTwapDeviation = MaxTwapDeviation;
+	type MaxInviteCodeLength = frame_support::traits::ConstU32<16>;
+	type MaxReferralLevels = frame_support::traits::ConstU32<3>;
+	type CancelIcoFee = CancelIcoFee;
+	type PowerHalfLife = frame_support::traits::ConstU64<10_000>;
+	type MaxPowerDelegators = frame_support::traits::ConstU32<2>;
+	type PowerRewardBoost = PowerRewardBoost;
+	type MaxLotteryEntrants = frame_support::traits::ConstU32<10>;
+	type MaxLotteryDrawsPerBlock = frame_support::traits::ConstU32<10>;
+	type MaxRequestExpiriesPerBlock = frame_support::traits::ConstU32<10>;
+	type RewardPoolClaimDuration = frame_support::traits::ConstU64<100>;
+	type ProtectionFundId = ProtectionFundId;
+	type ProtectionFundProportion = ProtectionFundProportion;
+	type IcoProposalHandler = Dao;
+	type LiquidityHandler = AMM;
+	type ReceiptHandler = ();
+	type Randomness = MockRandomness;
+	type IcoMembersOrigin = dao::EnsureIcoMembers<Test, AccountId>;
 }
 
 parameter_types! {
@@ -49,3 +49,64 @@ fn vote_should_work() {
 		assert_eq!(Voting::<Test>::contains_key(KSM, hash), false);
 	});
 }
+
+#[test]
+fn ico_members_origin_should_be_scoped() {
+	new_test_ext().execute_with(|| {
+		MilestonesOf::<Test>::insert(
+			KSM,
+			1,
+			MilestoneInfo {
+				milestones: vec![],
+				threshold: Percent::from_percent(50u8),
+			},
+		);
+		let permit = |currency_id, index, yes, total| {
+			Call::IcoTest(ico::Call::permit_release {
+				currency_id: KSM,
+				index: 1,
+			})
+			.dispatch(Origin::from(IcoRawOrigin::Members(currency_id, index, yes, total)))
+			.map_err(|e| e.error)
+		};
+
+		// The members of another ico can not permit the release.
+		assert_eq!(permit(DOT, 1, 100, 100), Err(ico::Error::<Test>::BadOrigin.into()));
+		assert_eq!(permit(KSM, 2, 100, 100), Err(ico::Error::<Test>::BadOrigin.into()));
+		assert_eq!(
+			permit(KSM, 1, 40, 100),
+			Err(ico::Error::<Test>::MilestoneThresholdNotReached.into())
+		);
+		// The threshold of the ico is reached.
+		assert_eq!(permit(KSM, 1, 50, 100), Err(ico::Error::<Test>::IcoNotExists123.into()));
+	});
+}
+
+#[test]
+fn propose_permit_release_should_replace_stale_proposal() {
+	new_test_ext().execute_with(|| {
+		let proposal = Call::IcoTest(ico::Call::permit_release {
+			currency_id: KSM,
+			index: 1,
+		});
+		let hash = BlakeTwo256::hash_of(&proposal);
+		assert_ok!(<Dao as IcoProposalHandler<_>>::propose_permit_release(
+			KSM,
+			1,
+			Percent::from_percent(50u8),
+			vec![]
+		));
+		assert_eq!(Dao::proposal_ico_index_of(KSM, hash), Some(1));
+		assert_eq!(Dao::voting(KSM, hash).unwrap().index, 0);
+
+		// The request of the next milestone replaces the proposal of the expired request.
+		assert_ok!(<Dao as IcoProposalHandler<_>>::propose_permit_release(
+			KSM,
+			1,
+			Percent::from_percent(50u8),
+			vec![]
+		));
+		assert_eq!(Dao::proposals(KSM), vec![hash]);
+		assert_eq!(Dao::voting(KSM, hash).unwrap().index, 1);
+	});
+}
@@ -0,0 +1,20 @@
+//! Runtime API definition for farm extend pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use pallet_farm_extend::{ParticipantExtend, PoolExtendInfo};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait FarmExtendApi<AccountId, PoolExtendId, Balance> where
+		AccountId: codec::Codec,
+		PoolExtendId: codec::Codec,
+		Balance: codec::Codec,
+	{
+		fn get_pending_reward(account: AccountId, pid: PoolExtendId) -> Balance;
+
+		fn get_active_pools() -> Vec<(PoolExtendId, PoolExtendInfo<AccountId>)>;
+
+		fn get_account_positions(account: AccountId) -> Vec<(PoolExtendId, ParticipantExtend, Balance)>;
+	}
+}
@@ -0,0 +1,142 @@
+#![allow(clippy::upper_case_acronyms)]
+#![allow(dead_code)]
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{async_trait, Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorCode, ErrorObject},
+};
+use pallet_farm_extend::{ParticipantExtend, PoolExtendInfo};
+use sc_rpc_api::DenyUnsafe;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, MaybeDisplay},
+};
+use std::convert::TryInto;
+use std::sync::Arc;
+
+pub use pallet_farm_extend_rpc_runtime_api::FarmExtendApi as FarmExtendRuntimeApi;
+
+#[rpc(client, server)]
+pub trait FarmExtendApi<AccountId, PoolExtendId, Balance> {
+	#[method(name = "farmExtend_getPendingReward")]
+	fn get_pending_reward(&self, account: AccountId, pid: PoolExtendId) -> RpcResult<NumberOrHex>;
+
+	#[method(name = "farmExtend_getActivePools")]
+	fn get_active_pools(&self) -> RpcResult<Vec<(PoolExtendId, PoolExtendInfo<AccountId>)>>;
+
+	#[method(name = "farmExtend_getAccountPositions")]
+	fn get_account_positions(
+		&self,
+		account: AccountId,
+	) -> RpcResult<Vec<(PoolExtendId, ParticipantExtend, NumberOrHex)>>;
+}
+
+/// A struct that implements the [`FarmExtendApi`].
+pub struct FarmExtend<C, B> {
+	client: Arc<C>,
+	deny_unsafe: DenyUnsafe,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> FarmExtend<C, B> {
+	/// Create new `FarmExtend` with the given reference to the client.
+	pub fn new(client: Arc<C>, deny_unsafe: DenyUnsafe) -> Self {
+		FarmExtend {
+			client,
+			deny_unsafe,
+			_marker: Default::default(),
+		}
+	}
+}
+
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The transaction was not decodable.
+	DecodeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::DecodeError => 2,
+		}
+	}
+}
+
+fn runtime_error(message: &'static str, e: impl std::fmt::Debug) -> JsonRpseeError {
+	JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+		Error::RuntimeError.into(),
+		message,
+		Some(format!("{:?}", e)),
+	)))
+}
+
+fn try_into_rpc_balance<Balance: TryInto<NumberOrHex>>(value: Balance) -> RpcResult<NumberOrHex> {
+	value.try_into().map_err(|_| {
+		JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+			ErrorCode::InvalidParams.code(),
+			format!("doesn't fit in NumberOrHex representation"),
+			None::<()>,
+		)))
+	})
+}
+
+#[async_trait]
+impl<C, Block, AccountId, PoolExtendId, Balance> FarmExtendApiServer<AccountId, PoolExtendId, Balance>
+	for FarmExtend<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: FarmExtendRuntimeApi<Block, AccountId, PoolExtendId, Balance>,
+	AccountId: Codec + serde::Serialize + serde::de::DeserializeOwned + Send + Sync + 'static,
+	PoolExtendId: Codec + serde::Serialize + serde::de::DeserializeOwned + Send + Sync + 'static,
+	Balance: Codec + MaybeDisplay + Copy + TryInto<NumberOrHex> + std::marker::Send + 'static,
+{
+	fn get_pending_reward(&self, account: AccountId, pid: PoolExtendId) -> RpcResult<NumberOrHex> {
+		let api = self.client.runtime_api();
+		let best = self.client.info().best_hash;
+		let at = BlockId::hash(best);
+
+		let reward = api
+			.get_pending_reward(&at, account, pid)
+			.map_err(|e| runtime_error("Unable to query pending reward.", e))?;
+
+		try_into_rpc_balance(reward)
+	}
+
+	fn get_active_pools(&self) -> RpcResult<Vec<(PoolExtendId, PoolExtendInfo<AccountId>)>> {
+		let api = self.client.runtime_api();
+		let best = self.client.info().best_hash;
+		let at = BlockId::hash(best);
+
+		api.get_active_pools(&at)
+			.map_err(|e| runtime_error("Unable to query active pools.", e))
+	}
+
+	fn get_account_positions(
+		&self,
+		account: AccountId,
+	) -> RpcResult<Vec<(PoolExtendId, ParticipantExtend, NumberOrHex)>> {
+		let api = self.client.runtime_api();
+		let best = self.client.info().best_hash;
+		let at = BlockId::hash(best);
+
+		let positions = api
+			.get_account_positions(&at, account)
+			.map_err(|e| runtime_error("Unable to query account positions.", e))?;
+
+		positions
+			.into_iter()
+			.map(|(pid, participant_extend, pending_reward)| {
+				Ok((pid, participant_extend, try_into_rpc_balance(pending_reward)?))
+			})
+			.collect()
+	}
+}
@@ -72,4 +72,52 @@ benchmarks! {
 		assert_eq!(T::Currency::free_balance(DOT, &maker), 999000000000000u128);
 		assert_eq!(T::Currency::free_balance(DICO, &caller), 1_000_000_000_000_000u128);
 	}
+
+	top_up_pool {
+		let maker = funded_account::<T>("maker", 0);
+		FarmExtend::<T>::create_pool(
+			RawOrigin::Signed(maker.clone()).into(),
+			DOT,
+			T::BlockNumber::from(100u32),
+			T::BlockNumber::from(1100u32),
+			1_000_000_000u128,
+			DICO
+		)?;
+
+	}: _(RawOrigin::Signed(maker.clone()), T::PoolExtendId::from(0u32), T::BlockNumber::from(2100u32))
+	verify {
+		assert_eq!(T::Currency::free_balance(DOT, &maker), 998000000000000u128);
+	}
+
+	update_reward_per_block {
+		let maker = funded_account::<T>("maker", 0);
+		FarmExtend::<T>::create_pool(
+			RawOrigin::Signed(maker.clone()).into(),
+			DOT,
+			T::BlockNumber::from(100u32),
+			T::BlockNumber::from(1100u32),
+			1_000_000_000u128,
+			DICO
+		)?;
+
+	}: _(RawOrigin::Signed(maker.clone()), T::PoolExtendId::from(0u32), 2_000_000_000u128)
+	verify {
+		assert_eq!(T::Currency::free_balance(DOT, &maker), 998000000000000u128);
+	}
+
+	cancel_pool {
+		let maker = funded_account::<T>("maker", 0);
+		FarmExtend::<T>::create_pool(
+			RawOrigin::Signed(maker.clone()).into(),
+			DOT,
+			T::BlockNumber::from(100u32),
+			T::BlockNumber::from(1100u32),
+			1_000_000_000u128,
+			DICO
+		)?;
+
+	}: _(RawOrigin::Signed(maker.clone()), T::PoolExtendId::from(0u32))
+	verify {
+		assert_eq!(T::Currency::free_balance(DOT, &maker), 1_000_000_000_000_000u128);
+	}
 }
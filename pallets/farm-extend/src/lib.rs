@@ -28,6 +28,7 @@ use sp_runtime::{
 	traits::{AccountIdConversion, SaturatedConversion},
 	ArithmeticError,
 };
+use sp_std::vec::Vec;
 
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
@@ -144,6 +145,10 @@ pub mod pallet {
 		InsufficientWithdrawAmount,
 		/// No pool id available.
 		NoPoolExtendIdAvailable,
+		/// Only the owner of the mining pool can do this.
+		NotPoolExtendOwner,
+		/// The mining pool has already ended.
+		PoolExtendEnded,
 	}
 
 	#[pallet::event]
@@ -155,6 +160,12 @@ pub mod pallet {
 		AssetDeposited(T::AccountId, T::PoolExtendId, Balance),
 		/// The mining pool was created. [pool id]
 		PoolExtendCreated(T::AccountId, T::PoolExtendId, AssetId, Balance, AssetId),
+		/// The mining pool was topped up. [pool id, end block, reward amount]
+		PoolExtendToppedUp(T::PoolExtendId, T::BlockNumber, Balance),
+		/// The reward for each block of the mining pool was updated. [pool id, reward per block]
+		PoolExtendRewardPerBlockUpdated(T::PoolExtendId, Balance),
+		/// The mining pool was cancelled. [pool id, refunded reward amount]
+		PoolExtendCancelled(T::PoolExtendId, Balance),
 	}
 
 	#[pallet::storage]
@@ -171,6 +182,12 @@ pub mod pallet {
 	pub type ParticipantExtends<T: Config> =
 		StorageDoubleMap<_, Twox64Concat, T::PoolExtendId, Twox64Concat, T::AccountId, ParticipantExtend, OptionQuery>;
 
+	/// Rewards of the blocks in which nobody staked, they are refunded to the owner when the
+	/// mining pool is cancelled.
+	#[pallet::storage]
+	#[pallet::getter(fn unallocated_reward)]
+	pub type UnallocatedRewards<T: Config> = StorageMap<_, Twox64Concat, T::PoolExtendId, Balance, ValueQuery>;
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		#[pallet::weight(< T as Config >::WeightInfo::create_pool())]
@@ -336,6 +353,149 @@ pub mod pallet {
 
 			Ok(().into())
 		}
+
+		/// Top up the rewards of the mining pool and extend its end block.
+		///
+		/// The owner pays `reward_per_block` for each block between the current end block and
+		/// the new one.
+		///
+		/// Emits `PoolExtendToppedUp` event when successful.
+		#[pallet::weight(< T as Config >::WeightInfo::top_up_pool())]
+		#[transactional]
+		pub fn top_up_pool(
+			origin: OriginFor<T>,
+			pool_extend_id: T::PoolExtendId,
+			end_block: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let mut pool_extend = PoolExtends::<T>::get(pool_extend_id).ok_or(Error::<T>::PoolExtendNotFind)?;
+			ensure!(pool_extend.owner == who, Error::<T>::NotPoolExtendOwner);
+
+			let block_number: BlockNumber = frame_system::pallet::Pallet::<T>::block_number().saturated_into();
+			ensure!(block_number < pool_extend.end_block, Error::<T>::PoolExtendEnded);
+
+			let new_end_block: BlockNumber = end_block.saturated_into();
+			ensure!(new_end_block > pool_extend.end_block, Error::<T>::InvalidBlockConfigure);
+
+			let block_delta = new_end_block
+				.checked_sub(pool_extend.end_block)
+				.ok_or(ArithmeticError::Overflow)?;
+			let top_up_amount = to_balance!(to_u256!(block_delta)
+				.checked_mul(to_u256!(pool_extend.reward_per_block))
+				.ok_or(ArithmeticError::Overflow)?)?;
+
+			T::Currency::transfer(pool_extend.currency_id, &who, &Self::account_id(), top_up_amount)?;
+
+			pool_extend.currency_amount = pool_extend
+				.currency_amount
+				.checked_add(top_up_amount)
+				.ok_or(ArithmeticError::Overflow)?;
+			pool_extend.end_block = new_end_block;
+			PoolExtends::<T>::insert(pool_extend_id, pool_extend);
+
+			Self::deposit_event(Event::PoolExtendToppedUp(pool_extend_id, end_block, top_up_amount));
+
+			Ok(().into())
+		}
+
+		/// Change the reward for each block of the mining pool.
+		///
+		/// Only the blocks after the current block are affected. The owner pays the difference
+		/// when the reward increases, and is refunded the difference when it decreases.
+		///
+		/// Emits `PoolExtendRewardPerBlockUpdated` event when successful.
+		#[pallet::weight(< T as Config >::WeightInfo::update_reward_per_block())]
+		#[transactional]
+		pub fn update_reward_per_block(
+			origin: OriginFor<T>,
+			pool_extend_id: T::PoolExtendId,
+			reward_per_block: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(reward_per_block > Balance::zero(), Error::<T>::InvalidRewardPerBlock);
+
+			Self::update_pool_extend(&pool_extend_id)?;
+
+			let mut pool_extend = PoolExtends::<T>::get(pool_extend_id).ok_or(Error::<T>::PoolExtendNotFind)?;
+			ensure!(pool_extend.owner == who, Error::<T>::NotPoolExtendOwner);
+
+			let block_number: BlockNumber = frame_system::pallet::Pallet::<T>::block_number().saturated_into();
+			ensure!(block_number < pool_extend.end_block, Error::<T>::PoolExtendEnded);
+
+			// The rewards before `last_reward_block` have been settled at the old rate.
+			let remaining_blocks = to_u256!(pool_extend
+				.end_block
+				.checked_sub(pool_extend.last_reward_block)
+				.ok_or(ArithmeticError::Overflow)?);
+			let old_amount = to_balance!(remaining_blocks
+				.checked_mul(to_u256!(pool_extend.reward_per_block))
+				.ok_or(ArithmeticError::Overflow)?)?;
+			let new_amount = to_balance!(remaining_blocks
+				.checked_mul(to_u256!(reward_per_block))
+				.ok_or(ArithmeticError::Overflow)?)?;
+
+			let module_account_id = Self::account_id();
+			if new_amount > old_amount {
+				let amount = new_amount - old_amount;
+				T::Currency::transfer(pool_extend.currency_id, &who, &module_account_id, amount)?;
+				pool_extend.currency_amount = pool_extend
+					.currency_amount
+					.checked_add(amount)
+					.ok_or(ArithmeticError::Overflow)?;
+			} else if new_amount < old_amount {
+				let amount = old_amount - new_amount;
+				T::Currency::transfer(pool_extend.currency_id, &module_account_id, &who, amount)?;
+				pool_extend.currency_amount = pool_extend
+					.currency_amount
+					.checked_sub(amount)
+					.ok_or(ArithmeticError::Overflow)?;
+			}
+
+			pool_extend.reward_per_block = reward_per_block;
+			PoolExtends::<T>::insert(pool_extend_id, pool_extend);
+
+			Self::deposit_event(Event::PoolExtendRewardPerBlockUpdated(pool_extend_id, reward_per_block));
+
+			Ok(().into())
+		}
+
+		/// Cancel the mining pool.
+		///
+		/// The rewards that have not been allocated to stakers are refunded to the owner and
+		/// the mining pool stops producing rewards. Stakers can still withdraw their assets and
+		/// the rewards they have earned.
+		///
+		/// Emits `PoolExtendCancelled` event when successful.
+		#[pallet::weight(< T as Config >::WeightInfo::cancel_pool())]
+		#[transactional]
+		pub fn cancel_pool(origin: OriginFor<T>, pool_extend_id: T::PoolExtendId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			Self::update_pool_extend(&pool_extend_id)?;
+
+			let mut pool_extend = PoolExtends::<T>::get(pool_extend_id).ok_or(Error::<T>::PoolExtendNotFind)?;
+			ensure!(pool_extend.owner == who, Error::<T>::NotPoolExtendOwner);
+
+			let remaining_blocks = pool_extend.end_block.saturating_sub(pool_extend.last_reward_block);
+			let refund_amount = to_balance!(to_u256!(remaining_blocks)
+				.checked_mul(to_u256!(pool_extend.reward_per_block))
+				.ok_or(ArithmeticError::Overflow)?
+				.checked_add(to_u256!(UnallocatedRewards::<T>::take(pool_extend_id)))
+				.ok_or(ArithmeticError::Overflow)?)?;
+
+			if refund_amount > Balance::zero() {
+				T::Currency::transfer(pool_extend.currency_id, &Self::account_id(), &who, refund_amount)?;
+			}
+
+			pool_extend.currency_amount = pool_extend.currency_amount.saturating_sub(refund_amount);
+			pool_extend.end_block = pool_extend.last_reward_block;
+			PoolExtends::<T>::insert(pool_extend_id, pool_extend);
+
+			Self::deposit_event(Event::PoolExtendCancelled(pool_extend_id, refund_amount));
+
+			Ok(().into())
+		}
 	}
 }
 
@@ -354,6 +514,93 @@ impl<T: Config> Pallet<T> {
 		Ok(next_pool_extend_id)
 	}
 
+	fn calc_acc_reward_per_share(
+		pool_extend: &PoolExtendInfo<T::AccountId>,
+	) -> sp_std::result::Result<Balance, DispatchErrorWithPostInfo> {
+		let block_number: BlockNumber = frame_system::pallet::Pallet::<T>::block_number().saturated_into();
+		let reward_block = block_number.min(pool_extend.end_block);
+		if reward_block <= pool_extend.last_reward_block || pool_extend.total_stake_amount == Balance::zero() {
+			return Ok(pool_extend.acc_reward_per_share);
+		}
+
+		let block_delta = reward_block
+			.checked_sub(pool_extend.last_reward_block)
+			.ok_or(ArithmeticError::Overflow)?;
+
+		let acc_reward_per_share = to_balance!(to_u256!(pool_extend.acc_reward_per_share)
+			.checked_add(
+				to_u256!(block_delta)
+					.checked_mul(to_u256!(pool_extend.reward_per_block))
+					.ok_or(ArithmeticError::Overflow)?
+					.checked_mul(to_u256!(1e12 as u64))
+					.ok_or(ArithmeticError::Overflow)?
+					.checked_div(to_u256!(pool_extend.total_stake_amount))
+					.ok_or(ArithmeticError::DivisionByZero)?
+			)
+			.ok_or(ArithmeticError::Overflow)?)?;
+
+		Ok(acc_reward_per_share)
+	}
+
+	fn calc_participant_reward(
+		pool_extend: &PoolExtendInfo<T::AccountId>,
+		participant_extend: &ParticipantExtend,
+	) -> sp_std::result::Result<Balance, DispatchErrorWithPostInfo> {
+		let acc_reward_per_share = Self::calc_acc_reward_per_share(pool_extend)?;
+
+		let pending_reward = to_balance!(to_u256!(participant_extend.amount)
+			.checked_mul(to_u256!(acc_reward_per_share))
+			.ok_or(ArithmeticError::Overflow)?
+			.checked_div(to_u256!(1e12 as u64))
+			.ok_or(ArithmeticError::Overflow)?
+			.checked_sub(to_u256!(participant_extend.reward_debt))
+			.ok_or(ArithmeticError::Overflow)?)?;
+
+		Ok(pending_reward)
+	}
+
+	/// The rewards the account can withdraw from the mining pool at the current block.
+	pub fn get_pending_reward(account: T::AccountId, pid: T::PoolExtendId) -> Balance {
+		let pool_extend = match PoolExtends::<T>::get(pid) {
+			Some(pool_extend) => pool_extend,
+			None => return Balance::zero(),
+		};
+		let participant_extend = match ParticipantExtends::<T>::get(pid, account) {
+			Some(participant_extend) => participant_extend,
+			None => return Balance::zero(),
+		};
+
+		Self::calc_participant_reward(&pool_extend, &participant_extend).unwrap_or_else(|_| Balance::zero())
+	}
+
+	/// All mining pools that have not ended yet.
+	///
+	/// `reward_per_block`, `total_stake_amount` and `end_block` of each pool are the inputs
+	/// needed to estimate its APR.
+	pub fn get_active_pools() -> Vec<(T::PoolExtendId, PoolExtendInfo<T::AccountId>)> {
+		let block_number: BlockNumber = frame_system::pallet::Pallet::<T>::block_number().saturated_into();
+
+		PoolExtends::<T>::iter()
+			.filter(|(_, pool_extend)| pool_extend.end_block > block_number)
+			.collect()
+	}
+
+	/// All positions of the account, together with the rewards it can withdraw from each pool.
+	pub fn get_account_positions(account: T::AccountId) -> Vec<(T::PoolExtendId, ParticipantExtend, Balance)> {
+		PoolExtends::<T>::iter()
+			.filter_map(|(pid, pool_extend)| {
+				let participant_extend = ParticipantExtends::<T>::get(pid, &account)?;
+				let pending_reward = Self::calc_participant_reward(&pool_extend, &participant_extend)
+					.unwrap_or_else(|_| Balance::zero());
+				if participant_extend.amount.is_zero() && pending_reward.is_zero() {
+					return None;
+				}
+
+				Some((pid, participant_extend, pending_reward))
+			})
+			.collect()
+	}
+
 	fn update_pool_extend(pid: &T::PoolExtendId) -> sp_std::result::Result<(), DispatchErrorWithPostInfo> {
 		PoolExtends::<T>::try_mutate(
 			pid,
@@ -376,15 +623,21 @@ impl<T: Config> Pallet<T> {
 					return Ok(());
 				}
 
+				let block_rewards = to_balance!(to_u256!(block_delta)
+					.checked_mul(to_u256!(pool_extend.reward_per_block))
+					.ok_or(ArithmeticError::Overflow)?)?;
+
 				if pool_extend.total_stake_amount == Balance::zero() {
+					UnallocatedRewards::<T>::try_mutate(pid, |unallocated| -> Result<(), ArithmeticError> {
+						*unallocated = unallocated
+							.checked_add(block_rewards)
+							.ok_or(ArithmeticError::Overflow)?;
+						Ok(())
+					})?;
 					pool_extend.last_reward_block = reward_block;
 					return Ok(());
 				}
 
-				let block_rewards = to_balance!(to_u256!(block_delta)
-					.checked_mul(to_u256!(pool_extend.reward_per_block))
-					.ok_or(ArithmeticError::Overflow)?)?;
-
 				pool_extend.acc_reward_per_share = to_balance!(to_u256!(pool_extend.acc_reward_per_share)
 					.checked_add(
 						to_u256!(block_rewards)
@@ -4,10 +4,10 @@
 
 use super::*;
 pub use crate::mock::{
-	Currency, Event as TestEvent, ExtBuilder, FarmExtend, Origin, System, Test, ALICE, BOB, DEFAULT_ASSET_AMOUNT, DICO,
-	DOT, USDT,
+	Currency, Event as TestEvent, ExtBuilder, FarmExtend, Origin, System, Test, ALICE, BOB, DAVE, DEFAULT_ASSET_AMOUNT,
+	DICO, DOT, USDT,
 };
-use frame_support::assert_ok;
+use frame_support::{assert_noop, assert_ok};
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
 	let mut ext = ExtBuilder::default().build();
@@ -389,3 +389,172 @@ fn withdraw_asset_should_work_2() {
 		assert_eq!(ParticipantExtends::<Test>::get(0, ALICE), Some(participant_extend));
 	});
 }
+
+#[test]
+fn top_up_pool_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(FarmExtend::create_pool(
+			Origin::signed(ALICE),
+			DOT,
+			100,
+			1100,
+			1_000_000_000,
+			DICO
+		));
+
+		assert_noop!(
+			FarmExtend::top_up_pool(Origin::signed(BOB), 0, 2100),
+			Error::<Test>::NotPoolExtendOwner
+		);
+		assert_noop!(
+			FarmExtend::top_up_pool(Origin::signed(ALICE), 0, 1000),
+			Error::<Test>::InvalidBlockConfigure
+		);
+
+		assert_ok!(FarmExtend::top_up_pool(Origin::signed(ALICE), 0, 2100));
+		let currency_amount: Balance = (2100 - 100) * 1_000_000_000;
+		let pool_extend_info = PoolExtendInfo::new(DOT, currency_amount, ALICE, 100, 2100, 1_000_000_000, 100, DICO);
+		assert_eq!(PoolExtends::<Test>::get(0), Some(pool_extend_info));
+		expect_events(vec![Event::PoolExtendToppedUp(0, 2100, 1000 * 1_000_000_000).into()]);
+
+		let module_id_account = FarmExtend::account_id();
+		assert_eq!(Currency::free_balance(DOT, &module_id_account), currency_amount);
+
+		System::set_block_number(2100);
+		assert_noop!(
+			FarmExtend::top_up_pool(Origin::signed(ALICE), 0, 3100),
+			Error::<Test>::PoolExtendEnded
+		);
+	});
+}
+
+#[test]
+fn update_reward_per_block_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(FarmExtend::create_pool(
+			Origin::signed(ALICE),
+			DOT,
+			100,
+			1100,
+			1_000_000_000,
+			DICO
+		));
+		assert_ok!(FarmExtend::deposit_asset(Origin::signed(BOB), 0, 1000_000_000_000));
+
+		System::set_block_number(600);
+		assert_ok!(FarmExtend::update_reward_per_block(
+			Origin::signed(ALICE),
+			0,
+			500_000_000
+		));
+
+		// The 500 blocks left are paid at the new rate, the difference is refunded.
+		let currency_amount: Balance = 500 * 1_000_000_000 + 500 * 500_000_000;
+		let mut pool_extend_info = PoolExtendInfo::new(DOT, currency_amount, ALICE, 100, 1100, 500_000_000, 600, DICO);
+		pool_extend_info.total_stake_amount = 1000_000_000_000;
+		pool_extend_info.acc_reward_per_share = 500_000_000_000;
+		assert_eq!(PoolExtends::<Test>::get(0), Some(pool_extend_info));
+		expect_events(vec![Event::PoolExtendRewardPerBlockUpdated(0, 500_000_000).into()]);
+
+		let module_id_account = FarmExtend::account_id();
+		assert_eq!(Currency::free_balance(DOT, &module_id_account), currency_amount);
+
+		System::set_block_number(1100);
+		assert_ok!(FarmExtend::withdraw_asset(Origin::signed(BOB), 0, 0));
+		assert_eq!(
+			Currency::free_balance(DOT, &BOB),
+			DEFAULT_ASSET_AMOUNT + currency_amount
+		);
+		assert_eq!(Currency::free_balance(DOT, &module_id_account), 0);
+	});
+}
+
+#[test]
+fn cancel_pool_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(FarmExtend::create_pool(
+			Origin::signed(ALICE),
+			DOT,
+			100,
+			1100,
+			1_000_000_000,
+			DICO
+		));
+
+		// Nobody stakes during the first 100 blocks.
+		System::set_block_number(200);
+		assert_ok!(FarmExtend::deposit_asset(Origin::signed(BOB), 0, 1000_000_000_000));
+		assert_eq!(FarmExtend::unallocated_reward(0), 100 * 1_000_000_000);
+
+		System::set_block_number(600);
+		assert_noop!(
+			FarmExtend::cancel_pool(Origin::signed(BOB), 0),
+			Error::<Test>::NotPoolExtendOwner
+		);
+		assert_ok!(FarmExtend::cancel_pool(Origin::signed(ALICE), 0));
+
+		let refund_amount: Balance = (100 + 500) * 1_000_000_000;
+		expect_events(vec![Event::PoolExtendCancelled(0, refund_amount).into()]);
+		assert_eq!(FarmExtend::unallocated_reward(0), 0);
+		assert_eq!(
+			Currency::free_balance(DOT, &ALICE),
+			DEFAULT_ASSET_AMOUNT - 1000 * 1_000_000_000 + refund_amount
+		);
+
+		let pool_extend = PoolExtends::<Test>::get(0).unwrap();
+		assert_eq!(pool_extend.end_block, 600);
+		assert_eq!(pool_extend.currency_amount, 400 * 1_000_000_000);
+
+		// Stakers keep the rewards earned before the cancellation.
+		System::set_block_number(1100);
+		assert_ok!(FarmExtend::withdraw_asset(Origin::signed(BOB), 0, 1000_000_000_000));
+		assert_eq!(
+			Currency::free_balance(DOT, &BOB),
+			DEFAULT_ASSET_AMOUNT + 400 * 1_000_000_000
+		);
+		assert_eq!(Currency::free_balance(DICO, &BOB), DEFAULT_ASSET_AMOUNT);
+
+		let module_id_account = FarmExtend::account_id();
+		assert_eq!(Currency::free_balance(DOT, &module_id_account), 0);
+	});
+}
+
+#[test]
+fn pending_reward_and_positions_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(FarmExtend::create_pool(
+			Origin::signed(ALICE),
+			DOT,
+			100,
+			1100,
+			1_000_000_000,
+			DICO
+		));
+		assert_ok!(FarmExtend::create_pool(
+			Origin::signed(ALICE),
+			USDT,
+			100,
+			200,
+			1_000_000_000,
+			DICO
+		));
+
+		assert_ok!(FarmExtend::deposit_asset(Origin::signed(BOB), 0, 1000_000_000_000));
+		assert_ok!(FarmExtend::deposit_asset(Origin::signed(ALICE), 0, 1000_000_000_000));
+
+		System::set_block_number(300);
+		// (300 - 100) * 1000000000 / 2
+		assert_eq!(FarmExtend::get_pending_reward(BOB, 0), 100_000_000_000);
+		assert_eq!(FarmExtend::get_pending_reward(BOB, 1), 0);
+
+		let active_pools = FarmExtend::get_active_pools();
+		assert_eq!(active_pools.len(), 1);
+		assert_eq!(active_pools[0].0, 0);
+
+		assert_eq!(
+			FarmExtend::get_account_positions(BOB),
+			vec![(0, ParticipantExtend::new(1000_000_000_000, 0), 100_000_000_000)]
+		);
+		assert_eq!(FarmExtend::get_account_positions(DAVE), vec![]);
+	});
+}
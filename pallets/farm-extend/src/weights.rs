@@ -30,6 +30,9 @@ pub trait WeightInfo {
 	fn create_pool() -> Weight;
 	fn deposit_asset() -> Weight;
 	fn withdraw_asset() -> Weight;
+	fn top_up_pool() -> Weight;
+	fn update_reward_per_block() -> Weight;
+	fn cancel_pool() -> Weight;
 }
 
 /// Weights for pallet_farm_extend using the Substrate node and recommended hardware.
@@ -56,6 +59,23 @@ impl<T: frame_system::Config> WeightInfo for DicoWeight<T> {
 	fn withdraw_asset() -> Weight {
 		Weight::from_ref_time(20_0000_0000)
 	}
+	// Storage: FarmExtend PoolExtends (r:1 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	fn top_up_pool() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: FarmExtend PoolExtends (r:1 w:1)
+	// Storage: FarmExtend UnallocatedRewards (r:1 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	fn update_reward_per_block() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: FarmExtend PoolExtends (r:1 w:1)
+	// Storage: FarmExtend UnallocatedRewards (r:1 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	fn cancel_pool() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
 }
 
 // For backwards compatibility and tests
@@ -81,4 +101,21 @@ impl WeightInfo for () {
 	fn withdraw_asset() -> Weight {
 		Weight::from_ref_time(20_0000_0000)
 	}
+	// Storage: FarmExtend PoolExtends (r:1 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	fn top_up_pool() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: FarmExtend PoolExtends (r:1 w:1)
+	// Storage: FarmExtend UnallocatedRewards (r:1 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	fn update_reward_per_block() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: FarmExtend PoolExtends (r:1 w:1)
+	// Storage: FarmExtend UnallocatedRewards (r:1 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	fn cancel_pool() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
 }
@@ -2,6 +2,9 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use pallet_farm::{Participant, PoolRewardInfo};
+use sp_std::vec::Vec;
+
 sp_api::decl_runtime_apis! {
 	pub trait FarmApi<AccountId, PoolId, Balance> where
 		AccountId: codec::Codec,
@@ -9,5 +12,9 @@ sp_api::decl_runtime_apis! {
 		Balance: codec::Codec,
 	{
 		fn get_participant_reward(account: AccountId, pid: PoolId) -> Balance;
+
+		fn get_account_positions(account: AccountId) -> Vec<(PoolId, Participant, Balance)>;
+
+		fn get_pool_reward_infos() -> Vec<(PoolId, PoolRewardInfo)>;
 	}
 }
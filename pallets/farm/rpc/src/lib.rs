@@ -9,6 +9,7 @@ use jsonrpsee::{
 	proc_macros::rpc,
 	types::error::{CallError, ErrorCode, ErrorObject},
 };
+use pallet_farm::{Participant, PoolRewardInfo};
 use sc_rpc_api::DenyUnsafe;
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
@@ -26,6 +27,12 @@ pub use pallet_farm_rpc_runtime_api::FarmApi as FarmRuntimeApi;
 pub trait FarmApi<AccountId, PoolId, Balance> {
 	#[method(name = "farm_getParticipantReward")]
 	fn get_participant_reward(&self, account: AccountId, pid: PoolId) -> RpcResult<NumberOrHex>;
+
+	#[method(name = "farm_getAccountPositions")]
+	fn get_account_positions(&self, account: AccountId) -> RpcResult<Vec<(PoolId, Participant, NumberOrHex)>>;
+
+	#[method(name = "farm_getPoolRewardInfos")]
+	fn get_pool_reward_infos(&self) -> RpcResult<Vec<(PoolId, PoolRewardInfo)>>;
 }
 
 /// A struct that implements the [`FarmApi`].
@@ -68,8 +75,8 @@ where
 	Block: BlockT,
 	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
 	C::Api: FarmRuntimeApi<Block, AccountId, PoolId, Balance>,
-	AccountId: Codec,
-	PoolId: Codec,
+	AccountId: Codec + Send + Sync + 'static,
+	PoolId: Codec + Send + Sync + 'static,
 	Balance: Codec + MaybeDisplay + Copy + TryInto<NumberOrHex> + std::marker::Send + 'static,
 {
 	fn get_participant_reward(&self, account: AccountId, pid: PoolId) -> RpcResult<NumberOrHex> {
@@ -93,4 +100,47 @@ where
 			)))
 		})
 	}
+
+	fn get_account_positions(&self, account: AccountId) -> RpcResult<Vec<(PoolId, Participant, NumberOrHex)>> {
+		let api = self.client.runtime_api();
+		let best = self.client.info().best_hash;
+		let at = BlockId::hash(best);
+
+		let positions = api.get_account_positions(&at, account).map_err(|e| {
+			CallError::Custom(ErrorObject::owned(
+				Error::RuntimeError.into(),
+				"Unable to query account positions.",
+				Some(format!("{:?}", e)),
+			))
+		})?;
+
+		positions
+			.into_iter()
+			.map(|(pid, participant, pending_reward)| {
+				let pending_reward = pending_reward.try_into().map_err(|_| {
+					JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+						ErrorCode::InvalidParams.code(),
+						format!("doesn't fit in NumberOrHex representation"),
+						None::<()>,
+					)))
+				})?;
+
+				Ok((pid, participant, pending_reward))
+			})
+			.collect()
+	}
+
+	fn get_pool_reward_infos(&self) -> RpcResult<Vec<(PoolId, PoolRewardInfo)>> {
+		let api = self.client.runtime_api();
+		let best = self.client.info().best_hash;
+		let at = BlockId::hash(best);
+
+		api.get_pool_reward_infos(&at).map_err(|e| {
+			JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+				Error::RuntimeError.into(),
+				"Unable to query pool reward infos.",
+				Some(format!("{:?}", e)),
+			)))
+		})
+	}
 }
@@ -119,4 +119,25 @@ benchmarks! {
 		let participant = Participant::new(amount, 875012500000000000);
 		assert_eq!(Participants::<T>::get(pool_id, caller).unwrap(), participant);
 	}
+
+	insert_emission_epoch {
+		let rate = EmissionRate::Constant(Balance::from(10000u32));
+	}: _(RawOrigin::Root, T::BlockNumber::from(10000u32), rate)
+	verify {
+		assert_eq!(EmissionSchedule::<T>::get().into_inner(), vec![EmissionEpoch::new(10000, rate)]);
+	}
+
+	remove_emission_epoch {
+		let rate = EmissionRate::Constant(Balance::from(10000u32));
+		Farm::<T>::insert_emission_epoch(RawOrigin::Root.into(), T::BlockNumber::from(10000u32), rate)?;
+	}: _(RawOrigin::Root, T::BlockNumber::from(10000u32))
+	verify {
+		assert!(EmissionSchedule::<T>::get().is_empty());
+	}
+
+	set_emission_cap {
+	}: _(RawOrigin::Root, Some(Balance::from(10000u32)))
+	verify {
+		assert_eq!(EmissionCap::<T>::get(), Some(Balance::from(10000u32)));
+	}
 }
@@ -28,12 +28,14 @@ use sp_runtime::{
 	traits::{AccountIdConversion, SaturatedConversion},
 	ArithmeticError,
 };
+use sp_std::vec::Vec;
 
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
 mod benchmarking;
 
+pub mod migrations;
 pub mod weights;
 
 use weights::WeightInfo;
@@ -79,6 +81,95 @@ impl PoolInfo {
 	}
 }
 
+/// The inputs needed to estimate the TVL and APR of a mining pool.
+#[derive(Encode, Decode, Default, Eq, PartialEq, Copy, Clone, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct PoolRewardInfo {
+	pub currency_id: AssetId,
+	/// The amount of liquid assets staked in the pool.
+	pub total_amount: Balance,
+	pub alloc_point: u128,
+	/// The number of DICOs the pool receives for the current block.
+	pub reward_per_block: Balance,
+}
+
+/// The per-block emission rule of an emission epoch.
+#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum EmissionRate {
+	/// Every block of the epoch produces the same amount of DICO.
+	Constant(Balance),
+	/// The amount produced by each block decreases linearly from `from` to `to` over
+	/// `duration` blocks, and stays at `to` until the next epoch starts.
+	LinearDecay {
+		from: Balance,
+		to: Balance,
+		duration: BlockNumber,
+	},
+}
+
+impl EmissionRate {
+	/// Calculate the number of DICOs produced by the first `blocks` blocks of an epoch.
+	fn cumulative(&self, blocks: BlockNumber) -> sp_std::result::Result<U256, ArithmeticError> {
+		match *self {
+			EmissionRate::Constant(per_block) => to_u256!(per_block)
+				.checked_mul(to_u256!(blocks))
+				.ok_or(ArithmeticError::Overflow),
+			EmissionRate::LinearDecay { from, to, duration } => {
+				let decay_blocks = blocks.min(duration);
+				let (x, d, from_hp, to_hp) = to_u256!(decay_blocks, duration, from, to);
+				let double_d = d.checked_mul(U256::from(2u32)).ok_or(ArithmeticError::Overflow)?;
+
+				// sum(from - (from - to) * k / d) for k in 0..x
+				let decay = from_hp
+					.checked_mul(x)
+					.ok_or(ArithmeticError::Overflow)?
+					.checked_mul(double_d)
+					.ok_or(ArithmeticError::Overflow)?
+					.checked_sub(
+						from_hp
+							.checked_sub(to_hp)
+							.ok_or(ArithmeticError::Underflow)?
+							.checked_mul(x)
+							.ok_or(ArithmeticError::Overflow)?
+							.checked_mul(x.saturating_sub(U256::one()))
+							.ok_or(ArithmeticError::Overflow)?,
+					)
+					.ok_or(ArithmeticError::Underflow)?
+					.checked_div(double_d)
+					.ok_or(ArithmeticError::DivisionByZero)?;
+
+				let tail = to_hp
+					.checked_mul(to_u256!(blocks - decay_blocks))
+					.ok_or(ArithmeticError::Overflow)?;
+
+				decay.checked_add(tail).ok_or(ArithmeticError::Overflow)
+			}
+		}
+	}
+
+	fn is_valid(&self) -> bool {
+		match *self {
+			EmissionRate::Constant(_) => true,
+			EmissionRate::LinearDecay { from, to, duration } => from >= to && !duration.is_zero(),
+		}
+	}
+}
+
+/// An emission epoch lasts from `start_block` until the start block of the next epoch.
+#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct EmissionEpoch {
+	pub start_block: BlockNumber,
+	pub rate: EmissionRate,
+}
+
+impl EmissionEpoch {
+	pub fn new(start_block: BlockNumber, rate: EmissionRate) -> Self {
+		Self { start_block, rate }
+	}
+}
+
 // Re-export pallet items so that they can be accessed from the crate namespace.
 pub use pallet::*;
 
@@ -86,12 +177,19 @@ pub use pallet::*;
 pub mod pallet {
 	use super::*;
 
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			migrations::v1::migrate::<T>()
+		}
+	}
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
@@ -114,6 +212,10 @@ pub mod pallet {
 		#[pallet::constant]
 		type NativeAssetId: Get<AssetId>;
 
+		/// The maximum number of epochs in the emission schedule.
+		#[pallet::constant]
+		type MaxEmissionEpochs: Get<u32>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -134,6 +236,16 @@ pub mod pallet {
 		InsufficientWithdrawAmount,
 		/// No pool id available.
 		NoPoolIdAvailable,
+		/// The emission rate is invalid.
+		InvalidEmissionRate,
+		/// The emission epoch has already started.
+		EmissionEpochStarted,
+		/// The emission epoch does not exist.
+		EmissionEpochNotFind,
+		/// The emission schedule is full.
+		TooManyEmissionEpochs,
+		/// The emission cap is lower than the DICOs already emitted.
+		EmissionCapTooLow,
 	}
 
 	#[pallet::event]
@@ -153,6 +265,12 @@ pub mod pallet {
 		PoolCreated(T::PoolId),
 		/// The allocated points of each designated mining pool was updated. [pool id, alloc point]
 		PoolAllocPointUpdated(T::PoolId, u128),
+		/// An emission epoch was inserted into the emission schedule. [start block, emission rate]
+		EmissionEpochInserted(T::BlockNumber, EmissionRate),
+		/// An emission epoch was removed from the emission schedule. [start block]
+		EmissionEpochRemoved(T::BlockNumber),
+		/// The hard cap on total emissions was set. [dico amount]
+		EmissionCapIsSet(Option<Balance>),
 	}
 
 	#[pallet::storage]
@@ -168,6 +286,11 @@ pub mod pallet {
 	pub type Participants<T: Config> =
 		StorageDoubleMap<_, Twox64Concat, T::PoolId, Twox64Concat, T::AccountId, Participant, OptionQuery>;
 
+	/// The mining pools in which the account has staked liquid assets.
+	#[pallet::storage]
+	#[pallet::getter(fn pools_of)]
+	pub type PoolsOf<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, Vec<T::PoolId>, ValueQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn dico_per_block)]
 	pub type DicoPerBlock<T: Config> = StorageValue<_, Balance, ValueQuery>;
@@ -184,6 +307,22 @@ pub mod pallet {
 	#[pallet::getter(fn halving_period)]
 	pub type HalvingPeriod<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
 
+	/// Emission epochs sorted by start block. Before the first epoch starts,
+	/// rewards follow `DicoPerBlock` and `HalvingPeriod`.
+	#[pallet::storage]
+	#[pallet::getter(fn emission_schedule)]
+	pub type EmissionSchedule<T: Config> = StorageValue<_, BoundedVec<EmissionEpoch, T::MaxEmissionEpochs>, ValueQuery>;
+
+	/// The maximum number of DICOs the farm is allowed to mint.
+	#[pallet::storage]
+	#[pallet::getter(fn emission_cap)]
+	pub type EmissionCap<T: Config> = StorageValue<_, Balance, OptionQuery>;
+
+	/// The number of DICOs minted by the farm since the emission cap was introduced.
+	#[pallet::storage]
+	#[pallet::getter(fn total_emission)]
+	pub type TotalEmission<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Set the mining reward halving cycle,the unit is the number of blocks.
@@ -368,6 +507,14 @@ pub mod pallet {
 				.checked_div(to_u256!(1e12 as u64))
 				.ok_or(ArithmeticError::Overflow)?)?;
 
+			if participant.amount > Balance::zero() {
+				PoolsOf::<T>::mutate(&who, |pools| {
+					if !pools.contains(&pool_id) {
+						pools.push(pool_id);
+					}
+				});
+			}
+
 			Pools::<T>::insert(pool_id, pool);
 			Participants::<T>::insert(pool_id, &who, participant);
 			Self::deposit_event(Event::LpDeposited(who, pool_id, amount));
@@ -425,12 +572,103 @@ pub mod pallet {
 				.checked_div(to_u256!(1e12 as u64))
 				.ok_or(ArithmeticError::Overflow)?)?;
 
+			if participant.amount.is_zero() {
+				PoolsOf::<T>::mutate(&who, |pools| pools.retain(|pid| *pid != pool_id));
+			}
+
 			Pools::<T>::insert(pool_id, pool);
 			Participants::<T>::insert(pool_id, &who, participant);
 			Self::deposit_event(Event::LpWithdrawn(who, pool_id, amount));
 
 			Ok(().into())
 		}
+
+		/// Insert an emission epoch starting at `start_block` into the emission schedule.
+		///
+		/// The epoch lasts until the next epoch starts. An existing epoch with the same start
+		/// block is replaced. Only epochs that have not started yet can be inserted, so the
+		/// rewards already produced are never changed.
+		///
+		/// Emits `EmissionEpochInserted` event when successful.
+		#[pallet::weight(< T as Config >::WeightInfo::insert_emission_epoch())]
+		#[transactional]
+		pub fn insert_emission_epoch(
+			origin: OriginFor<T>,
+			start_block: T::BlockNumber,
+			rate: EmissionRate,
+		) -> DispatchResultWithPostInfo {
+			T::FounderSetOrigin::ensure_origin(origin)?;
+			ensure!(rate.is_valid(), Error::<T>::InvalidEmissionRate);
+
+			let block_number = frame_system::pallet::Pallet::<T>::block_number();
+			ensure!(start_block > block_number, Error::<T>::EmissionEpochStarted);
+
+			let epoch = EmissionEpoch::new(start_block.saturated_into(), rate);
+			EmissionSchedule::<T>::try_mutate(|schedule| -> DispatchResult {
+				match schedule.binary_search_by_key(&epoch.start_block, |e| e.start_block) {
+					Ok(index) => {
+						if let Some(e) = schedule.get_mut(index) {
+							*e = epoch;
+						}
+					}
+					Err(index) => schedule
+						.try_insert(index, epoch)
+						.map_err(|_| Error::<T>::TooManyEmissionEpochs)?,
+				}
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::EmissionEpochInserted(start_block, rate));
+
+			Ok(().into())
+		}
+
+		/// Remove an emission epoch that has not started yet from the emission schedule.
+		///
+		/// Emits `EmissionEpochRemoved` event when successful.
+		#[pallet::weight(< T as Config >::WeightInfo::remove_emission_epoch())]
+		#[transactional]
+		pub fn remove_emission_epoch(origin: OriginFor<T>, start_block: T::BlockNumber) -> DispatchResultWithPostInfo {
+			T::FounderSetOrigin::ensure_origin(origin)?;
+
+			let block_number = frame_system::pallet::Pallet::<T>::block_number();
+			ensure!(start_block > block_number, Error::<T>::EmissionEpochStarted);
+
+			let start_block_hp: BlockNumber = start_block.saturated_into();
+			EmissionSchedule::<T>::try_mutate(|schedule| -> DispatchResult {
+				let index = schedule
+					.binary_search_by_key(&start_block_hp, |e| e.start_block)
+					.map_err(|_| Error::<T>::EmissionEpochNotFind)?;
+				schedule.remove(index);
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::EmissionEpochRemoved(start_block));
+
+			Ok(().into())
+		}
+
+		/// Set the hard cap on the total number of DICOs minted by the farm.
+		///
+		/// Once the cap is reached, the mining pools stop producing rewards.
+		/// `None` removes the cap.
+		///
+		/// Emits `EmissionCapIsSet` event when successful.
+		#[pallet::weight(< T as Config >::WeightInfo::set_emission_cap())]
+		#[transactional]
+		pub fn set_emission_cap(origin: OriginFor<T>, cap: Option<Balance>) -> DispatchResultWithPostInfo {
+			T::FounderSetOrigin::ensure_origin(origin)?;
+
+			Self::mass_update_pools()?;
+
+			if let Some(cap) = cap {
+				ensure!(cap >= Self::total_emission(), Error::<T>::EmissionCapTooLow);
+			}
+			EmissionCap::<T>::set(cap);
+			Self::deposit_event(Event::EmissionCapIsSet(cap));
+
+			Ok(().into())
+		}
 	}
 }
 
@@ -491,10 +729,72 @@ impl<T: Config> Pallet<T> {
 	/// Calculate the number of DICOs produced from the last reward block to the current block
 	/// cycle.
 	fn get_dico_block_reward(last_reward_block: BlockNumber) -> sp_std::result::Result<Balance, ArithmeticError> {
+		let block_number: BlockNumber = frame_system::pallet::Pallet::<T>::block_number().saturated_into();
+
+		Self::get_block_reward_between(last_reward_block, block_number)
+	}
+
+	/// Calculate the number of DICOs produced from `last_reward_block` to `block_number`.
+	fn get_block_reward_between(
+		last_reward_block: BlockNumber,
+		block_number: BlockNumber,
+	) -> sp_std::result::Result<Balance, ArithmeticError> {
+		if block_number <= last_reward_block {
+			return to_balance!(0);
+		}
+
+		// Before the first emission epoch starts, rewards follow the halving parameters.
+		let schedule = Self::emission_schedule();
+		let schedule_start = match schedule.first() {
+			Some(epoch) => epoch.start_block,
+			None => return Self::get_halving_block_reward(last_reward_block, block_number),
+		};
+
+		let mut block_reward = U256::zero();
+		if last_reward_block < schedule_start {
+			let halving_reward = Self::get_halving_block_reward(last_reward_block, block_number.min(schedule_start))?;
+			block_reward = to_u256!(halving_reward);
+		}
+
+		// Only the epochs overlapping (last_reward_block, block_number] are visited,
+		// so the cost grows with the number of epochs rather than the number of blocks.
+		for (index, epoch) in schedule.iter().enumerate() {
+			if epoch.start_block >= block_number {
+				break;
+			}
+			let end_block = schedule
+				.get(index + 1)
+				.map(|next| next.start_block)
+				.unwrap_or(BlockNumber::MAX);
+			if end_block <= last_reward_block {
+				continue;
+			}
+
+			let from = last_reward_block.max(epoch.start_block) - epoch.start_block;
+			let to = block_number.min(end_block) - epoch.start_block;
+			let epoch_reward = epoch
+				.rate
+				.cumulative(to)?
+				.checked_sub(epoch.rate.cumulative(from)?)
+				.ok_or(ArithmeticError::Underflow)?;
+
+			block_reward = block_reward
+				.checked_add(epoch_reward)
+				.ok_or(ArithmeticError::Overflow)?;
+		}
+
+		to_balance!(block_reward)
+	}
+
+	/// Calculate the number of DICOs produced from `last_reward_block` to `block_number`
+	/// according to `DicoPerBlock` and `HalvingPeriod`.
+	fn get_halving_block_reward(
+		last_reward_block: BlockNumber,
+		block_number: BlockNumber,
+	) -> sp_std::result::Result<Balance, ArithmeticError> {
 		let mut block_reward = U256::zero();
 		let halving_period: BlockNumber = Self::halving_period().saturated_into();
 		let start_block: BlockNumber = Self::start_block().saturated_into();
-		let block_number: BlockNumber = frame_system::pallet::Pallet::<T>::block_number().saturated_into();
 
 		if block_number <= last_reward_block {
 			return to_balance!(0);
@@ -554,11 +854,13 @@ impl<T: Config> Pallet<T> {
 			to_u256!(pool.acc_dico_per_share)
 		} else {
 			let block_reward = Self::get_dico_block_reward(pool.last_reward_block)?;
-			let dico_reward = to_u256!(block_reward)
-				.checked_mul(to_u256!(pool.alloc_point))
-				.ok_or(ArithmeticError::Overflow)?
-				.checked_div(to_u256!(Self::total_alloc_point()))
-				.ok_or(ArithmeticError::Overflow)?;
+			let dico_reward = Self::cap_emission(
+				to_u256!(block_reward)
+					.checked_mul(to_u256!(pool.alloc_point))
+					.ok_or(ArithmeticError::Overflow)?
+					.checked_div(to_u256!(Self::total_alloc_point()))
+					.ok_or(ArithmeticError::Overflow)?,
+			);
 
 			let module_account_id = Self::account_id();
 			let lp_supply = T::Currency::free_balance(pool.currency_id, &module_account_id);
@@ -594,6 +896,52 @@ impl<T: Config> Pallet<T> {
 		Balance::zero()
 	}
 
+	/// All positions of the account, together with the DICOs it can withdraw from each pool.
+	pub fn get_account_positions(account: T::AccountId) -> Vec<(T::PoolId, Participant, Balance)> {
+		Self::pools_of(&account)
+			.into_iter()
+			.filter_map(|pid| {
+				let participant = Participants::<T>::get(pid, &account)?;
+				let pending_reward = Self::get_participant_reward(account.clone(), pid);
+
+				Some((pid, participant, pending_reward))
+			})
+			.collect()
+	}
+
+	/// The TVL and APR inputs of all mining pools.
+	pub fn get_pool_reward_infos() -> Vec<(T::PoolId, PoolRewardInfo)> {
+		let block_number: BlockNumber = frame_system::pallet::Pallet::<T>::block_number().saturated_into();
+		let total_alloc_point = Self::total_alloc_point();
+		let block_reward = Self::get_block_reward_between(block_number, block_number.saturating_add(1))
+			.map(|block_reward| Self::cap_emission(to_u256!(block_reward)))
+			.unwrap_or_default();
+
+		Pools::<T>::iter()
+			.map(|(pid, pool)| {
+				let reward_per_block = if total_alloc_point.is_zero() {
+					Balance::zero()
+				} else {
+					block_reward
+						.checked_mul(to_u256!(pool.alloc_point))
+						.and_then(|reward| reward.checked_div(to_u256!(total_alloc_point)))
+						.and_then(|reward| to_balance!(reward).ok())
+						.unwrap_or_default()
+				};
+
+				(
+					pid,
+					PoolRewardInfo {
+						currency_id: pool.currency_id,
+						total_amount: pool.total_amount,
+						alloc_point: pool.alloc_point,
+						reward_per_block,
+					},
+				)
+			})
+			.collect()
+	}
+
 	fn update_pool(pid: &T::PoolId) -> sp_std::result::Result<(), DispatchErrorWithPostInfo> {
 		Pools::<T>::try_mutate(
 			pid,
@@ -622,14 +970,22 @@ impl<T: Config> Pallet<T> {
 				// According to the mining weight value of the mining pool,
 				// the number of DICOs that the mining pool can allocate in this period is calculated.
 				let total_alloc_point = TotalAllocPoint::<T>::get();
-				let dico_reward = to_u256!(block_reward)
-					.checked_mul(to_u256!(pool.alloc_point))
-					.ok_or(ArithmeticError::Overflow)?
-					.checked_div(to_u256!(total_alloc_point))
-					.ok_or(ArithmeticError::Overflow)?;
+				// The reward is reduced once the emission cap is reached.
+				let dico_reward = Self::cap_emission(
+					to_u256!(block_reward)
+						.checked_mul(to_u256!(pool.alloc_point))
+						.ok_or(ArithmeticError::Overflow)?
+						.checked_div(to_u256!(total_alloc_point))
+						.ok_or(ArithmeticError::Overflow)?,
+				);
 
 				// Call the minting interface to mint DICO for the module.
-				T::Currency::deposit(native_asset, &module_account_id, to_balance!(dico_reward)?)?;
+				let dico_reward_amount = to_balance!(dico_reward)?;
+				T::Currency::deposit(native_asset, &module_account_id, dico_reward_amount)?;
+				let total_emission = Self::total_emission()
+					.checked_add(dico_reward_amount)
+					.ok_or(ArithmeticError::Overflow)?;
+				TotalEmission::<T>::put(total_emission);
 				let lp_supply_hp = to_u256!(lp_supply);
 
 				pool.acc_dico_per_share = to_balance!(to_u256!(pool.acc_dico_per_share)
@@ -649,6 +1005,15 @@ impl<T: Config> Pallet<T> {
 		)
 	}
 
+	/// Limit the number of DICOs to be minted so that the total emission never exceeds
+	/// `EmissionCap`.
+	fn cap_emission(dico_reward: U256) -> U256 {
+		match Self::emission_cap() {
+			Some(cap) => dico_reward.min(to_u256!(cap.saturating_sub(Self::total_emission()))),
+			None => dico_reward,
+		}
+	}
+
 	/// Update the reward variables of all pools. Be careful of gas consumption!
 	fn mass_update_pools() -> sp_std::result::Result<(), DispatchErrorWithPostInfo> {
 		for (pid, _) in Pools::<T>::iter() {
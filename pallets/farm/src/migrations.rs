@@ -0,0 +1,27 @@
+use super::*;
+use frame_support::{log, traits::StorageVersion};
+
+pub mod v1 {
+	use super::*;
+
+	/// Build the `PoolsOf` index from the existing participants.
+	pub fn migrate<T: Config>() -> Weight {
+		if StorageVersion::get::<Pallet<T>>() >= 1 {
+			return Weight::zero();
+		}
+
+		log::info!("🌾 Farm module start migrate ✅",);
+
+		let mut reads_writes = 0u64;
+		Participants::<T>::iter().for_each(|(pid, account, participant)| {
+			reads_writes = reads_writes.saturating_add(1);
+			if participant.amount > Balance::zero() {
+				PoolsOf::<T>::append(account, pid);
+			}
+		});
+
+		StorageVersion::new(1).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(reads_writes, reads_writes.saturating_add(1))
+	}
+}
@@ -56,6 +56,7 @@ parameter_types! {
 	pub const SS58Prefix: u8 = 63;
 	pub const FarmPalletId: PalletId = PalletId(*b"dico/fam");
 	pub const DICOAssetId: AssetId = 0;
+	pub const MaxEmissionEpochs: u32 = 10;
 }
 
 impl system::Config for Test {
@@ -113,6 +114,7 @@ impl Config for Test {
 	type Currency = Currency;
 	type PalletId = FarmPalletId;
 	type NativeAssetId = DICOAssetId;
+	type MaxEmissionEpochs = MaxEmissionEpochs;
 	type PoolId = u32;
 	type WeightInfo = ();
 	type FounderSetOrigin = EnsureSignedBy<One, u64>;
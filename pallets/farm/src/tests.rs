@@ -7,7 +7,7 @@ pub use crate::mock::{
 	Currency, Event as TestEvent, ExtBuilder, Farm, Origin, System, Test, ALICE, BOB, DEFAULT_ASSET_AMOUNT, DICO, DOT,
 	PDOTUSDT, USDT,
 };
-use frame_support::assert_ok;
+use frame_support::{assert_noop, assert_ok};
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
 	let mut ext = ExtBuilder::default().build();
@@ -347,3 +347,193 @@ fn withdraw_lp_should_work2() {
 		assert_eq!(Currency::free_balance(0, &module_id_account), 0);
 	});
 }
+
+#[test]
+fn insert_emission_epoch_should_work() {
+	new_test_ext().execute_with(|| {
+		let decay = EmissionRate::LinearDecay {
+			from: 1000,
+			to: 100,
+			duration: 100,
+		};
+
+		assert_ok!(Farm::insert_emission_epoch(Origin::signed(ALICE), 200, decay));
+		assert_ok!(Farm::insert_emission_epoch(
+			Origin::signed(ALICE),
+			100,
+			EmissionRate::Constant(1000)
+		));
+		expect_events(vec![
+			Event::EmissionEpochInserted(100, EmissionRate::Constant(1000)).into()
+		]);
+
+		assert_eq!(
+			Farm::emission_schedule().into_inner(),
+			vec![
+				EmissionEpoch::new(100, EmissionRate::Constant(1000)),
+				EmissionEpoch::new(200, decay)
+			]
+		);
+
+		assert_noop!(
+			Farm::insert_emission_epoch(Origin::signed(ALICE), 1, EmissionRate::Constant(1000)),
+			Error::<Test>::EmissionEpochStarted
+		);
+		assert_noop!(
+			Farm::insert_emission_epoch(
+				Origin::signed(ALICE),
+				300,
+				EmissionRate::LinearDecay {
+					from: 100,
+					to: 1000,
+					duration: 100
+				}
+			),
+			Error::<Test>::InvalidEmissionRate
+		);
+
+		assert_ok!(Farm::remove_emission_epoch(Origin::signed(ALICE), 100));
+		assert_eq!(
+			Farm::emission_schedule().into_inner(),
+			vec![EmissionEpoch::new(200, decay)]
+		);
+		assert_noop!(
+			Farm::remove_emission_epoch(Origin::signed(ALICE), 100),
+			Error::<Test>::EmissionEpochNotFind
+		);
+	});
+}
+
+#[test]
+fn emission_schedule_reward_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Farm::set_dico_per_block(Origin::signed(ALICE), 5));
+		assert_ok!(Farm::insert_emission_epoch(
+			Origin::signed(ALICE),
+			100,
+			EmissionRate::Constant(10)
+		));
+		assert_ok!(Farm::insert_emission_epoch(
+			Origin::signed(ALICE),
+			200,
+			EmissionRate::LinearDecay {
+				from: 10,
+				to: 0,
+				duration: 10
+			}
+		));
+
+		// The halving parameters are used until the first epoch starts.
+		System::set_block_number(120);
+		assert_eq!(Farm::get_dico_block_reward(50), Ok(50 * 5 + 20 * 10));
+
+		System::set_block_number(150);
+		assert_eq!(Farm::get_dico_block_reward(100), Ok(500));
+
+		// 10 + 9 + ... + 1 during the decay, nothing afterwards.
+		System::set_block_number(215);
+		assert_eq!(Farm::get_dico_block_reward(190), Ok(100 + 55));
+
+		// Rewards of consecutive periods add up to the reward of the whole period.
+		System::set_block_number(205);
+		let first = Farm::get_dico_block_reward(190).unwrap();
+		System::set_block_number(215);
+		assert_eq!(first + Farm::get_dico_block_reward(205).unwrap(), 155);
+	});
+}
+
+#[test]
+fn emission_cap_should_work() {
+	new_test_ext().execute_with(|| {
+		let liquidity_id: AssetId = DOT;
+
+		assert_ok!(Farm::insert_emission_epoch(
+			Origin::signed(ALICE),
+			10,
+			EmissionRate::Constant(100)
+		));
+		assert_ok!(Farm::set_emission_cap(Origin::signed(ALICE), Some(1000)));
+		expect_events(vec![Event::EmissionCapIsSet(Some(1000)).into()]);
+
+		assert_ok!(Farm::create_pool(Origin::signed(ALICE), liquidity_id, 1000));
+		assert_ok!(Farm::deposit_lp(Origin::signed(ALICE), 0, 100_000_000_000_000));
+
+		System::set_block_number(15);
+		assert_ok!(Farm::withdraw_lp(Origin::signed(ALICE), 0, 0));
+		assert_eq!(Farm::total_emission(), 500);
+
+		System::set_block_number(100);
+		assert_eq!(Farm::get_participant_reward(ALICE, 0), 500);
+		assert_ok!(Farm::withdraw_lp(Origin::signed(ALICE), 0, 0));
+		assert_eq!(Farm::total_emission(), 1000);
+
+		System::set_block_number(200);
+		assert_eq!(Farm::get_participant_reward(ALICE, 0), 0);
+
+		assert_noop!(
+			Farm::set_emission_cap(Origin::signed(ALICE), Some(999)),
+			Error::<Test>::EmissionCapTooLow
+		);
+	});
+}
+
+#[test]
+fn account_positions_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Farm::set_dico_per_block(Origin::signed(ALICE), 100_000_000_000_000));
+		assert_ok!(Farm::create_pool(Origin::signed(ALICE), DOT, 1000));
+		assert_ok!(Farm::create_pool(Origin::signed(ALICE), USDT, 3000));
+
+		assert_ok!(Farm::deposit_lp(Origin::signed(ALICE), 0, 100_000_000_000_000));
+		assert_ok!(Farm::deposit_lp(Origin::signed(ALICE), 1, 100_000_000_000_000));
+		assert_ok!(Farm::deposit_lp(Origin::signed(ALICE), 1, 100_000_000_000_000));
+		assert_eq!(Farm::pools_of(ALICE), vec![0, 1]);
+
+		System::set_block_number(11);
+		assert_eq!(
+			Farm::get_account_positions(ALICE),
+			vec![
+				(
+					0,
+					Participant::new(100_000_000_000_000, 0),
+					Farm::get_participant_reward(ALICE, 0)
+				),
+				(
+					1,
+					Participant::new(200_000_000_000_000, 0),
+					Farm::get_participant_reward(ALICE, 1)
+				),
+			]
+		);
+		assert_eq!(Farm::get_participant_reward(ALICE, 0), 250_000_000_000_000);
+
+		let mut pool_reward_infos = Farm::get_pool_reward_infos();
+		pool_reward_infos.sort_by_key(|(pid, _)| *pid);
+		assert_eq!(
+			pool_reward_infos,
+			vec![
+				(
+					0,
+					PoolRewardInfo {
+						currency_id: DOT,
+						total_amount: 100_000_000_000_000,
+						alloc_point: 1000,
+						reward_per_block: 25_000_000_000_000,
+					}
+				),
+				(
+					1,
+					PoolRewardInfo {
+						currency_id: USDT,
+						total_amount: 200_000_000_000_000,
+						alloc_point: 3000,
+						reward_per_block: 75_000_000_000_000,
+					}
+				),
+			]
+		);
+
+		assert_ok!(Farm::withdraw_lp(Origin::signed(ALICE), 0, 100_000_000_000_000));
+		assert_eq!(Farm::pools_of(ALICE), vec![1]);
+	});
+}
@@ -34,6 +34,9 @@ pub trait WeightInfo {
 	fn update_pool_alloc_point() -> Weight;
 	fn deposit_lp() -> Weight;
 	fn withdraw_lp() -> Weight;
+	fn insert_emission_epoch() -> Weight;
+	fn remove_emission_epoch() -> Weight;
+	fn set_emission_cap() -> Weight;
 }
 
 /// Weights for pallet_farm using the Substrate node and recommended hardware.
@@ -83,6 +86,20 @@ impl<T: frame_system::Config> WeightInfo for DicoWeight<T> {
 	fn withdraw_lp() -> Weight {
 		Weight::from_ref_time(20_0000_0000)
 	}
+	// Storage: Farm EmissionSchedule (r:1 w:1)
+	fn insert_emission_epoch() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Farm EmissionSchedule (r:1 w:1)
+	fn remove_emission_epoch() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Farm Pools (r:1 w:1)
+	// Storage: Farm TotalEmission (r:1 w:1)
+	// Storage: Farm EmissionCap (r:1 w:1)
+	fn set_emission_cap() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
 }
 
 // For backwards compatibility and tests
@@ -131,4 +148,18 @@ impl WeightInfo for () {
 	fn withdraw_lp() -> Weight {
 		Weight::from_ref_time(20_0000_0000)
 	}
+	// Storage: Farm EmissionSchedule (r:1 w:1)
+	fn insert_emission_epoch() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Farm EmissionSchedule (r:1 w:1)
+	fn remove_emission_epoch() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Farm Pools (r:1 w:1)
+	// Storage: Farm TotalEmission (r:1 w:1)
+	// Storage: Farm EmissionCap (r:1 w:1)
+	fn set_emission_cap() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
 }
@@ -25,6 +25,7 @@ use jsonrpsee::{
 	types::error::{CallError, ErrorCode, ErrorObject},
 };
 
+use pallet_ico::{IcoDetail, IcoPosition, IcoStage};
 use pallet_ico_rpc_runtime_api::IcoAmountApi;
 use sc_rpc_api::DenyUnsafe;
 use sp_blockchain::{Error as ClientError, HeaderBackend};
@@ -36,7 +37,7 @@ use std::sync::Arc;
 
 /// Ico RPC method
 #[rpc(client, server)]
-pub trait IcoApi<AccountId, CurrencyId, Index, Balance> {
+pub trait IcoApi<AccountId, CurrencyId, Index, Balance, BlockNumber> {
 	#[method(name = "ico_canReleaseAmount")]
 	fn can_release_amount(&self, account: AccountId, currency_id: CurrencyId, index: Index) -> RpcResult<NumberOrHex>;
 	#[method(name = "ico_getRewardAmount")]
@@ -52,6 +53,23 @@ pub trait IcoApi<AccountId, CurrencyId, Index, Balance> {
 	) -> RpcResult<(NumberOrHex, NumberOrHex)>;
 	#[method(name = "ico_getTokenPrice")]
 	fn get_token_price(&self, currency_id: CurrencyId) -> RpcResult<NumberOrHex>;
+	#[method(name = "ico_getReferralEarnings")]
+	fn get_referral_earnings(&self, account: AccountId) -> RpcResult<Vec<(CurrencyId, Index, NumberOrHex)>>;
+	#[method(name = "ico_getIcoes")]
+	fn get_icoes(
+		&self,
+		stage: Option<IcoStage>,
+		start: u32,
+		limit: u32,
+	) -> RpcResult<Vec<IcoDetail<AccountId, BlockNumber, Balance>>>;
+	#[method(name = "ico_getIcoDetail")]
+	fn get_ico_detail(
+		&self,
+		currency_id: CurrencyId,
+		index: Index,
+	) -> RpcResult<Option<IcoDetail<AccountId, BlockNumber, Balance>>>;
+	#[method(name = "ico_getPortfolio")]
+	fn get_portfolio(&self, account: AccountId) -> RpcResult<Vec<IcoPosition<Balance>>>;
 }
 
 pub struct FullIco<C, B> {
@@ -87,18 +105,19 @@ impl From<Error> for i32 {
 }
 
 #[async_trait]
-impl<C, AccountId, CurrencyId, Index, Balance, Block> IcoApiServer<AccountId, CurrencyId, Index, Balance>
-	for FullIco<C, Block>
+impl<C, AccountId, CurrencyId, Index, Balance, BlockNumber, Block>
+	IcoApiServer<AccountId, CurrencyId, Index, Balance, BlockNumber> for FullIco<C, Block>
 where
 	C: sp_api::ProvideRuntimeApi<Block>,
 	C: HeaderBackend<Block>,
 	C: Send + Sync + 'static,
-	C::Api: IcoAmountApi<Block, AccountId, CurrencyId, Index, Balance>,
+	C::Api: IcoAmountApi<Block, AccountId, CurrencyId, Index, Balance, BlockNumber>,
 	Block: traits::Block,
 	AccountId: Clone + std::fmt::Display + Codec,
 	Index: Clone + std::fmt::Display + Codec + Send + traits::AtLeast32Bit + 'static,
 	CurrencyId: Clone + std::fmt::Display + Codec,
 	Balance: Codec + traits::MaybeDisplay + Copy + TryInto<NumberOrHex> + std::marker::Send + 'static + std::fmt::Debug,
+	BlockNumber: Codec,
 {
 	fn can_release_amount(&self, account: AccountId, currency_id: CurrencyId, index: Index) -> RpcResult<NumberOrHex> {
 		// let get_release_amount = || {
@@ -263,4 +282,83 @@ where
 
 		// get_unlock_amount()
 	}
+
+	fn get_referral_earnings(&self, account: AccountId) -> RpcResult<Vec<(CurrencyId, Index, NumberOrHex)>> {
+		let api = self.client.runtime_api();
+		let best = self.client.info().best_hash;
+		let at = BlockId::hash(best);
+
+		let earnings = api.get_referral_earnings(&at, account).map_err(|e| {
+			CallError::Custom(ErrorObject::owned(
+				Error::RuntimeError.into(),
+				"Unable to query referral earnings.",
+				Some(format!("{:?}", e)),
+			))
+		})?;
+
+		earnings
+			.into_iter()
+			.map(|(currency_id, index, reward)| {
+				let reward = reward.try_into().map_err(|_| {
+					JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+						ErrorCode::InvalidParams.code(),
+						format!("{:?} doesn't fit in NumberOrHex representation", reward),
+						None::<()>,
+					)))
+				})?;
+				Ok((currency_id, index, reward))
+			})
+			.collect()
+	}
+
+	fn get_icoes(
+		&self,
+		stage: Option<IcoStage>,
+		start: u32,
+		limit: u32,
+	) -> RpcResult<Vec<IcoDetail<AccountId, BlockNumber, Balance>>> {
+		let api = self.client.runtime_api();
+		let best = self.client.info().best_hash;
+		let at = BlockId::hash(best);
+
+		api.get_icoes(&at, stage, start, limit).map_err(|e| {
+			JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+				Error::RuntimeError.into(),
+				"Unable to query icoes.",
+				Some(format!("{:?}", e)),
+			)))
+		})
+	}
+
+	fn get_ico_detail(
+		&self,
+		currency_id: CurrencyId,
+		index: Index,
+	) -> RpcResult<Option<IcoDetail<AccountId, BlockNumber, Balance>>> {
+		let api = self.client.runtime_api();
+		let best = self.client.info().best_hash;
+		let at = BlockId::hash(best);
+
+		api.get_ico_detail(&at, currency_id, index).map_err(|e| {
+			JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+				Error::RuntimeError.into(),
+				"Unable to query ico detail.",
+				Some(format!("{:?}", e)),
+			)))
+		})
+	}
+
+	fn get_portfolio(&self, account: AccountId) -> RpcResult<Vec<IcoPosition<Balance>>> {
+		let api = self.client.runtime_api();
+		let best = self.client.info().best_hash;
+		let at = BlockId::hash(best);
+
+		api.get_portfolio(&at, account).map_err(|e| {
+			JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+				Error::RuntimeError.into(),
+				"Unable to query portfolio.",
+				Some(format!("{:?}", e)),
+			)))
+		})
+	}
 }
@@ -14,18 +14,27 @@
 // limitations under the License.
 
 #![cfg_attr(not(feature = "std"), no_std)]
+
+use pallet_ico::{IcoDetail, IcoPosition, IcoStage};
+use sp_std::vec::Vec;
+
 sp_api::decl_runtime_apis! {
-	pub trait IcoAmountApi<AccountId, CurrencyId, Index, Balance> where
+	pub trait IcoAmountApi<AccountId, CurrencyId, Index, Balance, BlockNumber> where
 		AccountId: codec::Codec,
 		CurrencyId: codec::Codec,
 		Index: codec::Codec,
 		Balance: codec::Codec,
+		BlockNumber: codec::Codec,
 	{
 		fn can_release_amount(account: AccountId, currency_id: CurrencyId, index: Index) -> Balance;
 		fn get_reward_amount(account: AccountId, currency_id: CurrencyId, index: Index) -> Balance;
 		fn can_unlock_amount(user: AccountId, currency_id: CurrencyId, index: Index) -> Balance;
 		fn can_join_amount(user: AccountId, currency_id: CurrencyId, index: Index) -> (Balance, Balance);
 		fn get_token_price(currency_id: CurrencyId) -> Balance;
+		fn get_referral_earnings(account: AccountId) -> Vec<(CurrencyId, Index, Balance)>;
+		fn get_icoes(stage: Option<IcoStage>, start: u32, limit: u32) -> Vec<IcoDetail<AccountId, BlockNumber, Balance>>;
+		fn get_ico_detail(currency_id: CurrencyId, index: Index) -> Option<IcoDetail<AccountId, BlockNumber, Balance>>;
+		fn get_portfolio(account: AccountId) -> Vec<IcoPosition<Balance>>;
 	}
 
 }
@@ -132,9 +132,21 @@ fn set_ico<T: Config>() -> CurrencyId {
 		exchange_token: currency_id,
 		exchange_token_total_amount: (1000 * DOLLARS).saturated_into::<MultiBalanceOf<T>>(),
 		exclude_area: vec![AreaCode::AF],
+		area_caps: vec![],
+		kyc_level_caps: vec![],
 		lock_proportion: Percent::from_percent(10u8),
 		unlock_duration: T::BlockNumber::from(1u32),
 		per_duration_unlock_amount: MultiBalanceOf::<T>::from(100u32),
+		soft_cap: None,
+		mode: IcoMode::FirstComeFirstServed,
+		dutch_auction: None,
+		allowlist_root: None,
+		vesting: None,
+		milestones: None,
+		payment_tokens: vec![],
+		liquidity: None,
+		lottery: None,
+		mint_receipts: false,
 	};
 	assert_ok!(Ico::<T>::initiate_ico(
 		RawOrigin::Signed(alice.clone()).into(),
@@ -152,11 +164,50 @@ fn get_ico<T: Config>() -> (CurrencyId, u32) {
 		id,
 		1,
 		(200 * DOLLARS).saturated_into::<MultiBalanceOf<T>>(),
+		None,
+		None,
+		None,
 		None
 	));
 	(id, 1)
 }
 
+fn get_pro_rata_ico<T: Config>() -> (CurrencyId, u32) {
+	let id = set_ico::<T>();
+	IcoModeOf::<T>::insert(id, 1, IcoMode::ProRata);
+	assert_ok!(Ico::<T>::permit_ico(T::PermitIcoOrigin::successful_origin(), id));
+	frame_system::Pallet::<T>::set_block_number(36002u32.into());
+	assert_ok!(Ico::<T>::join(
+		RawOrigin::Signed(get_haha::<T>()).into(),
+		id,
+		1,
+		(200 * DOLLARS).saturated_into::<MultiBalanceOf<T>>(),
+		None,
+		None,
+		None,
+		None
+	));
+	(id, 1)
+}
+
+fn get_lottery_ico<T: Config>() -> (CurrencyId, u32) {
+	let (id, index) = get_ico::<T>();
+	LotteryOf::<T>::insert(
+		id,
+		index,
+		LotteryInfo {
+			registration_duration: T::BlockNumber::from(100000u32),
+			ticket_power: MultiBalanceOf::<T>::from(1u32),
+			max_tickets_per_user: 100,
+			winner_count: 10,
+			total_tickets: 0,
+			random_seed: None,
+			is_drawn: false,
+		},
+	);
+	(id, index)
+}
+
 fn release_requests<T: Config>() -> (CurrencyId, u32) {
 	let (id, index) = get_ico::<T>();
 	let alice = get_alice::<T>();
@@ -187,7 +238,8 @@ fn haha_get_release_amount<T: Config>() -> (T::AccountId, CurrencyId, u32) {
 	assert_ok!(Ico::<T>::user_release_ico_amount(
 		RawOrigin::Signed(haha.clone()).into(),
 		id,
-		index
+		index,
+		None
 	));
 	(haha, id, index)
 }
@@ -228,9 +280,21 @@ benchmarks! {
 			exchange_token: currency_id,
 			exchange_token_total_amount: (1000 * DOLLARS).saturated_into::<MultiBalanceOf<T>>(),
 			exclude_area: vec![AreaCode::AF],
+			area_caps: vec![],
+			kyc_level_caps: vec![],
 			lock_proportion: Default::default(),
 			unlock_duration: T::BlockNumber::from(0u32),
 			per_duration_unlock_amount: MultiBalanceOf::<T>::from(100u32),
+			soft_cap: None,
+			mode: IcoMode::FirstComeFirstServed,
+			dutch_auction: None,
+			allowlist_root: None,
+			vesting: None,
+			milestones: None,
+			payment_tokens: vec![],
+			liquidity: None,
+			lottery: None,
+			mint_receipts: false,
 		};
 	}:_(RawOrigin::Signed(alice.clone()), ico_info)
 
@@ -252,7 +316,7 @@ benchmarks! {
 		let (id, index) = get_ico::<T>();
 		let bob = get_bob::<T>();
 
-	}:_(RawOrigin::Signed(bob.clone()), id, index, (200 * DOLLARS).saturated_into::<MultiBalanceOf<T>>(), None)
+	}:_(RawOrigin::Signed(bob.clone()), id, index, (200 * DOLLARS).saturated_into::<MultiBalanceOf<T>>(), None, None, None, None)
 
 	terminate_ico {
 		let (id, index) = get_ico::<T>();
@@ -285,12 +349,12 @@ benchmarks! {
 		let (id, index) = release_permit::<T>();
 		let haha = get_haha::<T>();
 
-	}:_(RawOrigin::Signed(haha.clone()), id, index)
+	}:_(RawOrigin::Signed(haha.clone()), id, index, None)
 
 	// todo
 	unlock {
 		let (haha, id, index) = haha_get_release_amount::<T>();
-	}:_(RawOrigin::Signed(haha.clone()), id, index)
+	}:_(RawOrigin::Signed(haha.clone()), id, index, None)
 
 	set_system_ico_amount_bound {
 
@@ -311,7 +375,7 @@ benchmarks! {
 	get_reward {
 		let haha = get_haha::<T>();
 		let (id, index) = get_ico::<T>();
-	}:_(RawOrigin::Signed(haha.clone()), id, index)
+	}:_(RawOrigin::Signed(haha.clone()), id, index, None)
 
 	set_asset_power_multiple {
 
@@ -319,6 +383,205 @@ benchmarks! {
 		up: 10,
 		down: 2,
 	})
+
+	refund {
+		let (id, index) = get_ico::<T>();
+		SoftCapOf::<T>::insert(id, index, (5000 * DOLLARS).saturated_into::<MultiBalanceOf<T>>());
+		let haha = get_haha::<T>();
+	}:_(RawOrigin::Signed(haha.clone()), id, index)
+
+	claim_allocation {
+		let (id, index) = get_pro_rata_ico::<T>();
+		let haha = get_haha::<T>();
+	}:_(RawOrigin::Signed(haha.clone()), id, index)
+
+	join_with_payment_token {
+		let id = set_ico::<T>();
+		assert_ok!(Ico::<T>::permit_ico(T::PermitIcoOrigin::successful_origin(), id));
+		frame_system::Pallet::<T>::set_block_number(36002u32.into());
+		let bob = get_bob::<T>();
+	}:_(RawOrigin::Signed(bob.clone()), id, 1, T::USDCurrencyId::get(), (200 * DOLLARS).saturated_into::<MultiBalanceOf<T>>(), None, None, None, None)
+
+	release_payment_token {
+		let (id, index) = release_permit::<T>();
+		let alice = get_alice::<T>();
+		let amount = (100 * DOLLARS).saturated_into::<MultiBalanceOf<T>>();
+		assert_ok!(T::MultiCurrency::reserve(50, &alice, amount));
+		PaymentRaisedOf::<T>::insert((id, index), 50, PaymentRaised {
+			raised: amount,
+			equivalent: amount,
+			refund: MultiBalanceOf::<T>::from(0u32),
+			released: MultiBalanceOf::<T>::from(0u32),
+		});
+	}:_(RawOrigin::Signed(alice.clone()), id, index, 50)
+
+	register_invite_code {
+		let alice = get_alice::<T>();
+	}:_(RawOrigin::Signed(alice.clone()), b"DICO".to_vec())
+
+	bind_invite_code {
+		let alice = get_alice::<T>();
+		let bob = get_bob::<T>();
+		assert_ok!(Ico::<T>::register_invite_code(RawOrigin::Signed(alice.clone()).into(), b"DICO".to_vec()));
+	}:_(RawOrigin::Signed(bob.clone()), b"DICO".to_vec())
+
+	set_referral_reward_proportions {
+
+	}:_<T::Origin>(T::SetRewardEconomicsOrigin::successful_origin(), vec![Percent::from_percent(10u8)])
+
+	set_reward_economics {
+		let economics = RewardEconomics {
+			total_reward: MultiBalanceOf::<T>::from(1000u32),
+			inviter_reward_proportion: Percent::from_percent(10u8),
+			invitee_reward_proportion: Percent::from_percent(10u8),
+			initiator_pledge: MultiBalanceOf::<T>::from(100u32),
+		};
+	}:_<T::Origin>(T::SetRewardEconomicsOrigin::successful_origin(), economics)
+
+	cancel_ico {
+		let id = set_ico::<T>();
+		assert_ok!(Ico::<T>::permit_ico(T::PermitIcoOrigin::successful_origin(), id));
+		let alice = get_alice::<T>();
+	}:_(RawOrigin::Signed(alice.clone()), id, 1)
+
+	amend_ico {
+		let id = set_ico::<T>();
+		let alice = get_alice::<T>();
+		let amendment = IcoAmendment {
+			desc: vec![2; 100],
+			official_website: vec![2; 20],
+			ico_duration: T::BlockNumber::from(200u32),
+			lock_proportion: Percent::from_percent(20u8),
+			unlock_duration: T::BlockNumber::from(2u32),
+			per_duration_unlock_amount: MultiBalanceOf::<T>::from(100u32),
+			vesting: Some(VestingParameters {
+				cliff: T::BlockNumber::from(10u32),
+				period: T::BlockNumber::from(10u32),
+				period_count: 10,
+			}),
+		};
+	}:_(RawOrigin::Signed(alice.clone()), id, amendment)
+
+	delegate_power {
+		let alice = get_alice::<T>();
+		let bob = get_bob::<T>();
+	}:_(RawOrigin::Signed(alice.clone()), T::Lookup::unlookup(bob))
+
+	revoke_power_delegation {
+		let alice = get_alice::<T>();
+		let bob = get_bob::<T>();
+		assert_ok!(Ico::<T>::delegate_power(RawOrigin::Signed(alice.clone()).into(), T::Lookup::unlookup(bob)));
+	}:_(RawOrigin::Signed(alice.clone()))
+
+	unlock_ico_liquidity {
+		let (id, index) = get_ico::<T>();
+		let alice = get_alice::<T>();
+		IcoLiquidityOf::<T>::insert(id, index, IcoLiquidity {
+			liquidity_id: id,
+			exchange_token_amount: MultiBalanceOf::<T>::from(0u32),
+			project_token_amount: MultiBalanceOf::<T>::from(0u32),
+			liquidity_amount: MultiBalanceOf::<T>::from(0u32),
+			unlock_block: T::BlockNumber::from(0u32),
+			is_unlocked: false,
+		});
+	}:_(RawOrigin::Signed(alice.clone()), id, index)
+
+	register_lottery {
+		let (id, index) = get_lottery_ico::<T>();
+	}:_(RawOrigin::Signed(get_haha::<T>()), id, index)
+
+	draw_lottery {
+		let (id, index) = get_lottery_ico::<T>();
+		assert_ok!(Ico::<T>::register_lottery(RawOrigin::Signed(get_haha::<T>()).into(), id, index));
+		LotteryOf::<T>::mutate(id, index, |lottery| {
+			if let Some(lottery) = lottery.as_mut() {
+				lottery.registration_duration = T::BlockNumber::from(0u32);
+				lottery.random_seed = Some(T::Hash::default());
+			}
+		});
+	}:_(RawOrigin::Signed(get_alice::<T>()), id, index)
+
+	terminate_fraud_ico {
+		let (id, index) = get_ico::<T>();
+	}:_<T::Origin>(T::TerminateIcoOrigin::successful_origin(), id, index, MultiBalanceOf::<T>::from(0u32))
+
+	claim_compensation {
+		let (id, index) = get_ico::<T>();
+		assert_ok!(Ico::<T>::terminate_fraud_ico(
+			T::TerminateIcoOrigin::successful_origin(),
+			id,
+			index,
+			MultiBalanceOf::<T>::from(0u32)
+		));
+	}:_(RawOrigin::Signed(get_haha::<T>()), id, index)
+
+	fund_reward_pool {
+		let (id, index) = get_ico::<T>();
+	}:_(RawOrigin::Signed(get_alice::<T>()), id, index, MultiBalanceOf::<T>::from(100u32))
+
+	get_pool_reward {
+		let haha = get_haha::<T>();
+		let (id, index) = get_ico::<T>();
+		assert_ok!(Ico::<T>::fund_reward_pool(
+			RawOrigin::Signed(get_alice::<T>()).into(),
+			id,
+			index,
+			MultiBalanceOf::<T>::from(100u32)
+		));
+	}:_(RawOrigin::Signed(haha.clone()), id, index, None)
+
+	reclaim_reward_pool {
+		let (id, index) = get_ico::<T>();
+		assert_ok!(Ico::<T>::fund_reward_pool(
+			RawOrigin::Signed(get_alice::<T>()).into(),
+			id,
+			index,
+			MultiBalanceOf::<T>::from(100u32)
+		));
+		let ico = crate::Ico::<T>::get(id, index).unwrap();
+		frame_system::Pallet::<T>::set_block_number(
+			ico.start_time.unwrap() + ico.ico_duration + T::RewardPoolClaimDuration::get() + 1u32.into()
+		);
+	}:_(RawOrigin::Signed(get_alice::<T>()), id, index)
+
+	seed_lotteries {
+		let n in 1 .. T::MaxLotteryDrawsPerBlock::get();
+		let (id, _) = get_ico::<T>();
+		let at = frame_system::Pallet::<T>::block_number() + T::BlockNumber::from(1u32);
+		for index in 0..n {
+			LotteryOf::<T>::insert(id, index, LotteryInfo::default());
+			assert_ok!(Ico::<T>::schedule_lottery_seed(id, index, at));
+		}
+	}: {
+		Ico::<T>::on_initialize(at);
+	}
+	verify {
+		assert!(LotterySeedQueue::<T>::get(at).is_empty());
+	}
+
+	expire_requests {
+		let n in 1 .. T::MaxRequestExpiriesPerBlock::get();
+		let (id, _) = get_ico::<T>();
+		let alice = get_alice::<T>();
+		let now = frame_system::Pallet::<T>::block_number();
+		let at = now + T::RequestExpire::get();
+		for index in 0..n {
+			RequestReleaseInfo::<T>::insert(id, index, Release {
+				who: alice.clone(),
+				currency_id: id,
+				index,
+				request_time: now,
+				percent: Percent::from_percent(10u8),
+				pledge: BalanceOf::<T>::zero(),
+			});
+			assert_ok!(Ico::<T>::schedule_request_expiry(id, index, at));
+		}
+	}: {
+		Ico::<T>::on_initialize(at);
+	}
+	verify {
+		assert!(RequestReleaseInfo::<T>::iter_prefix(id).next().is_none());
+	}
 }
 
 // impl_benchmark_test_suite!(Ico, crate::mock::new_test_ext(), crate::mock::Test,);
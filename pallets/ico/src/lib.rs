@@ -32,7 +32,8 @@ pub use dico_primitives::{currency::*, time::*};
 pub use dico_primitives::{AssetId, CurrencyId};
 use dico_primitives::{Balance, Price};
 use dico_treasury::traits::DicoTreasuryHandler;
-use frame_support::traits::ExistenceRequirement;
+use frame_support::storage::with_transaction;
+use frame_support::traits::{ExistenceRequirement, Randomness};
 pub use frame_support::{
 	debug, decl_error, decl_event, decl_module, decl_storage, ensure, runtime_print,
 	traits::{
@@ -44,26 +45,37 @@ pub use frame_support::{
 	weights::Weight,
 	Blake2_256, IterableStorageDoubleMap, IterableStorageMap,
 };
+use frame_support::{BoundedVec, PalletId};
 pub use frame_system::{self as system, ensure_none, ensure_root, ensure_signed};
 use orml_tokens::BalanceLock;
 use orml_tokens::{self as tokens, Locks};
-use orml_traits::{BalanceStatus, MultiCurrency, MultiReservableCurrency};
-use pallet_kyc::{traits::KycHandler, types::AreaCode};
+use orml_traits::{BalanceStatus, MultiCurrency, MultiLockableCurrency, MultiReservableCurrency};
+use pallet_kyc::{
+	traits::KycHandler,
+	types::{AreaCode, KycLevel},
+};
 use pallet_pricedao::traits::PriceData;
+use pallet_tier::TierHandler;
 pub use primitive_types::U256;
 use scale_info::TypeInfo;
-use sp_runtime::traits::{CheckedAdd, CheckedSub, StaticLookup};
+use sp_core::H256;
+use sp_io::hashing::blake2_256;
+use sp_runtime::traits::{CheckedAdd, CheckedSub, Hash as HashT, StaticLookup};
 pub use sp_runtime::{
 	traits::CheckedMul,
 	traits::{AccountIdConversion, CheckedDiv, Saturating, Zero},
-	DispatchError, DispatchResult, Percent, RuntimeDebug, SaturatedConversion,
+	DispatchError, DispatchResult, Percent, RuntimeDebug, SaturatedConversion, TransactionOutcome,
 };
 pub use sp_std::convert::{Into, TryFrom, TryInto};
 use sp_std::vec::Vec;
 use sp_std::{collections::btree_map::BTreeMap, prelude::*, result};
-use traits::{IcoHandler, PowerHandler};
+use traits::{IcoHandler, IcoProposalHandler, IcoReceiptHandler, LiquidityHandler, PowerHandler};
 use weights::WeightInfo;
 
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+pub mod migrations;
 pub mod mock;
 pub mod tests;
 pub mod traits;
@@ -87,6 +99,57 @@ impl Default for IcoStatus {
 	}
 }
 
+#[derive(PartialEq, Eq, Encode, Decode, RuntimeDebug, Clone, Copy, TypeInfo)]
+pub enum IcoMode {
+	/// Users join until the ico amount is sold out.
+	FirstComeFirstServed,
+	/// Users can join above the ico amount, and everyone gets a pro-rata allocation at the end.
+	ProRata,
+	/// The price decays per block to a floor, and everyone pays the clearing price at the end.
+	DutchAuction,
+	/// Users register tickets by their power, and only the winners of the random draw can join.
+	Lottery,
+}
+
+impl Default for IcoMode {
+	fn default() -> Self {
+		IcoMode::FirstComeFirstServed
+	}
+}
+
+impl IcoMode {
+	/// The allocation of the users is not known until the end of the ico.
+	pub fn is_settled_at_end(&self) -> bool {
+		matches!(self, IcoMode::ProRata | IcoMode::DutchAuction)
+	}
+}
+
+#[derive(PartialEq, Eq, Encode, Decode, Default, RuntimeDebug, Clone, TypeInfo)]
+pub struct DutchAuctionParameters<Balance> {
+	/// The amount of exchange token for the total ico amount when the auction starts
+	pub start_exchange_token_total_amount: Balance,
+	/// How much the amount of exchange token for the total ico amount decays per block
+	pub decay_per_block: Balance,
+}
+
+#[derive(PartialEq, Eq, Encode, Decode, Default, RuntimeDebug, Clone, TypeInfo)]
+pub struct AllowlistProof<Balance> {
+	/// The maximum USDT value the user can join with
+	pub max_allocation: Balance,
+	/// The merkle proof of `(account, max_allocation)`
+	pub proof: Vec<H256>,
+}
+
+#[derive(PartialEq, Eq, Encode, Decode, Default, RuntimeDebug, Clone, TypeInfo)]
+pub struct DutchAuctionInfo<Balance> {
+	pub start_exchange_token_total_amount: Balance,
+	pub decay_per_block: Balance,
+	/// The amount of project token users have bid for
+	pub sold_amount: Balance,
+	/// The amount of exchange token for the total ico amount when the ico amount is sold out
+	pub clearing_exchange_token_total_amount: Option<Balance>,
+}
+
 #[derive(PartialEq, Encode, Decode, RuntimeDebug, Clone, TypeInfo)]
 pub struct InviteInfo<AccountId, Balance, AssetId> {
 	inviter: AccountId,
@@ -135,6 +198,180 @@ pub struct IcoLock<Balance, BlockNumber> {
 	per_duration_unlock_amount: Balance,
 }
 
+#[derive(PartialEq, Eq, Encode, Decode, Default, RuntimeDebug, Clone, TypeInfo)]
+pub struct VestingParameters<BlockNumber> {
+	/// How long after the release the first period starts
+	pub cliff: BlockNumber,
+	/// How often to unlock
+	pub period: BlockNumber,
+	/// How many periods to unlock all the locked tokens
+	pub period_count: u32,
+}
+
+/// The same schedule as `orml_vesting::VestingSchedule`, of the project token of an ico.
+#[derive(PartialEq, Eq, Encode, Decode, Default, RuntimeDebug, Clone, TypeInfo)]
+pub struct VestingSchedule<BlockNumber, Balance> {
+	/// the ico unique id
+	pub index: u32,
+	pub start: BlockNumber,
+	pub period: BlockNumber,
+	pub period_count: u32,
+	pub per_period: Balance,
+	/// the amount that have unlocked
+	pub unlock_amount: Balance,
+}
+
+impl<BlockNumber, Balance> VestingSchedule<BlockNumber, Balance>
+where
+	BlockNumber: sp_runtime::traits::AtLeast32BitUnsigned + Copy,
+	Balance: sp_runtime::traits::AtLeast32BitUnsigned + Copy,
+{
+	/// The amount that is still locked at `time`.
+	pub fn locked_amount(&self, time: BlockNumber) -> Balance {
+		let full = time
+			.saturating_sub(self.start)
+			.checked_div(&self.period)
+			.unwrap_or_else(Zero::zero);
+		let unrealized = self.period_count.saturating_sub(full.saturated_into::<u32>());
+		self.per_period.saturating_mul(Balance::from(unrealized))
+	}
+
+	pub fn total_amount(&self) -> Balance {
+		self.per_period.saturating_mul(Balance::from(self.period_count))
+	}
+}
+
+#[derive(PartialEq, Eq, Encode, Decode, Default, RuntimeDebug, Clone, TypeInfo)]
+pub struct Milestone<BlockNumber> {
+	/// The hash of the milestone description
+	pub desc_hash: H256,
+	/// The percent of the raised funds released when the milestone is reached
+	pub percent: Percent,
+	/// The initiator must request the release before this block
+	pub deadline: BlockNumber,
+}
+
+#[derive(PartialEq, Eq, Encode, Decode, Default, RuntimeDebug, Clone, TypeInfo)]
+pub struct MilestoneInfo<BlockNumber> {
+	pub milestones: Vec<Milestone<BlockNumber>>,
+	/// The proportion of the ico members that must approve the release
+	pub threshold: Percent,
+}
+
+#[derive(PartialEq, Eq, Encode, Decode, Default, RuntimeDebug, Clone, TypeInfo)]
+pub struct PaymentToken<CurrencyId, Balance> {
+	/// The asset ID of the token accepted besides the exchange token
+	pub currency_id: CurrencyId,
+	/// The max amount of the token that the ico raises
+	pub cap: Balance,
+}
+
+#[derive(PartialEq, Eq, Encode, Decode, Default, RuntimeDebug, Clone, TypeInfo)]
+pub struct LiquidityParameters<BlockNumber> {
+	/// The proportion of the raised exchange tokens paired with the project tokens into the amm
+	pub proportion: Percent,
+	/// How long the liquidity is locked after it is added
+	pub lock_duration: BlockNumber,
+}
+
+#[derive(PartialEq, Eq, Encode, Decode, Default, RuntimeDebug, Clone, TypeInfo)]
+pub struct LotteryParameters<BlockNumber, Balance> {
+	/// How long users register tickets after the ico starts, the winners join after it
+	pub registration_duration: BlockNumber,
+	/// The power of one ticket
+	pub ticket_power: Balance,
+	pub max_tickets_per_user: u32,
+	/// The number of users drawn
+	pub winner_count: u32,
+}
+
+#[derive(PartialEq, Eq, Encode, Decode, Default, RuntimeDebug, Clone, TypeInfo)]
+pub struct LotteryInfo<BlockNumber, Balance, Hash> {
+	pub registration_duration: BlockNumber,
+	pub ticket_power: Balance,
+	pub max_tickets_per_user: u32,
+	pub winner_count: u32,
+	/// The tickets registered by all the users
+	pub total_tickets: u32,
+	/// The relay chain randomness of the block the registration ends, the winners are drawn with
+	pub random_seed: Option<Hash>,
+	pub is_drawn: bool,
+}
+
+#[derive(PartialEq, Eq, Encode, Decode, Default, RuntimeDebug, Clone, TypeInfo)]
+pub struct IcoLiquidity<BlockNumber, Balance> {
+	/// The asset ID of the liquidity
+	pub liquidity_id: AssetId,
+	pub exchange_token_amount: Balance,
+	pub project_token_amount: Balance,
+	/// The liquidity reserved by the initiator
+	pub liquidity_amount: Balance,
+	pub unlock_block: BlockNumber,
+	pub is_unlocked: bool,
+}
+
+#[derive(PartialEq, Eq, Encode, Decode, Default, RuntimeDebug, Clone, TypeInfo)]
+pub struct IcoCompensation<Balance> {
+	/// The pledge of the initiator slashed to the protection fund, in the native token
+	pub pledge: Balance,
+	/// The exchange tokens of the protection fund given to the participants
+	pub amount: Balance,
+	pub claimed_pledge: Balance,
+	pub claimed_amount: Balance,
+	/// The unreleased amount of all the participants when the ico was terminated as a fraud
+	pub total_unreleased: Balance,
+	pub released_proportion: Percent,
+}
+
+#[derive(PartialEq, Eq, Encode, Decode, Default, RuntimeDebug, Clone, TypeInfo)]
+pub struct PaymentRaised<Balance> {
+	/// The amount of the token raised
+	pub raised: Balance,
+	/// The raised amount converted to the exchange token
+	pub equivalent: Balance,
+	/// The amount given back to the users
+	pub refund: Balance,
+	/// The amount released to the initiator
+	pub released: Balance,
+}
+
+/// The reward economics of all the ICOs, adjusted by governance.
+#[derive(PartialEq, Eq, Encode, Decode, Default, RuntimeDebug, Clone, TypeInfo)]
+pub struct RewardEconomics<Balance> {
+	/// The DICO reward of the first half of all the ICOs
+	pub total_reward: Balance,
+	pub inviter_reward_proportion: Percent,
+	pub invitee_reward_proportion: Percent,
+	/// The DICO pledged by the initiator when the ico is initiated
+	pub initiator_pledge: Balance,
+}
+
+#[derive(PartialEq, Eq, Encode, Decode, Default, RuntimeDebug, Clone, TypeInfo)]
+pub struct RewardPool<Balance> {
+	/// The project tokens funded by the initiator, reserved until the participants get them
+	pub total: Balance,
+	pub claimed: Balance,
+	/// The usdt of the participants that have got their share
+	pub claimed_usdt: Balance,
+}
+
+/// The parameters that the initiator can amend before the ICO is permitted
+#[derive(PartialEq, Encode, Decode, Default, RuntimeDebug, Clone, TypeInfo)]
+pub struct IcoAmendment<BlockNumber, Balance> {
+	pub desc: Vec<u8>,
+	/// The project's official website
+	pub official_website: Vec<u8>,
+	/// The lifetime of an ICO
+	pub ico_duration: BlockNumber,
+	/// The proportion of lock-ups required
+	pub lock_proportion: Percent,
+	/// How often to unlock
+	pub unlock_duration: BlockNumber,
+	pub per_duration_unlock_amount: Balance,
+	/// How the locked tokens vest after release
+	pub vesting: Option<VestingParameters<BlockNumber>>,
+}
+
 #[derive(PartialEq, Encode, Decode, Default, RuntimeDebug, Clone, TypeInfo)]
 pub struct IcoParameters<BlockNumber, Balance, CurrencyId, AreaCode> {
 	pub desc: Vec<u8>,
@@ -164,11 +401,38 @@ pub struct IcoParameters<BlockNumber, Balance, CurrencyId, AreaCode> {
 	pub exchange_token_total_amount: Balance,
 	/// AreaCode these not participate in the ico
 	pub exclude_area: Vec<AreaCode>,
+	/// The most usdt that the users of the area join in total
+	pub area_caps: Vec<(AreaCode, Balance)>,
+	/// The most usdt that a user of the kyc level joins, instead of `user_max_amount`
+	pub kyc_level_caps: Vec<(KycLevel, Balance)>,
 	/// The proportion of lock-ups required
 	pub lock_proportion: Percent,
 	/// How often to unlock
 	pub unlock_duration: BlockNumber,
 	pub per_duration_unlock_amount: Balance,
+	/// The minimum USDT value the ICO must raise, otherwise participants can get refunds
+	pub soft_cap: Option<Balance>,
+	/// How users join the ICO
+	pub mode: IcoMode,
+	/// The price of the dutch auction ICO, the floor is `exchange_token_total_amount`
+	pub dutch_auction: Option<DutchAuctionParameters<Balance>>,
+	/// The merkle root of the `(account, max_allocation)` allowlist, only these accounts can join
+	pub allowlist_root: Option<H256>,
+	/// How the locked tokens vest after release, instead of `unlock_duration` and
+	/// `per_duration_unlock_amount`
+	pub vesting: Option<VestingParameters<BlockNumber>>,
+	/// The raised funds are released by milestones voted by the ico members
+	pub milestones: Option<MilestoneInfo<BlockNumber>>,
+	/// The tokens accepted besides the exchange token, each with its own cap
+	pub payment_tokens: Vec<PaymentToken<CurrencyId, Balance>>,
+	/// Part of the raised exchange tokens and the project tokens are added to the amm at the ico
+	/// price when the ico ends
+	pub liquidity: Option<LiquidityParameters<BlockNumber>>,
+	/// The registration and the draw of the lottery ICO
+	pub lottery: Option<LotteryParameters<BlockNumber, Balance>>,
+	/// Each participation is a transferable nft receipt, the owner of the nft releases,
+	/// unlocks and gets the reward of it
+	pub mint_receipts: bool,
 }
 
 #[derive(PartialEq, Encode, Decode, Default, RuntimeDebug, Clone, TypeInfo)]
@@ -208,6 +472,71 @@ impl Default for PowerMultiple {
 	}
 }
 
+/// The stage of an ico when it is listed.
+#[derive(PartialEq, Eq, Encode, Decode, RuntimeDebug, Clone, Copy, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum IcoStage {
+	/// Waiting for the foundation to permit it
+	Pending,
+	/// Permitted but not started
+	Upcoming,
+	Ongoing,
+	/// Expired or terminated
+	Finished,
+}
+
+/// The detail of an ico.
+/// Provided for RPC use
+#[derive(PartialEq, Eq, Encode, Decode, RuntimeDebug, Clone, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct IcoDetail<AccountId, BlockNumber, Balance> {
+	pub currency_id: AssetId,
+	pub index: u32,
+	pub stage: IcoStage,
+	pub initiator: AccountId,
+	pub desc: Vec<u8>,
+	pub project_name: Vec<u8>,
+	pub token_symbol: Vec<u8>,
+	pub decimals: u8,
+	pub official_website: Vec<u8>,
+	pub is_must_kyc: bool,
+	pub start_time: Option<BlockNumber>,
+	pub ico_duration: BlockNumber,
+	pub total_ico_amount: Balance,
+	pub exchange_token: AssetId,
+	pub exchange_token_total_amount: Balance,
+	pub user_min_amount: Balance,
+	pub user_max_amount: Balance,
+	pub soft_cap: Option<Balance>,
+	/// The amount of exchange token raised
+	pub raised_amount: Balance,
+	/// Total value of the token raised
+	pub raised_usdt: Balance,
+	pub participant_count: u32,
+	pub is_terminated: bool,
+}
+
+/// What an account has in an ico.
+/// Provided for RPC use
+#[derive(PartialEq, Eq, Encode, Decode, Default, RuntimeDebug, Clone, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct IcoPosition<Balance> {
+	pub currency_id: AssetId,
+	pub index: u32,
+	/// Total value of the token the account joined with
+	pub joined_usdt: Balance,
+	/// The total number of project tokens the account gets
+	pub total_amount: Balance,
+	pub released_amount: Balance,
+	pub releasable_amount: Balance,
+	/// The project tokens that are released but still locked
+	pub locked_amount: Balance,
+	pub unlockable_amount: Balance,
+	pub reward_amount: Balance,
+	/// The referral rewards the account earned in the ico
+	pub invite_earnings: Balance,
+}
+
 #[derive(PartialEq, Encode, Decode, Default, RuntimeDebug, Clone, TypeInfo)]
 pub struct IcoInfo<BlockNumber, Balance, CurrencyId, AreaCode, AccountId> {
 	desc: Vec<u8>,
@@ -263,16 +592,22 @@ pub struct IcoInfo<BlockNumber, Balance, CurrencyId, AreaCode, AccountId> {
 pub mod pallet {
 	use super::*;
 	use frame_support::pallet_prelude::{
-		Blake2_128Concat, IsType, OptionQuery, StorageDoubleMap, StorageMap, StorageValue, ValueQuery,
+		Blake2_128Concat, IsType, OptionQuery, StorageDoubleMap, StorageMap, StorageValue, Twox64Concat, ValueQuery,
 	};
-	use frame_support::traits::Hooks;
+	use frame_support::traits::{Hooks, StorageVersion};
 	use frame_system::pallet_prelude::*;
 
 	pub(crate) type MultiBalanceOf<T> =
 		<<T as Config>::MultiCurrency as MultiCurrency<<T as frame_system::Config>::AccountId>>::Balance;
-	type BalanceOf<T> = <<T as Config>::NativeCurrency as Currency<<T as system::Config>::AccountId>>::Balance;
+	pub(crate) type BalanceOf<T> =
+		<<T as Config>::NativeCurrency as Currency<<T as system::Config>::AccountId>>::Balance;
 	type NegativeImbalanceOf<T> =
 		<<T as Config>::NativeCurrency as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
+	pub type ReceiptIdOf<T> = <<T as Config>::ReceiptHandler as IcoReceiptHandler<
+		<T as frame_system::Config>::AccountId,
+		AssetId,
+		MultiBalanceOf<T>,
+	>>::ReceiptId;
 
 	#[pallet::config]
 	#[pallet::disable_frame_system_supertrait_check]
@@ -288,10 +623,15 @@ pub mod pallet {
 
 		type TerminateIcoOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 
+		/// The origin that adjusts the reward economics and the referral reward proportions of the
+		/// ICOs.
+		type SetRewardEconomicsOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
 		type OnSlash: OnUnbalanced<NegativeImbalanceOf<Self>>;
 
 		type MultiCurrency: MultiCurrency<Self::AccountId, CurrencyId = AssetId>
-			+ MultiReservableCurrency<Self::AccountId>;
+			+ MultiReservableCurrency<Self::AccountId>
+			+ MultiLockableCurrency<Self::AccountId>;
 
 		type NativeCurrency: Currency<Self::AccountId> + ReservableCurrency<Self::AccountId>;
 
@@ -309,9 +649,33 @@ pub mod pallet {
 
 		type KycHandler: KycHandler<Self::AccountId, AreaCode>;
 
+		/// The tier of the user multiplies the `user_max_amount` of ICOs.
+		type TierHandler: TierHandler<Self::AccountId>;
+
+		/// Opens the proposals of the ico members to permit the release of milestone ICOs.
+		type IcoProposalHandler: IcoProposalHandler<AssetId>;
+
+		/// Adds the liquidity of ICOs to the amm when they end.
+		type LiquidityHandler: LiquidityHandler<Self::AccountId, AssetId, MultiBalanceOf<Self>>;
+
+		/// Mints the nft receipts of the participations of ICOs.
+		type ReceiptHandler: IcoReceiptHandler<Self::AccountId, AssetId, MultiBalanceOf<Self>>;
+
+		/// The randomness the winners of lottery ICOs are drawn with, `None` when it is not
+		/// available in the block.
+		type Randomness: Randomness<Option<Self::Hash>, Self::BlockNumber>;
+
+		/// The origin of the ico members, succeeds with the ico, the approved amount and the total
+		/// amount.
+		type IcoMembersOrigin: EnsureOrigin<
+			Self::RuntimeOrigin,
+			Success = (AssetId, u32, MultiBalanceOf<Self>, MultiBalanceOf<Self>),
+		>;
+
 		type WeightInfo: WeightInfo;
 
 		type GetNativeCurrencyId: Get<AssetId>;
+		/// The initial pledge of the initiators, `RewardEconomics` is used afterwards.
 		#[pallet::constant]
 		type InitiatorPledge: Get<MultiBalanceOf<Self>>;
 		#[pallet::constant]
@@ -320,6 +684,7 @@ pub mod pallet {
 		type RequestPledge: Get<BalanceOf<Self>>;
 		#[pallet::constant]
 		type RequestExpire: Get<Self::BlockNumber>;
+		/// The initial total reward of the ICOs, `RewardEconomics` is used afterwards.
 		#[pallet::constant]
 		type IcoTotalReward: Get<MultiBalanceOf<Self>>;
 		#[pallet::constant]
@@ -328,22 +693,72 @@ pub mod pallet {
 		type ReleaseProtectPeriod: Get<Percent>;
 		#[pallet::constant]
 		type ChillDuration: Get<Self::BlockNumber>;
+		/// The initial reward proportions of the inviter and the invitee, `RewardEconomics` is
+		/// used afterwards.
 		#[pallet::constant]
 		type InviterRewardProportion: Get<Percent>;
 		#[pallet::constant]
 		type InviteeRewardProportion: Get<Percent>;
 		#[pallet::constant]
 		type USDCurrencyId: Get<AssetId>;
+		/// The maximum length of an allowlist merkle proof.
+		#[pallet::constant]
+		type MaxAllowlistProofLength: Get<u32>;
+		/// The maximum age of the oracle price used by `join`, in milliseconds.
+		#[pallet::constant]
+		type MaxPriceAge: Get<u64>;
+		/// The maximum deviation of the price used by `join` from the TWAP of the swap.
+		#[pallet::constant]
+		type MaxTwapDeviation: Get<Percent>;
+		/// The maximum length of an invite code.
+		#[pallet::constant]
+		type MaxInviteCodeLength: Get<u32>;
+		/// The maximum levels of referral rewards.
+		#[pallet::constant]
+		type MaxReferralLevels: Get<u32>;
+		/// The proportion of the pledge that goes to the treasury when the initiator cancels the
+		/// ico.
+		#[pallet::constant]
+		type CancelIcoFee: Get<Percent>;
+		/// The number of blocks it takes the power of a user to halve, zero means the power does
+		/// not decay.
+		#[pallet::constant]
+		type PowerHalfLife: Get<Self::BlockNumber>;
+		/// The maximum number of users that delegate their power to one user.
+		#[pallet::constant]
+		type MaxPowerDelegators: Get<u32>;
+		/// The most that the usable power of a user raises its ico reward by.
+		#[pallet::constant]
+		type PowerRewardBoost: Get<Percent>;
+		/// The maximum number of users that register for a lottery ico.
+		#[pallet::constant]
+		type MaxLotteryEntrants: Get<u32>;
+		/// The maximum number of lottery ICOs whose registration ends in one block.
+		#[pallet::constant]
+		type MaxLotteryDrawsPerBlock: Get<u32>;
+		/// The account of the fund that compensates the participants of fraud ICOs.
+		#[pallet::constant]
+		type ProtectionFundId: Get<PalletId>;
+		/// The share of the funds released to the initiators that goes to the protection fund.
+		#[pallet::constant]
+		type ProtectionFundProportion: Get<Percent>;
+		/// The maximum number of release requests that expire in one block.
+		#[pallet::constant]
+		type MaxRequestExpiriesPerBlock: Get<u32>;
+		/// The number of blocks after the end of an ico that the participants have to get the
+		/// reward pool, the initiator reclaims the rest afterwards.
+		#[pallet::constant]
+		type RewardPoolClaimDuration: Get<Self::BlockNumber>;
 	}
 
 	#[pallet::storage]
 	#[pallet::getter(fn pending_ico)]
-	pub type PendingIco<T: Config> = StorageValue<
+	pub type PendingIco<T: Config> = StorageMap<
 		_,
-		Vec<
-			PendingInfo<IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>, MultiBalanceOf<T>>,
-		>,
-		ValueQuery,
+		Blake2_128Concat,
+		AssetId,
+		PendingInfo<IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>, MultiBalanceOf<T>>,
+		OptionQuery,
 	>;
 
 	#[pallet::storage]
@@ -369,8 +784,33 @@ pub mod pallet {
 
 	#[pallet::storage]
 	#[pallet::getter(fn release_info)]
-	pub type RequestReleaseInfo<T: Config> =
-		StorageValue<_, Vec<Release<T::AccountId, T::BlockNumber, AssetId, BalanceOf<T>>>, ValueQuery>;
+	pub type RequestReleaseInfo<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		AssetId,
+		Blake2_128Concat,
+		u32,
+		Release<T::AccountId, T::BlockNumber, AssetId, BalanceOf<T>>,
+		OptionQuery,
+	>;
+
+	/// The release requests that expire at the block, they are removed in `on_initialize`.
+	#[pallet::storage]
+	#[pallet::getter(fn request_expiry_queue)]
+	pub type RequestExpiryQueue<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<(AssetId, u32), T::MaxRequestExpiriesPerBlock>,
+		ValueQuery,
+	>;
+
+	/// The lottery ICOs whose registration ends at the block, their random seed is taken in
+	/// `on_initialize`.
+	#[pallet::storage]
+	#[pallet::getter(fn lottery_seed_queue)]
+	pub type LotterySeedQueue<T: Config> =
+		StorageMap<_, Twox64Concat, T::BlockNumber, BoundedVec<(AssetId, u32), T::MaxLotteryDrawsPerBlock>, ValueQuery>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn locks)]
@@ -401,92 +841,433 @@ pub mod pallet {
 		StorageDoubleMap<_, Blake2_128Concat, AssetId, Blake2_128Concat, u32, bool, ValueQuery>;
 
 	#[pallet::storage]
-	pub type IcoMinUsdtAmount<T: Config> = StorageValue<_, MultiBalanceOf<T>, ValueQuery>;
+	#[pallet::getter(fn soft_cap_of)]
+	pub type SoftCapOf<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, AssetId, Blake2_128Concat, u32, MultiBalanceOf<T>, OptionQuery>;
 
 	#[pallet::storage]
-	pub type IcoMaxUsdtAmount<T: Config> = StorageValue<_, MultiBalanceOf<T>, ValueQuery>;
+	#[pallet::getter(fn initiator_pledge_of)]
+	pub type InitiatorPledgeOf<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, AssetId, Blake2_128Concat, u32, MultiBalanceOf<T>, ValueQuery>;
 
 	#[pallet::storage]
-	#[pallet::getter(fn initiate_icoes_of)]
-	pub type InitiatedIcoesOf<T: Config> = StorageMap<
+	#[pallet::getter(fn ico_mode_of)]
+	pub type IcoModeOf<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, AssetId, Blake2_128Concat, u32, IcoMode, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn dutch_auction_of)]
+	pub type DutchAuctionOf<T: Config> = StorageDoubleMap<
 		_,
 		Blake2_128Concat,
-		T::AccountId,
-		Vec<InitiatedIco<AssetId, IcoStatus, MultiBalanceOf<T>>>,
+		AssetId,
+		Blake2_128Concat,
+		u32,
+		DutchAuctionInfo<MultiBalanceOf<T>>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn area_caps_of)]
+	pub type AreaCapsOf<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		AssetId,
+		Blake2_128Concat,
+		u32,
+		Vec<(AreaCode, MultiBalanceOf<T>)>,
 		ValueQuery,
 	>;
 
+	/// The usdt joined by the users of the area.
 	#[pallet::storage]
-	#[pallet::getter(fn icoes_of)]
-	pub type IcoesOf<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, Vec<(AssetId, u32)>, ValueQuery>;
+	#[pallet::getter(fn area_raised_of)]
+	pub type AreaRaisedOf<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		(AssetId, u32),
+		Blake2_128Concat,
+		AreaCode,
+		MultiBalanceOf<T>,
+		ValueQuery,
+	>;
 
 	#[pallet::storage]
-	pub type PassedIcoes<T: Config> = StorageValue<_, Vec<(AssetId, u32)>, ValueQuery>;
+	#[pallet::getter(fn kyc_level_caps_of)]
+	pub type KycLevelCapsOf<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		AssetId,
+		Blake2_128Concat,
+		u32,
+		Vec<(KycLevel, MultiBalanceOf<T>)>,
+		ValueQuery,
+	>;
 
 	#[pallet::storage]
-	#[pallet::getter(fn power_multiple_of)]
-	pub type PowerMultipleOf<T: Config> = StorageMap<_, Blake2_128Concat, AssetId, PowerMultiple, ValueQuery>;
+	#[pallet::getter(fn allowlist_root_of)]
+	pub type AllowlistRootOf<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, AssetId, Blake2_128Concat, u32, H256, OptionQuery>;
 
+	/// The max allocation of the users that have proved they are in the allowlist.
 	#[pallet::storage]
-	#[pallet::getter(fn total_power_of)]
-	pub type TotalPowerOf<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, MultiBalanceOf<T>, ValueQuery>;
+	#[pallet::getter(fn allowlist_cap_of)]
+	pub type AllowlistCapOf<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		(AssetId, u32),
+		Blake2_128Concat,
+		T::AccountId,
+		MultiBalanceOf<T>,
+		OptionQuery,
+	>;
 
 	#[pallet::storage]
-	#[pallet::getter(fn invite_info_of)]
-	pub type InviteInfoOf<T: Config> = StorageDoubleMap<
+	#[pallet::getter(fn vesting_of)]
+	pub type VestingOf<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		AssetId,
+		Blake2_128Concat,
+		u32,
+		VestingParameters<T::BlockNumber>,
+		OptionQuery,
+	>;
+
+	/// The project tokens are free but locked until they vest, so that wallets show them.
+	#[pallet::storage]
+	#[pallet::getter(fn vesting_schedules)]
+	pub type VestingSchedules<T: Config> = StorageDoubleMap<
 		_,
 		Blake2_128Concat,
 		T::AccountId,
 		Blake2_128Concat,
 		AssetId,
-		Vec<InviteInfo<T::AccountId, MultiBalanceOf<T>, AssetId>>,
+		Vec<VestingSchedule<T::BlockNumber, MultiBalanceOf<T>>>,
 		ValueQuery,
 	>;
 
-	#[pallet::pallet]
-	#[pallet::without_storage_info]
-	pub struct Pallet<T>(_);
+	#[pallet::storage]
+	#[pallet::getter(fn milestones_of)]
+	pub type MilestonesOf<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		AssetId,
+		Blake2_128Concat,
+		u32,
+		MilestoneInfo<T::BlockNumber>,
+		OptionQuery,
+	>;
 
-	#[pallet::call]
-	impl<T: Config> Pallet<T> {
-		/// The project party initiates an ICO
-		#[pallet::weight(<T as pallet::Config>::WeightInfo::initiate_ico())]
-		pub fn initiate_ico(
-			origin: OriginFor<T>,
-			info: IcoParameters<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode>,
-		) -> DispatchResult {
-			let initiator = ensure_signed(origin)?;
+	#[pallet::storage]
+	#[pallet::getter(fn payment_tokens_of)]
+	pub type PaymentTokensOf<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		AssetId,
+		Blake2_128Concat,
+		u32,
+		Vec<PaymentToken<AssetId, MultiBalanceOf<T>>>,
+		ValueQuery,
+	>;
 
-			Self::initiate_ico_simple_check(&info, &initiator)?;
-			let exchange_token_metadata = T::CurrenciesHandler::get_metadata(info.exchange_token)?;
-			let metadata = T::CurrenciesHandler::get_metadata(info.currency_id)?;
+	#[pallet::storage]
+	#[pallet::getter(fn liquidity_of)]
+	pub type LiquidityOf<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		AssetId,
+		Blake2_128Concat,
+		u32,
+		LiquidityParameters<T::BlockNumber>,
+		OptionQuery,
+	>;
 
-			let bond = T::InitiatorBond::get() * info.exchange_token_total_amount;
-			let total_num = <TotalNum<T>>::get().checked_add(1u32).ok_or(Error::<T>::Overflow)?;
-			T::MultiCurrency::reserve(T::GetNativeCurrencyId::get(), &initiator, T::InitiatorPledge::get())?;
-			T::MultiCurrency::reserve(info.exchange_token, &initiator, bond);
-			T::MultiCurrency::reserve(info.currency_id, &initiator, info.total_ico_amount)?;
+	/// The liquidity added to the amm when the ico ended.
+	#[pallet::storage]
+	#[pallet::getter(fn ico_liquidity_of)]
+	pub type IcoLiquidityOf<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		AssetId,
+		Blake2_128Concat,
+		u32,
+		IcoLiquidity<T::BlockNumber, MultiBalanceOf<T>>,
+		OptionQuery,
+	>;
 
-			<PendingIco<T>>::mutate(|h| {
-				h.push(PendingInfo {
-					ico: IcoInfo {
-						desc: info.desc.clone(),
-						start_time: None,
-						is_already_kyc: Self::is_already_kyc(&initiator),
-						initiator: initiator.clone(),
-						total_usdt: MultiBalanceOf::<T>::from(0u32),
-						tag: None,
-						is_terminated: false,
-						project_name: metadata.name.clone(),
-						token_symbol: metadata.symbol.clone(),
-						decimals: metadata.decimals,
-						index: Some(total_num),
-						already_released_proportion: Percent::from_percent(0u8),
-						currency_id: info.currency_id,
-						official_website: info.official_website.clone(),
-						is_must_kyc: info.is_must_kyc,
-						user_ico_max_times: info.user_ico_max_times,
-						total_issuance: info.total_issuance,
+	#[pallet::storage]
+	#[pallet::getter(fn lottery_of)]
+	pub type LotteryOf<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		AssetId,
+		Blake2_128Concat,
+		u32,
+		LotteryInfo<T::BlockNumber, MultiBalanceOf<T>, T::Hash>,
+		OptionQuery,
+	>;
+
+	/// The users registered for the lottery ico and their tickets, in the order they registered.
+	#[pallet::storage]
+	#[pallet::getter(fn lottery_entrants_of)]
+	pub type LotteryEntrantsOf<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, AssetId, Blake2_128Concat, u32, Vec<(T::AccountId, u32)>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn is_lottery_winner)]
+	pub type LotteryWinnerOf<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, (AssetId, u32), Blake2_128Concat, T::AccountId, bool, ValueQuery>;
+
+	/// The compensation of the participants of the ico terminated as a fraud.
+	#[pallet::storage]
+	#[pallet::getter(fn ico_compensation_of)]
+	pub type IcoCompensationOf<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		AssetId,
+		Blake2_128Concat,
+		u32,
+		IcoCompensation<MultiBalanceOf<T>>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn is_compensation_claimed)]
+	pub type CompensationClaimedOf<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, (AssetId, u32), Blake2_128Concat, T::AccountId, bool, ValueQuery>;
+
+	#[pallet::type_value]
+	pub fn RewardEconomicsOnEmpty<T: Config>() -> RewardEconomics<MultiBalanceOf<T>> {
+		RewardEconomics {
+			total_reward: T::IcoTotalReward::get(),
+			inviter_reward_proportion: T::InviterRewardProportion::get(),
+			invitee_reward_proportion: T::InviteeRewardProportion::get(),
+			initiator_pledge: T::InitiatorPledge::get(),
+		}
+	}
+
+	/// The reward economics of the ICOs, the constants of the config until governance sets it.
+	#[pallet::storage]
+	#[pallet::getter(fn reward_economics)]
+	pub type IcoRewardEconomics<T: Config> =
+		StorageValue<_, RewardEconomics<MultiBalanceOf<T>>, ValueQuery, RewardEconomicsOnEmpty<T>>;
+
+	/// The project tokens funded by the initiator to reward the participants on top of DICO.
+	#[pallet::storage]
+	#[pallet::getter(fn reward_pool_of)]
+	pub type RewardPoolOf<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		AssetId,
+		Blake2_128Concat,
+		u32,
+		RewardPool<MultiBalanceOf<T>>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn is_pool_reward_claimed)]
+	pub type PoolRewardClaimedOf<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, (AssetId, u32), Blake2_128Concat, T::AccountId, bool, ValueQuery>;
+
+	/// The project tokens that all the participants of the ico get.
+	#[pallet::storage]
+	#[pallet::getter(fn total_allocation_of)]
+	pub type TotalAllocationOf<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, AssetId, Blake2_128Concat, u32, MultiBalanceOf<T>, ValueQuery>;
+
+	/// The tokens of the protection fund that the participants of fraud ICOs have not claimed.
+	#[pallet::storage]
+	#[pallet::getter(fn protection_fund_committed)]
+	pub type ProtectionFundCommitted<T: Config> = StorageMap<_, Twox64Concat, AssetId, MultiBalanceOf<T>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn is_receipt_ico)]
+	pub type IsReceiptIco<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, AssetId, Blake2_128Concat, u32, bool, ValueQuery>;
+
+	/// The nft receipt of the participation of the user.
+	#[pallet::storage]
+	#[pallet::getter(fn receipt_of)]
+	pub type ReceiptOf<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, (AssetId, u32), Blake2_128Concat, T::AccountId, ReceiptIdOf<T>>;
+
+	/// The ico and the participant of the nft receipt.
+	#[pallet::storage]
+	#[pallet::getter(fn receipt_position_of)]
+	pub type ReceiptPositionOf<T: Config> =
+		StorageMap<_, Blake2_128Concat, ReceiptIdOf<T>, (AssetId, u32, T::AccountId)>;
+
+	/// The amount raised in each payment token of an ico, released to the initiator separately.
+	#[pallet::storage]
+	#[pallet::getter(fn payment_raised_of)]
+	pub type PaymentRaisedOf<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		(AssetId, u32),
+		Blake2_128Concat,
+		AssetId,
+		PaymentRaised<MultiBalanceOf<T>>,
+		ValueQuery,
+	>;
+
+	/// The payment tokens the user paid in an ico, (payment token, amount, amount in the exchange
+	/// token).
+	#[pallet::storage]
+	#[pallet::getter(fn user_payments_of)]
+	pub type UserPaymentsOf<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		(AssetId, u32),
+		Vec<(AssetId, MultiBalanceOf<T>, MultiBalanceOf<T>)>,
+		ValueQuery,
+	>;
+
+	/// The owner of the invite code.
+	#[pallet::storage]
+	#[pallet::getter(fn invite_code_owner)]
+	pub type InviteCodeOwnerOf<T: Config> = StorageMap<_, Blake2_128Concat, Vec<u8>, T::AccountId, OptionQuery>;
+
+	/// The invite code of the user.
+	#[pallet::storage]
+	#[pallet::getter(fn invite_code_of)]
+	pub type InviteCodeOf<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, Vec<u8>, OptionQuery>;
+
+	/// The user who referred the user, in all icoes.
+	#[pallet::storage]
+	#[pallet::getter(fn referrer_of)]
+	pub type ReferrerOf<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId, OptionQuery>;
+
+	/// The reward proportions of the referrers by level, the first is the inviter of the user.
+	/// The inviter reward proportion of `RewardEconomics` is used if it is empty.
+	#[pallet::storage]
+	#[pallet::getter(fn referral_reward_proportions)]
+	pub type ReferralRewardProportions<T: Config> = StorageValue<_, Vec<Percent>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn is_allocation_claimed)]
+	pub type AllocationClaimedOf<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, (AssetId, u32), Blake2_128Concat, T::AccountId, bool, ValueQuery>;
+
+	#[pallet::storage]
+	pub type IcoMinUsdtAmount<T: Config> = StorageValue<_, MultiBalanceOf<T>, ValueQuery>;
+
+	#[pallet::storage]
+	pub type IcoMaxUsdtAmount<T: Config> = StorageValue<_, MultiBalanceOf<T>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn initiate_icoes_of)]
+	pub type InitiatedIcoesOf<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Vec<InitiatedIco<AssetId, IcoStatus, MultiBalanceOf<T>>>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn icoes_of)]
+	pub type IcoesOf<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, Vec<(AssetId, u32)>, ValueQuery>;
+
+	#[pallet::storage]
+	pub type PassedIcoes<T: Config> = StorageValue<_, Vec<(AssetId, u32)>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn power_multiple_of)]
+	pub type PowerMultipleOf<T: Config> = StorageMap<_, Blake2_128Concat, AssetId, PowerMultiple, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn total_power_of)]
+	pub type TotalPowerOf<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, MultiBalanceOf<T>, ValueQuery>;
+
+	/// The block when `TotalPowerOf` of the user was last updated, the power decays from it.
+	#[pallet::storage]
+	#[pallet::getter(fn power_checkpoint_of)]
+	pub type PowerCheckpointOf<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, T::BlockNumber, ValueQuery>;
+
+	/// The user that the power of the user is delegated to.
+	#[pallet::storage]
+	#[pallet::getter(fn power_delegate_of)]
+	pub type PowerDelegateOf<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId, OptionQuery>;
+
+	/// The users that delegate their power to the user.
+	#[pallet::storage]
+	#[pallet::getter(fn power_delegators_of)]
+	pub type PowerDelegatorsOf<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, Vec<T::AccountId>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn invite_info_of)]
+	pub type InviteInfoOf<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		AssetId,
+		Vec<InviteInfo<T::AccountId, MultiBalanceOf<T>, AssetId>>,
+		ValueQuery,
+	>;
+
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	#[pallet::storage_version(STORAGE_VERSION)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// The project party initiates an ICO
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::initiate_ico())]
+		pub fn initiate_ico(
+			origin: OriginFor<T>,
+			info: IcoParameters<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode>,
+		) -> DispatchResult {
+			let initiator = ensure_signed(origin)?;
+
+			Self::initiate_ico_simple_check(&info, &initiator)?;
+			let exchange_token_metadata = T::CurrenciesHandler::get_metadata(info.exchange_token)?;
+			let metadata = T::CurrenciesHandler::get_metadata(info.currency_id)?;
+
+			let bond = T::InitiatorBond::get() * info.exchange_token_total_amount;
+			let total_num = <TotalNum<T>>::get().checked_add(1u32).ok_or(Error::<T>::Overflow)?;
+			let pledge = Self::reward_economics().initiator_pledge;
+			T::MultiCurrency::reserve(T::GetNativeCurrencyId::get(), &initiator, pledge)?;
+			T::MultiCurrency::reserve(info.exchange_token, &initiator, bond);
+			T::MultiCurrency::reserve(info.currency_id, &initiator, info.total_ico_amount)?;
+			// The project tokens paired with the raised exchange tokens at most.
+			if let Some(liquidity) = info.liquidity.as_ref() {
+				T::MultiCurrency::reserve(
+					info.currency_id,
+					&initiator,
+					liquidity.proportion * info.total_ico_amount,
+				)?;
+			}
+
+			<PendingIco<T>>::insert(
+				info.currency_id,
+				PendingInfo {
+					ico: IcoInfo {
+						desc: info.desc.clone(),
+						start_time: None,
+						is_already_kyc: Self::is_already_kyc(&initiator),
+						initiator: initiator.clone(),
+						total_usdt: MultiBalanceOf::<T>::from(0u32),
+						tag: None,
+						is_terminated: false,
+						project_name: metadata.name.clone(),
+						token_symbol: metadata.symbol.clone(),
+						decimals: metadata.decimals,
+						index: Some(total_num),
+						already_released_proportion: Percent::from_percent(0u8),
+						currency_id: info.currency_id,
+						official_website: info.official_website.clone(),
+						is_must_kyc: info.is_must_kyc,
+						user_ico_max_times: info.user_ico_max_times,
+						total_issuance: info.total_issuance,
 						total_circulation: info.total_circulation,
 						ico_duration: info.ico_duration,
 						total_ico_amount: info.total_ico_amount,
@@ -499,12 +1280,69 @@ pub mod pallet {
 						unlock_duration: info.unlock_duration,
 						per_duration_unlock_amount: info.per_duration_unlock_amount,
 					},
-					pledge_dico: T::InitiatorPledge::get(),
+					pledge_dico: pledge,
 					pledge_exchange_token: bond,
-				})
-			});
+				},
+			);
 
 			<TotalNum<T>>::put(total_num);
+			if let Some(soft_cap) = info.soft_cap {
+				SoftCapOf::<T>::insert(info.currency_id, total_num, soft_cap);
+			}
+			if info.mode != IcoMode::default() {
+				IcoModeOf::<T>::insert(info.currency_id, total_num, info.mode);
+			}
+			if let (IcoMode::DutchAuction, Some(auction)) = (info.mode, info.dutch_auction.as_ref()) {
+				DutchAuctionOf::<T>::insert(
+					info.currency_id,
+					total_num,
+					DutchAuctionInfo {
+						start_exchange_token_total_amount: auction.start_exchange_token_total_amount,
+						decay_per_block: auction.decay_per_block,
+						sold_amount: MultiBalanceOf::<T>::from(0u32),
+						clearing_exchange_token_total_amount: None,
+					},
+				);
+			}
+			if let Some(root) = info.allowlist_root {
+				AllowlistRootOf::<T>::insert(info.currency_id, total_num, root);
+			}
+			if !info.area_caps.is_empty() {
+				AreaCapsOf::<T>::insert(info.currency_id, total_num, &info.area_caps);
+			}
+			if !info.kyc_level_caps.is_empty() {
+				KycLevelCapsOf::<T>::insert(info.currency_id, total_num, &info.kyc_level_caps);
+			}
+			if let Some(vesting) = info.vesting.as_ref() {
+				VestingOf::<T>::insert(info.currency_id, total_num, vesting);
+			}
+			if let Some(milestones) = info.milestones.as_ref() {
+				MilestonesOf::<T>::insert(info.currency_id, total_num, milestones);
+			}
+			if !info.payment_tokens.is_empty() {
+				PaymentTokensOf::<T>::insert(info.currency_id, total_num, &info.payment_tokens);
+			}
+			if let Some(liquidity) = info.liquidity.as_ref() {
+				LiquidityOf::<T>::insert(info.currency_id, total_num, liquidity);
+			}
+			if let (IcoMode::Lottery, Some(lottery)) = (info.mode, info.lottery.as_ref()) {
+				LotteryOf::<T>::insert(
+					info.currency_id,
+					total_num,
+					LotteryInfo {
+						registration_duration: lottery.registration_duration,
+						ticket_power: lottery.ticket_power,
+						max_tickets_per_user: lottery.max_tickets_per_user,
+						winner_count: lottery.winner_count,
+						total_tickets: 0,
+						random_seed: None,
+						is_drawn: false,
+					},
+				);
+			}
+			if info.mint_receipts {
+				IsReceiptIco::<T>::insert(info.currency_id, total_num, true);
+			}
 
 			InitiatedIcoesOf::<T>::mutate(&initiator, |h| {
 				h.push(InitiatedIco {
@@ -531,14 +1369,9 @@ pub mod pallet {
 		pub fn permit_ico(origin: OriginFor<T>, currency_id: AssetId) -> DispatchResult {
 			T::PermitIcoOrigin::try_origin(origin).map_err(|_| Error::<T>::BadOrigin)?;
 
-			let mut pending_ico = <PendingIco<T>>::get();
-			let pos_opt = pending_ico.iter().position(|h| currency_id == h.ico.currency_id);
-
-			match pos_opt {
+			match <PendingIco<T>>::take(currency_id) {
 				None => return Err(Error::<T>::PendingIcoNotExists123)?,
-				Some(pos) => {
-					let mut pending_info = pending_ico.swap_remove(pos);
-
+				Some(mut pending_info) => {
 					let bond_amount = pending_info.pledge_exchange_token;
 
 					T::MultiCurrency::repatriate_reserved(
@@ -548,12 +1381,6 @@ pub mod pallet {
 						bond_amount,
 						BalanceStatus::Free,
 					);
-					T::MultiCurrency::unreserve(
-						T::GetNativeCurrencyId::get(),
-						&pending_info.ico.initiator,
-						pending_info.pledge_dico,
-					);
-					<PendingIco<T>>::put(pending_ico);
 					pending_info.ico.start_time = Some(
 						Self::now()
 							.checked_add(&T::ChillDuration::get())
@@ -565,6 +1392,17 @@ pub mod pallet {
 						None => Err(Error::<T>::IcoIndexNotExists)?,
 						Some(x) => index = x,
 					}
+					// The pledge is kept until the end of the ICO.
+					InitiatorPledgeOf::<T>::insert(currency_id, index, pending_info.pledge_dico);
+					if let (Some(lottery), Some(start_time)) =
+						(Self::lottery_of(currency_id, index), pending_info.ico.start_time)
+					{
+						Self::schedule_lottery_seed(
+							currency_id,
+							index,
+							start_time.saturating_add(lottery.registration_duration),
+						)?;
+					}
 					<Ico<T>>::insert(&pending_info.ico.currency_id, index, &pending_info.ico);
 					Indexs::<T>::mutate(currency_id, |h| h.push(index));
 					IsUnservePledge::<T>::insert(currency_id, index, true);
@@ -585,13 +1423,9 @@ pub mod pallet {
 		pub fn reject_ico(origin: OriginFor<T>, currency_id: AssetId) -> DispatchResult {
 			T::RejectIcoOrigin::try_origin(origin).map_err(|_| Error::<T>::BadOrigin)?;
 
-			let mut pending_ico = <PendingIco<T>>::get();
-			let pos_opt = pending_ico.iter().position(|h| currency_id == h.ico.currency_id);
-
-			match pos_opt {
+			match <PendingIco<T>>::take(currency_id) {
 				None => return Err(Error::<T>::PendingIcoNotExists123)?,
-				Some(pos) => {
-					let pending_info = pending_ico.swap_remove(pos);
+				Some(pending_info) => {
 					let mut index: u32 = 0;
 					match pending_info.ico.index {
 						None => Err(Error::<T>::IcoIndexNotExists)?,
@@ -610,9 +1444,12 @@ pub mod pallet {
 					T::MultiCurrency::unreserve(
 						pending_info.ico.currency_id,
 						&pending_info.ico.initiator,
-						pending_info.ico.total_ico_amount,
+						pending_info
+							.ico
+							.total_ico_amount
+							.saturating_add(Self::liquidity_project_token_reserved(&pending_info.ico)),
 					);
-					<PendingIco<T>>::put(pending_ico);
+					Self::remove_ico_settings(currency_id, index);
 					Self::update_initiate_icoes(currency_id, index, &pending_info.ico.initiator, IcoStatus::Failed);
 					Self::deposit_event(Event::RejectIco(
 						pending_info.ico.initiator,
@@ -625,6 +1462,11 @@ pub mod pallet {
 		/// call id:801
 		///
 		/// User participation in ICO
+		///
+		/// `allowlist_proof` is required the first time the user joins an ICO with an allowlist.
+		///
+		/// `min_power` is the least power the user accepts to get, and `max_price` is the most
+		/// exchange token the user accepts to pay for one whole project token.
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::join())]
 		#[transactional]
 		pub fn join(
@@ -633,54 +1475,74 @@ pub mod pallet {
 			index: u32,
 			amount: MultiBalanceOf<T>,
 			inviter: Option<<T::Lookup as StaticLookup>::Source>,
+			allowlist_proof: Option<AllowlistProof<MultiBalanceOf<T>>>,
+			min_power: Option<MultiBalanceOf<T>>,
+			max_price: Option<MultiBalanceOf<T>>,
 		) -> DispatchResult {
 			let user = ensure_signed(origin)?;
+			Self::do_join(
+				user,
+				currency_id,
+				index,
+				None,
+				amount,
+				inviter,
+				allowlist_proof,
+				min_power,
+				max_price,
+			)
+		}
 
-			let inviter = match inviter {
-				None => None,
-				Some(x) => Some(T::Lookup::lookup(x)?),
-			};
-
-			if let Some(inv) = inviter.as_ref() {
-				ensure!(&user != inv, Error::<T>::CanNotInviteYouself);
-				ensure!(
-					Self::is_member(inv, currency_id, index, false),
-					Error::<T>::InviterNotInIco
-				);
-			}
-
-			let mut ico = <Ico<T>>::get(currency_id, index).ok_or(Error::<T>::IcoNotExists123)?;
-			let exchange_token_decimals = T::CurrenciesHandler::get_metadata(ico.exchange_token)?.decimals;
-			let mut total_usdt =
-				Self::exchange_token_convert_usdt(ico.exchange_token, exchange_token_decimals, amount)?;
-			ensure!(total_usdt != MultiBalanceOf::<T>::from(0u32), Error::<T>::PowerIsZero);
-			let new_total_usdt = ico.total_usdt.checked_add(&total_usdt).ok_or(Error::<T>::Overflow)?;
-
-			Self::join_simple_check(&ico, amount, ico.currency_id, index, &user, total_usdt)?;
-
-			let user_exchange_amount = Self::swap(&user, amount, &ico)?;
-
-			Self::insert_ico_assets_info(&user, &ico, amount, total_usdt, &inviter);
-			Self::update_user_icoes(&user, currency_id, index);
-			// Must be at the end
-			ico.total_usdt = new_total_usdt;
-			Ico::<T>::insert(currency_id, index, &ico);
-			Self::add_user_power(&user, total_usdt)?;
-			Self::insert_invite_info(&user, inviter, currency_id, index);
-
-			Self::deposit_event(Event::Join(user, currency_id, index, amount, user_exchange_amount));
-			Ok(())
+		/// call id:807
+		///
+		/// User participation in ICO with one of the payment tokens of the ICO.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::join_with_payment_token())]
+		#[transactional]
+		pub fn join_with_payment_token(
+			origin: OriginFor<T>,
+			currency_id: AssetId,
+			index: u32,
+			payment_token: AssetId,
+			amount: MultiBalanceOf<T>,
+			inviter: Option<<T::Lookup as StaticLookup>::Source>,
+			allowlist_proof: Option<AllowlistProof<MultiBalanceOf<T>>>,
+			min_power: Option<MultiBalanceOf<T>>,
+			max_price: Option<MultiBalanceOf<T>>,
+		) -> DispatchResult {
+			let user = ensure_signed(origin)?;
+			Self::do_join(
+				user,
+				currency_id,
+				index,
+				Some(payment_token),
+				amount,
+				inviter,
+				allowlist_proof,
+				min_power,
+				max_price,
+			)
 		}
 
 		/// DAO terminate the ico
+		///
+		/// The members of a milestone ico can terminate it with the threshold of the ico when a
+		/// milestone deadline is missed.
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::terminate_ico())]
 		pub fn terminate_ico(origin: OriginFor<T>, currency_id: AssetId, index: u32) -> DispatchResult {
-			T::TerminateIcoOrigin::try_origin(origin).map_err(|_| Error::<T>::BadOrigin)?;
-
 			let mut ico = <Ico<T>>::get(currency_id, index).ok_or(Error::<T>::IcoNotExists123)?;
 			ensure!(!ico.is_terminated, Error::<T>::IcoTerminated);
 
+			let is_milestone_missed = Self::is_milestone_missed(&ico);
+			let members = Self::ico_members_of(&origin, currency_id, index)?;
+			if T::TerminateIcoOrigin::try_origin(origin).is_err() {
+				let (yes, total) = members.ok_or(Error::<T>::BadOrigin)?;
+				ensure!(is_milestone_missed, Error::<T>::MilestoneNotMissed);
+				let info = Self::milestones_of(currency_id, index).ok_or(Error::<T>::BadOrigin)?;
+				ensure!(yes >= info.threshold * total, Error::<T>::MilestoneThresholdNotReached);
+			}
+
 			match ico.start_time.as_ref() {
+				Some(_) if is_milestone_missed => {}
 				Some(time) => {
 					#[cfg(test)]
 					println!(
@@ -704,22 +1566,88 @@ pub mod pallet {
 			Ok(())
 		}
 
-		/// The project party requests the release of the funds
-		#[pallet::weight(<T as pallet::Config>::WeightInfo::request_release())]
-		pub fn request_release(
+		/// DAO terminates the ico as a fraud
+		///
+		/// The pledge of the initiator is slashed to the protection fund. The participants claim
+		/// it and `compensation` of the exchange token of the fund, pro-rata to their unreleased
+		/// amount.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::terminate_fraud_ico())]
+		pub fn terminate_fraud_ico(
 			origin: OriginFor<T>,
 			currency_id: AssetId,
 			index: u32,
-			percent: Percent,
+			compensation: MultiBalanceOf<T>,
 		) -> DispatchResult {
-			let initiator = ensure_signed(origin)?;
+			T::TerminateIcoOrigin::ensure_origin(origin)?;
 
-			let ico = <Ico<T>>::get(currency_id, index).ok_or(Error::<T>::IcoNotExists123)?;
-			match ico.start_time.as_ref() {
-				Some(time) => {
-					#[cfg(test)]
-					println!(
-						"time: {:?}, now:{:?}",
+			let mut ico = <Ico<T>>::get(currency_id, index).ok_or(Error::<T>::IcoNotExists123)?;
+			ensure!(ico.start_time.is_some(), Error::<T>::StartTimeNotExists);
+			ensure!(
+				!IcoCompensationOf::<T>::contains_key(currency_id, index),
+				Error::<T>::AlreadyFraudIco
+			);
+			let fund = Self::protection_fund_account();
+			ensure!(
+				Self::protection_fund_committed(ico.exchange_token).saturating_add(compensation)
+					<= T::MultiCurrency::free_balance(ico.exchange_token, &fund),
+				Error::<T>::ProtectionFundNotEnough
+			);
+			let total_unreleased = Self::total_unreleased_amount(&ico);
+			ensure!(
+				total_unreleased > MultiBalanceOf::<T>::from(0u32),
+				Error::<T>::NothingToCompensate
+			);
+
+			let native_currency_id = T::GetNativeCurrencyId::get();
+			let pledge = InitiatorPledgeOf::<T>::take(currency_id, index);
+			let pledge = pledge.saturating_sub(T::MultiCurrency::repatriate_reserved(
+				native_currency_id,
+				&ico.initiator,
+				&fund,
+				pledge,
+				BalanceStatus::Free,
+			)?);
+			ProtectionFundCommitted::<T>::mutate(native_currency_id, |h| *h = h.saturating_add(pledge));
+			ProtectionFundCommitted::<T>::mutate(ico.exchange_token, |h| *h = h.saturating_add(compensation));
+			IcoCompensationOf::<T>::insert(
+				currency_id,
+				index,
+				IcoCompensation {
+					pledge,
+					amount: compensation,
+					claimed_pledge: MultiBalanceOf::<T>::from(0u32),
+					claimed_amount: MultiBalanceOf::<T>::from(0u32),
+					total_unreleased,
+					released_proportion: ico.already_released_proportion,
+				},
+			);
+
+			if !ico.is_terminated {
+				ico.is_terminated = true;
+				<Ico<T>>::insert(currency_id, index, ico);
+				Self::remove_passed_icoes(currency_id, index);
+				Self::deposit_event(Event::TerminateIco(currency_id, index));
+			}
+			Self::deposit_event(Event::TerminateFraudIco(currency_id, index, pledge, compensation));
+			Ok(())
+		}
+
+		/// The project party requests the release of the funds
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::request_release())]
+		pub fn request_release(
+			origin: OriginFor<T>,
+			currency_id: AssetId,
+			index: u32,
+			percent: Percent,
+		) -> DispatchResult {
+			let initiator = ensure_signed(origin)?;
+
+			let ico = <Ico<T>>::get(currency_id, index).ok_or(Error::<T>::IcoNotExists123)?;
+			match ico.start_time.as_ref() {
+				Some(time) => {
+					#[cfg(test)]
+					println!(
+						"time: {:?}, now:{:?}",
 						time.saturating_add(T::ReleaseProtectPeriod::get() * ico.ico_duration),
 						Self::now()
 					);
@@ -734,23 +1662,39 @@ pub mod pallet {
 			ensure!(ico.already_released_proportion < percent, Error::<T>::ProportionTooLow);
 			ensure!(&initiator == &ico.initiator, Error::<T>::NotInitiator);
 			ensure!(!ico.is_terminated, Error::<T>::IcoTerminated);
+			ensure!(Self::is_soft_cap_reached(&ico), Error::<T>::SoftCapNotReached);
 			ensure!(
-				Self::get_request_release_info(currency_id, index).is_none(),
+				Self::release_info(currency_id, index).is_none(),
 				Error::<T>::AlreadyRequest
 			);
 
 			T::NativeCurrency::reserve(&initiator, T::RequestPledge::get())?;
 
-			<RequestReleaseInfo<T>>::mutate(|h| {
-				h.push(Release {
+			if let Some(info) = Self::milestones_of(currency_id, index) {
+				let (milestone, release_proportion) = Self::get_next_milestone(&info, ico.already_released_proportion)
+					.ok_or(Error::<T>::NoMilestoneLeft)?;
+				ensure!(percent == release_proportion, Error::<T>::NotMilestoneProportion);
+				T::IcoProposalHandler::propose_permit_release(
+					currency_id,
+					index,
+					info.threshold,
+					milestone.desc_hash.as_bytes().to_vec(),
+				)?;
+			}
+
+			Self::schedule_request_expiry(currency_id, index, Self::now().saturating_add(T::RequestExpire::get()))?;
+			<RequestReleaseInfo<T>>::insert(
+				currency_id,
+				index,
+				Release {
 					who: initiator,
 					currency_id: currency_id,
 					index: index,
 					request_time: Self::now(),
 					percent: percent,
 					pledge: T::RequestPledge::get(),
-				})
-			});
+				},
+			);
 
 			Self::deposit_event(Event::RequestRelease(currency_id, index, percent));
 			Ok(())
@@ -765,7 +1709,7 @@ pub mod pallet {
 
 			ensure!(&initiator == &ico.initiator, Error::<T>::NotInitiator);
 
-			let release_info_opt = Self::get_request_release_info(currency_id, index);
+			let release_info_opt = Self::release_info(currency_id, index);
 			match release_info_opt {
 				None => Err(Error::<T>::RequestNotExists)?,
 				Some(release_info) => {
@@ -773,7 +1717,7 @@ pub mod pallet {
 					let unreserve = release_info.pledge.saturating_sub(slash);
 					T::OnSlash::on_unbalanced(T::NativeCurrency::slash_reserved(&initiator, slash).0);
 					T::NativeCurrency::unreserve(&initiator, unreserve);
-					Self::remove_request_release_info(Some(currency_id), index, false);
+					<RequestReleaseInfo<T>>::remove(currency_id, index);
 				}
 			}
 
@@ -784,22 +1728,29 @@ pub mod pallet {
 		/// DAO allow asset release
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::permit_release())]
 		pub fn permit_release(origin: OriginFor<T>, currency_id: AssetId, index: u32) -> DispatchResult {
-			T::PermitReleaseOrigin::try_origin(origin).map_err(|_| Error::<T>::BadOrigin)?;
+			let members = Self::ico_members_of(&origin, currency_id, index)?;
+			if T::PermitReleaseOrigin::try_origin(origin).is_err() {
+				// The members of a milestone ico permit with the threshold of the ico.
+				let (yes, total) = members.ok_or(Error::<T>::BadOrigin)?;
+				let info = Self::milestones_of(currency_id, index).ok_or(Error::<T>::BadOrigin)?;
+				ensure!(yes >= info.threshold * total, Error::<T>::MilestoneThresholdNotReached);
+			}
 
 			let mut ico = <Ico<T>>::get(currency_id, index).ok_or(Error::<T>::IcoNotExists123)?;
-			let release_info_opt = Self::get_request_release_info(currency_id, index);
+			let release_info_opt = Self::release_info(currency_id, index);
 
 			match release_info_opt {
 				None => Err(Error::<T>::RequestNotExists)?,
 				Some(release_info) => {
 					ensure!(!ico.is_terminated, Error::<T>::IcoTerminated);
+					ensure!(Self::is_soft_cap_reached(&ico), Error::<T>::SoftCapNotReached);
 					ensure!(
 						release_info.percent > ico.already_released_proportion,
 						Error::<T>::ProportionTooLow
 					);
 
 					ico.already_released_proportion = release_info.percent;
-					Self::remove_request_release_info(Some(currency_id), index, false);
+					<RequestReleaseInfo<T>>::remove(currency_id, index);
 
 					T::NativeCurrency::unreserve(&ico.initiator, release_info.pledge);
 
@@ -814,10 +1765,19 @@ pub mod pallet {
 		///
 		/// Users release their own asset.
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::user_release_ico_amount())]
-		pub fn user_release_ico_amount(origin: OriginFor<T>, currency_id: AssetId, index: u32) -> DispatchResult {
-			let user = ensure_signed(origin)?;
+		pub fn user_release_ico_amount(
+			origin: OriginFor<T>,
+			currency_id: AssetId,
+			index: u32,
+			receipt: Option<ReceiptIdOf<T>>,
+		) -> DispatchResult {
+			let holder = ensure_signed(origin)?;
+			let user = Self::participant_of(&holder, currency_id, index, receipt)?;
 
-			let _ = Self::do_release(user, currency_id, index, true)?;
+			Self::with_receipt_holder(&user, &holder, currency_id, index, || {
+				Self::do_release(user.clone(), &holder, currency_id, index, true)
+			})?;
+			Self::update_receipt(&user, currency_id, index)?;
 			Ok(())
 		}
 
@@ -825,27 +1785,42 @@ pub mod pallet {
 		///
 		/// Users unlock their funds.
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::unlock())]
-		pub fn unlock(origin: OriginFor<T>, currency_id: AssetId, index: u32) -> DispatchResult {
-			let user = ensure_signed(origin)?;
-
-			ensure!(
-				<IcoLocks<T>>::contains_key(&user, &currency_id),
-				Error::<T>::LockIsEmpty
-			);
-
-			<IcoLocks<T>>::try_mutate(&user, &currency_id, |h| {
-				let (total, locks) = Self::unlock_asset(&user, &currency_id, index, true, h);
-				if total == <MultiBalanceOf<T>>::from(0u32) {
-					if cfg!(any(feature = "std", feature = "runtime-benchmarks", test)) {
-						return Ok(());
-					}
-					return Err(Error::<T>::UnlockAmountIsZero);
-				} else {
-					*h = locks;
-					Self::deposit_event(Event::UnlockAsset(currency_id, user.clone(), total));
-					Ok(())
+		pub fn unlock(
+			origin: OriginFor<T>,
+			currency_id: AssetId,
+			index: u32,
+			receipt: Option<ReceiptIdOf<T>>,
+		) -> DispatchResult {
+			let holder = ensure_signed(origin)?;
+			let user = Self::participant_of(&holder, currency_id, index, receipt)?;
+
+			// The vesting schedules are kept by the holder of the receipt at the release.
+			let is_vesting = VestingSchedules::<T>::contains_key(&holder, &currency_id);
+			let is_locked = <IcoLocks<T>>::contains_key(&user, &currency_id);
+			ensure!(is_vesting || is_locked, Error::<T>::LockIsEmpty);
+
+			// The vesting schedules and the locks of other icoes in the currency are both unlocked.
+			let mut total = Self::unlock_vesting(&holder, currency_id, index, true);
+			if is_locked {
+				let unlocked = Self::with_receipt_holder(&user, &holder, currency_id, index, || {
+					Ok(<IcoLocks<T>>::mutate(&user, &currency_id, |h| {
+						let (unlocked, locks) = Self::unlock_asset(&user, &currency_id, index, true, h);
+						if unlocked != <MultiBalanceOf<T>>::from(0u32) {
+							*h = locks;
+						}
+						unlocked
+					}))
+				})?;
+				total = total.saturating_add(unlocked);
+			}
+			if total == <MultiBalanceOf<T>>::from(0u32) {
+				if !is_vesting && cfg!(any(feature = "std", feature = "runtime-benchmarks", test)) {
+					return Ok(());
 				}
-			})?;
+				return Err(Error::<T>::UnlockAmountIsZero)?;
+			}
+
+			Self::deposit_event(Event::UnlockAsset(currency_id, holder, total));
 			Ok(())
 		}
 
@@ -926,12 +1901,24 @@ pub mod pallet {
 		///
 		/// When the end of the ico, users get the reward.
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::get_reward())]
-		pub fn get_reward(origin: OriginFor<T>, currency_id: AssetId, index: u32) -> DispatchResult {
-			let user = ensure_signed(origin)?;
+		pub fn get_reward(
+			origin: OriginFor<T>,
+			currency_id: AssetId,
+			index: u32,
+			receipt: Option<ReceiptIdOf<T>>,
+		) -> DispatchResult {
+			let holder = ensure_signed(origin)?;
+			let user = Self::participant_of(&holder, currency_id, index, receipt)?;
 
-			let reward = Self::do_reward(&user, currency_id, index, true)?;
+			let (reward, pool_reward) = Self::with_receipt_holder(&user, &holder, currency_id, index, || {
+				let reward = Self::do_reward(&user, currency_id, index, true)?;
+				Ok((reward, Self::reward_from_pool(&user, currency_id, index)?))
+			})?;
 
-			Self::deposit_event(Event::GetReward(currency_id, index, user, reward));
+			Self::deposit_event(Event::GetReward(currency_id, index, holder.clone(), reward));
+			if pool_reward > MultiBalanceOf::<T>::from(0u32) {
+				Self::deposit_event(Event::GetPoolReward(currency_id, index, holder, pool_reward));
+			}
 			Ok(())
 		}
 
@@ -949,582 +1936,2732 @@ pub mod pallet {
 			Self::deposit_event(Event::SetAssetPowerMultiple(currency_id, multiple));
 			Ok(())
 		}
-	}
-
-	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-		fn on_finalize(n: T::BlockNumber) {
-			Self::remove_request_release_info(None, 0u32, true);
-		}
-	}
 
-	#[pallet::event]
-	#[pallet::generate_deposit(pub (super) fn deposit_event)]
-	pub enum Event<T: Config> {
-		Test(T::AccountId),
-		InitiateIco(T::AccountId, AssetId, MultiBalanceOf<T>),
-		PermitIco(T::AccountId, AssetId),
-		RejectIco(T::AccountId, AssetId),
-		Join(T::AccountId, AssetId, u32, MultiBalanceOf<T>, MultiBalanceOf<T>),
-		TerminateIco(AssetId, u32),
-		RequestRelease(AssetId, u32, Percent),
-		CancelRequest(AssetId, u32),
-		PermitRelease(
-			AssetId,
-			u32,
-			Release<<T as system::Config>::AccountId, <T as system::Config>::BlockNumber, AssetId, BalanceOf<T>>,
-		),
-		UnlockAsset(AssetId, T::AccountId, MultiBalanceOf<T>),
-		GetReward(AssetId, u32, T::AccountId, MultiBalanceOf<T>),
-		UserReleaseIcoAmount(AssetId, u32, MultiBalanceOf<T>),
-		InitiatorSetIcoAmountBound(AssetId, u32, MultiBalanceOf<T>, MultiBalanceOf<T>),
-		SetSystemIcoAmountBound(MultiBalanceOf<T>, MultiBalanceOf<T>),
-		SetIcoMaxCount(AssetId, u32, u8),
-		TerminatedGiveBackAmount(T::AccountId, AssetId, u32, MultiBalanceOf<T>),
-		UnreservedInitiatorRemainPledgeAmount(AssetId, u32, MultiBalanceOf<T>),
-		SetAssetPowerMultiple(AssetId, PowerMultiple),
-	}
+		/// call id:805
+		///
+		/// When the ico does not reach its soft cap, users get back their exchange tokens
+		/// and the initiator gets back the project tokens and the pledge.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::refund())]
+		#[transactional]
+		pub fn refund(origin: OriginFor<T>, currency_id: AssetId, index: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
 
-	#[pallet::error]
-	pub enum Error<T> {
-		BeingIco,
-		PendingIcoNotExists123,
-		IcoNotExists123,
-		InExcludeArea,
-		BadOrigin,
-		IsPendingIco,
-		InitiatorIsYourself,
-		AmountIsZero,
-		TokenShouldBeDifferent,
-		DurationIsZero,
-		IcoExpire,
-		IcoNotExpireOrTerminated,
-		IcoTerminated,
-		NotInitiator,
-		AlreadyRequest,
-		RequestNotExists,
-		RequestExpire,
-		LockIsEmpty,
-		UnlockAmountIsZero,
-		NotIcoMember,
-		Overflow,
-		ProportionTooLow,
-		UnreleaseAmountIsZero,
-		RewardIsZero,
-		IcoTimesToMax,
-		NativeCurrencyId,
-		UnreleasedAmountIsZero,
-		AmountNotMeetProjectRequirement,
-		AmountNotMeetSystemRequirement,
-		MaxLessThanMin,
-		MaxIsZero,
-		DuplicateSet,
-		CirculationMoreThanIssuance,
-		IcoAmountMoreThanCirculation,
-		MinAmountTooLow,
-		MaxAmountTooLarge,
-		MaxAmountIsZero,
-		BalanceInsufficient,
-		UserIcoAmountTooLow,
-		UserIcoAmountToMax,
-		ProjectIcoAmountToMax,
-		DivByZero,
-		IsNotStartIcoTime,
-		InviterNotInIco,
-		CanNotInviteYouself,
-		TerminateProtectTime,
-		ExchangeTokenBalanceTooLow,
-		AlreadyGetReward,
-		ReleaseProtectTime,
-		NotKycUser,
-		UserAreaNotExists,
-		IcoIndexNotExists,
-		StartTimeNotExists,
-		MultipleNotChange,
-		DownIsZero,
-		PowerIsZero,
-		PriceNotExists,
-	}
+			let ico = <Ico<T>>::get(currency_id, index).ok_or(Error::<T>::IcoNotExists123)?;
+			ensure!(Self::is_soft_cap_failed(&ico), Error::<T>::IcoNotFailed);
+			Self::ensure_receipt_owner(&who, currency_id, index)?;
 
-	impl<T: Config> Pallet<T> {
-		/// Whether or not project member(exclude project party）
-		pub fn is_member(who: &T::AccountId, currency_id: AssetId, index: u32, is_exclude_manager: bool) -> bool {
-			let mut unrelease_info_vec = UnReleaseAssets::<T>::get(&who);
-			if let Some(pos) = unrelease_info_vec
-				.iter()
-				.position(|h| h.currency_id == currency_id && h.index == index)
-			{
-				let info = unrelease_info_vec.swap_remove(pos);
-				if is_exclude_manager {
-					if info.unreleased_currency_id != currency_id {
-						return false;
-					}
-				}
-				return true;
+			let amount = if who == ico.initiator {
+				Self::refund_initiator(&ico)?
 			} else {
-				false
-			}
+				Self::refund_user(&who, &ico)?
+			};
+
+			Self::deposit_event(Event::Refund(currency_id, index, who, amount));
+			Ok(())
 		}
 
-		fn do_reward(
-			user: &T::AccountId,
-			currency_id: AssetId,
-			index: u32,
-			is_do: bool,
-		) -> result::Result<MultiBalanceOf<T>, DispatchError> {
-			let mut ico = <Ico<T>>::get(currency_id, index).ok_or(Error::<T>::IcoNotExists123)?;
-			ensure!(&ico.initiator != user, Error::<T>::InitiatorIsYourself);
+		/// call id:806
+		///
+		/// When the end of the pro-rata or dutch auction ico, users get their allocation and the
+		/// excess exchange tokens back.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::claim_allocation())]
+		#[transactional]
+		pub fn claim_allocation(origin: OriginFor<T>, currency_id: AssetId, index: u32) -> DispatchResult {
+			let user = ensure_signed(origin)?;
 
+			let ico = <Ico<T>>::get(currency_id, index).ok_or(Error::<T>::IcoNotExists123)?;
+			ensure!(
+				Self::ico_mode_of(currency_id, index).is_settled_at_end(),
+				Error::<T>::NotProRataIco
+			);
 			ensure!(Self::is_ico_expire(&ico), Error::<T>::IcoNotExpireOrTerminated);
+			ensure!(!Self::is_soft_cap_failed(&ico), Error::<T>::IcoFailed);
+			ensure!(
+				!Self::is_allocation_claimed((currency_id, index), &user),
+				Error::<T>::AllocationAlreadyClaimed
+			);
 
-			let total_reward = Self::calculate_total_reward(&ico);
-			let asset_info =
-				Self::get_unrelease_asset_info(&user, currency_id, index).ok_or(Error::<T>::NotIcoMember)?;
-			ensure!(asset_info.reward.is_none(), Error::<T>::AlreadyGetReward);
-			let tags = asset_info.tags;
-
-			let classify = Self::split_user_amount(ico.total_usdt, tags);
-			let mut reward = Self::caculate_user_reward(classify, ico.total_usdt, total_reward);
-			ensure!(reward > MultiBalanceOf::<T>::from(0u32), Error::<T>::RewardIsZero);
-			reward = Self::reward(&user, asset_info.inviter, reward, is_do, currency_id, index);
-			if is_do {
-				UnReleaseAssets::<T>::mutate(user.clone(), |vec| {
-					if let Some(pos) = vec
-						.iter()
-						.position(|h| h.currency_id == currency_id && h.index == index)
-					{
-						let mut unreleased = vec.swap_remove(pos);
-						unreleased.reward = Some(reward);
-						vec.push(unreleased);
-					}
-				});
-
-				if ico.tag == None {
-					let mut new_usdt = TotalUsdt::<T>::get();
-					new_usdt = new_usdt.saturating_add(ico.total_usdt);
-					ico.tag = Some(new_usdt);
-					Ico::<T>::insert(currency_id, index, ico);
-					TotalUsdt::<T>::put(new_usdt);
-				}
-			}
+			let (allocation, excess) = Self::settle_allocation(&user, &ico)?;
+			Self::update_receipt(&user, currency_id, index)?;
 
-			Ok(reward)
+			Self::deposit_event(Event::ClaimAllocation(currency_id, index, user, allocation, excess));
+			Ok(())
 		}
 
-		fn reward(
-			user: &T::AccountId,
-			inviter: Option<T::AccountId>,
-			amount: MultiBalanceOf<T>,
-			is_do: bool,
+		/// call id:808
+		///
+		/// The project party gets the raised `payment_token` by the proportion that has been
+		/// released.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::release_payment_token())]
+		#[transactional]
+		pub fn release_payment_token(
+			origin: OriginFor<T>,
 			currency_id: AssetId,
 			index: u32,
-		) -> MultiBalanceOf<T> {
-			let mut user_total_amount = amount;
-			if let Some(inviter) = inviter {
-				let user_p = T::InviteeRewardProportion::get() * amount;
-				user_total_amount.saturating_sub(user_p);
-				if is_do {
-					T::MultiCurrency::deposit(T::GetNativeCurrencyId::get(), &user, user_p);
+			payment_token: AssetId,
+		) -> DispatchResult {
+			let initiator = ensure_signed(origin)?;
 
-					let inviter_reward = T::InviterRewardProportion::get() * amount;
-					T::MultiCurrency::deposit(T::GetNativeCurrencyId::get(), &inviter, inviter_reward);
-					Self::update_invite_info(&user, &inviter, currency_id, index, inviter_reward);
-				}
-			}
-			if is_do {
-				T::MultiCurrency::deposit(T::GetNativeCurrencyId::get(), &user, amount);
-			}
+			let ico = <Ico<T>>::get(currency_id, index).ok_or(Error::<T>::IcoNotExists123)?;
+			ensure!(&initiator == &ico.initiator, Error::<T>::NotInitiator);
+			ensure!(!Self::is_soft_cap_failed(&ico), Error::<T>::IcoFailed);
 
-			user_total_amount
+			let mut raised = Self::payment_raised_of((currency_id, index), payment_token);
+			let should_released =
+				(ico.already_released_proportion * raised.raised).min(raised.raised.saturating_sub(raised.refund));
+			let amount = should_released.saturating_sub(raised.released);
+			ensure!(
+				amount > MultiBalanceOf::<T>::from(0u32),
+				Error::<T>::UnreleaseAmountIsZero
+			);
+
+			Self::release_to_initiator(payment_token, &initiator, amount);
+			raised.released = raised.released.saturating_add(amount);
+			PaymentRaisedOf::<T>::insert((currency_id, index), payment_token, raised);
+
+			Self::deposit_event(Event::ReleasePaymentToken(currency_id, index, payment_token, amount));
+			Ok(())
 		}
 
-		fn is_already_kyc(who: &T::AccountId) -> bool {
-			if T::KycHandler::get_user_area(who).is_none() {
-				return false;
-			}
-			true
+		/// call id:809
+		///
+		/// Users claim a short invite code, which others use to be referred by them.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::register_invite_code())]
+		pub fn register_invite_code(origin: OriginFor<T>, code: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(
+				!code.is_empty() && code.len() as u32 <= T::MaxInviteCodeLength::get(),
+				Error::<T>::InvalidInviteCode
+			);
+			ensure!(
+				Self::invite_code_of(&who).is_none(),
+				Error::<T>::InviteCodeAlreadyRegistered
+			);
+			ensure!(
+				Self::invite_code_owner(&code).is_none(),
+				Error::<T>::InviteCodeAlreadyTaken
+			);
+
+			InviteCodeOwnerOf::<T>::insert(&code, &who);
+			InviteCodeOf::<T>::insert(&who, &code);
+			Self::deposit_event(Event::RegisterInviteCode(who, code));
+			Ok(())
 		}
 
-		fn caculate_user_reward(
-			info: Vec<(u32, MultiBalanceOf<T>)>,
-			total_usdt: MultiBalanceOf<T>,
-			total_reward: MultiBalanceOf<T>,
-		) -> MultiBalanceOf<T> {
-			let mut user_usdt = MultiBalanceOf::<T>::from(0u32);
-			let tmpt = 50u32;
-			for (n, amount) in info.iter() {
-				if *n <= 5u32 {
-					user_usdt += (*amount + (Percent::from_percent((50 - (n - 1) * 10) as u8) * *amount))
-				} else if *n > 5 && *n <= 10 {
-					user_usdt += (*amount - (Percent::from_percent(((n - 5) * 10) as u8) * *amount));
-				}
-			}
+		/// call id:810
+		///
+		/// Users are referred by the owner of the invite code in all icoes.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::bind_invite_code())]
+		pub fn bind_invite_code(origin: OriginFor<T>, code: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
 
-			Self::u256_convert_to_balance(
-				Self::balance_convert_to_u256(user_usdt) * Self::balance_convert_to_u256(total_reward)
-					/ Self::balance_convert_to_u256(total_usdt),
-			)
+			let referrer = Self::invite_code_owner(&code).ok_or(Error::<T>::InviteCodeNotExists)?;
+			ensure!(who != referrer, Error::<T>::CanNotInviteYouself);
+			ensure!(Self::referrer_of(&who).is_none(), Error::<T>::ReferrerAlreadyBound);
+			ensure!(!Self::is_referral_cycle(&who, &referrer), Error::<T>::ReferralCycle);
+
+			Self::insert_referrer(&who, &referrer);
+			Ok(())
 		}
 
-		fn update_invite_info(
-			inviter: &T::AccountId,
-			invitee: &T::AccountId,
-			currency_id: AssetId,
-			index: u32,
-			reward: MultiBalanceOf<T>,
-		) {
-			let mut invitees = InviteInfoOf::<T>::get(&inviter, currency_id);
-			if let Some(x) = invitees
-				.iter()
-				.position(|h| &h.invitee == invitee && h.currency_id == currency_id && h.index == index)
-			{
-				let mut info = invitees.swap_remove(x);
-				if info.reward.is_none() {
-					info.reward = Some(reward);
-					invitees.push(info);
-					InviteInfoOf::<T>::insert(&inviter, currency_id, invitees);
-				}
-			}
+		/// Set the reward proportions of the referrers by level, they add up to 100% at most.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_referral_reward_proportions())]
+		pub fn set_referral_reward_proportions(origin: OriginFor<T>, proportions: Vec<Percent>) -> DispatchResult {
+			T::SetRewardEconomicsOrigin::ensure_origin(origin)?;
+			ensure!(
+				proportions.len() as u32 <= T::MaxReferralLevels::get(),
+				Error::<T>::TooManyReferralLevels
+			);
+			ensure!(
+				proportions.iter().map(|h| h.deconstruct() as u32).sum::<u32>() <= 100,
+				Error::<T>::ReferralRewardTooHigh
+			);
+			ReferralRewardProportions::<T>::put(&proportions);
+			Self::deposit_event(Event::SetReferralRewardProportions(proportions));
+			Ok(())
 		}
 
-		fn split_user_amount(
-			total_amount: MultiBalanceOf<T>,
-			info: Vec<(
-				MultiBalanceOf<T>,
-				MultiBalanceOf<T>,
-				MultiBalanceOf<T>,
-				MultiBalanceOf<T>,
-			)>,
-		) -> Vec<(u32, MultiBalanceOf<T>)> {
-			const NUM: u32 = 10;
-			let av_amount = total_amount / NUM.saturated_into::<MultiBalanceOf<T>>();
-			let mut result: Vec<(u32, MultiBalanceOf<T>)> = vec![];
+		/// Set the total reward, the referral reward proportions and the initiator pledge of the
+		/// ICOs.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_reward_economics())]
+		pub fn set_reward_economics(
+			origin: OriginFor<T>,
+			economics: RewardEconomics<MultiBalanceOf<T>>,
+		) -> DispatchResult {
+			T::SetRewardEconomicsOrigin::ensure_origin(origin)?;
+			ensure!(economics != Self::reward_economics(), Error::<T>::DuplicateSet);
+			IcoRewardEconomics::<T>::put(&economics);
+			Self::deposit_event(Event::SetRewardEconomics(economics));
+			Ok(())
+		}
 
-			for (amount, tag_amount, _, _) in info.iter() {
-				let mut start_amount = tag_amount.saturating_sub(*amount);
-				let mut n = (start_amount / av_amount).saturated_into::<u32>() + 1;
-				loop {
-					let next_amount = n.saturated_into::<MultiBalanceOf<T>>() * av_amount;
-					if next_amount > *tag_amount {
-						if tag_amount.saturating_sub(start_amount) > MultiBalanceOf::<T>::from(0u32) {
-							result.push((n, tag_amount.saturating_sub(start_amount)));
-						}
-						break;
-					}
-					result.push((n, next_amount.saturating_sub(start_amount)));
-					start_amount = next_amount;
-					n += 1;
-				}
-			}
+		/// call id:813
+		///
+		/// Users let another user use their power, until they revoke it.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::delegate_power())]
+		pub fn delegate_power(origin: OriginFor<T>, delegatee: <T::Lookup as StaticLookup>::Source) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let delegatee = T::Lookup::lookup(delegatee)?;
 
-			result
+			ensure!(who != delegatee, Error::<T>::DelegateToYourself);
+			ensure!(
+				Self::power_delegate_of(&who).is_none(),
+				Error::<T>::PowerAlreadyDelegated
+			);
+			// The delegated power can not be delegated again.
+			ensure!(
+				Self::power_delegate_of(&delegatee).is_none() && Self::power_delegators_of(&who).is_empty(),
+				Error::<T>::PowerDelegationChain
+			);
+			PowerDelegatorsOf::<T>::try_mutate(&delegatee, |delegators| -> DispatchResult {
+				ensure!(
+					(delegators.len() as u32) < T::MaxPowerDelegators::get(),
+					Error::<T>::TooManyPowerDelegators
+				);
+				delegators.push(who.clone());
+				Ok(())
+			})?;
+			PowerDelegateOf::<T>::insert(&who, &delegatee);
+
+			Self::deposit_event(Event::DelegatePower(who, delegatee));
+			Ok(())
 		}
 
-		pub fn calculate_total_reward(
-			ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
-		) -> MultiBalanceOf<T> {
-			let mut this_time_total_usdt = ico.total_usdt;
-			let total_usdt = match ico.tag {
-				Some(tag_usdt) => tag_usdt,
-				None => TotalUsdt::<T>::get().saturating_add(this_time_total_usdt),
-			};
+		/// call id:814
+		///
+		/// Users take back the power they delegated.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::revoke_power_delegation())]
+		pub fn revoke_power_delegation(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
 
-			let mut num = total_usdt / HalfDuration.saturated_into::<MultiBalanceOf<T>>();
-			let mut remain = total_usdt % HalfDuration.saturated_into::<MultiBalanceOf<T>>();
+			let delegatee = PowerDelegateOf::<T>::take(&who).ok_or(Error::<T>::PowerNotDelegated)?;
+			PowerDelegatorsOf::<T>::mutate(&delegatee, |delegators| delegators.retain(|h| h != &who));
 
-			let mut power = MultiBalanceOf::<T>::from(0u32);
-			loop {
-				if this_time_total_usdt.saturating_sub(remain) > MultiBalanceOf::<T>::from(0u32) {
-					power += (remain / (2u32.pow(num.saturated_into::<u32>())).saturated_into::<MultiBalanceOf<T>>());
-					this_time_total_usdt = this_time_total_usdt.saturating_sub(remain);
-					remain = HalfDuration.saturated_into::<MultiBalanceOf<T>>();
-					num = num.saturating_sub(1u32.saturated_into::<MultiBalanceOf<T>>());
-				} else {
-					power += (this_time_total_usdt
-						/ (2u32.pow(num.saturated_into::<u32>())).saturated_into::<MultiBalanceOf<T>>());
-					break;
-				}
-			}
+			Self::deposit_event(Event::RevokePowerDelegation(who, delegatee));
+			Ok(())
+		}
 
-			let first_total_reward = T::IcoTotalReward::get() / 2u32.saturated_into::<MultiBalanceOf<T>>();
+		/// call id:815
+		///
+		/// The project party gets the liquidity added to the amm when the ico ended, after it is
+		/// unlocked.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::unlock_ico_liquidity())]
+		pub fn unlock_ico_liquidity(origin: OriginFor<T>, currency_id: AssetId, index: u32) -> DispatchResult {
+			let initiator = ensure_signed(origin)?;
 
-			Self::u256_convert_to_balance(
-				Self::balance_convert_to_u256(power) * Self::balance_convert_to_u256(first_total_reward)
-					/ Self::balance_convert_to_u256(HalfDuration.saturated_into::<MultiBalanceOf<T>>()),
-			)
+			let ico = <Ico<T>>::get(currency_id, index).ok_or(Error::<T>::IcoNotExists123)?;
+			ensure!(&initiator == &ico.initiator, Error::<T>::NotInitiator);
+			IcoLiquidityOf::<T>::try_mutate(currency_id, index, |liquidity| -> DispatchResult {
+				let liquidity = liquidity.as_mut().ok_or(Error::<T>::IcoLiquidityNotExists)?;
+				ensure!(!liquidity.is_unlocked, Error::<T>::IcoLiquidityNotExists);
+				ensure!(Self::now() >= liquidity.unlock_block, Error::<T>::IcoLiquidityLocked);
+				T::MultiCurrency::unreserve(liquidity.liquidity_id, &initiator, liquidity.liquidity_amount);
+				liquidity.is_unlocked = true;
+				Self::deposit_event(Event::UnlockIcoLiquidity(
+					initiator.clone(),
+					currency_id,
+					index,
+					liquidity.liquidity_amount,
+				));
+				Ok(())
+			})
 		}
 
-		fn do_release(
-			user: T::AccountId,
-			currency_id: AssetId,
-			index: u32,
-			is_do: bool,
-		) -> result::Result<MultiBalanceOf<T>, DispatchError> {
-			let mut this_time_total_release = MultiBalanceOf::<T>::from(0u32);
+		/// call id:811
+		///
+		/// The project party cancels the permitted ico before it starts. The project tokens and
+		/// the pledge are given back, and `CancelIcoFee` of the pledge goes to the treasury.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::cancel_ico())]
+		#[transactional]
+		pub fn cancel_ico(origin: OriginFor<T>, currency_id: AssetId, index: u32) -> DispatchResult {
+			let initiator = ensure_signed(origin)?;
 
-			let mut is_oprate = false;
-			let mut ico = <Ico<T>>::get(currency_id, index).ok_or(Error::<T>::IcoNotExists123)?;
+			let ico = <Ico<T>>::get(currency_id, index).ok_or(Error::<T>::IcoNotExists123)?;
+			ensure!(&initiator == &ico.initiator, Error::<T>::NotInitiator);
+			let start_time = ico.start_time.ok_or(Error::<T>::StartTimeNotExists)?;
+			ensure!(Self::now() < start_time, Error::<T>::IcoAlreadyStarted);
+
+			let pledge = InitiatorPledgeOf::<T>::take(currency_id, index);
+			T::MultiCurrency::unreserve(T::GetNativeCurrencyId::get(), &initiator, pledge);
+			let fee = T::CancelIcoFee::get() * pledge;
+			T::MultiCurrency::transfer(
+				T::GetNativeCurrencyId::get(),
+				&initiator,
+				&T::DicoTreasuryHandler::get_treasury_account_id(),
+				fee,
+			)?;
+			T::MultiCurrency::unreserve(
+				currency_id,
+				&initiator,
+				ico.total_ico_amount
+					.saturating_add(Self::liquidity_project_token_reserved(&ico)),
+			);
+			Self::refund_reward_pool(&ico);
 
-			/// For initiator
-			if Self::is_ico_expire(&ico)
-				&& (IsUnservePledge::<T>::contains_key(currency_id, index)
-					&& IsUnservePledge::<T>::get(currency_id, index))
-			{
-				if is_do {
-					Self::unreserve_initiator_remain_token(&ico);
-				}
-				is_oprate = true;
-			}
+			Ico::<T>::remove(currency_id, index);
+			Indexs::<T>::mutate(currency_id, |h| h.retain(|i| i != &index));
+			IsUnservePledge::<T>::remove(currency_id, index);
+			Self::remove_passed_icoes(currency_id, index);
+			Self::remove_ico_settings(currency_id, index);
+			Self::update_initiate_icoes(currency_id, index, &initiator, IcoStatus::Failed);
 
-			/// For users
-			let asset_info_opt = Self::get_unrelease_asset_info(&user, currency_id, index);
-			match asset_info_opt {
-				None => {
-					if is_oprate && &user == &ico.initiator {
-						return Ok(MultiBalanceOf::<T>::from(0u32));
-					} else {
-						return Err(Error::<T>::NotIcoMember)?;
+			Self::deposit_event(Event::CancelIco(initiator, currency_id, index, fee));
+			Ok(())
+		}
+
+		/// call id:812
+		///
+		/// The project party amends the description, timing and lock parameters of the ico
+		/// before it is permitted.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::amend_ico())]
+		pub fn amend_ico(
+			origin: OriginFor<T>,
+			currency_id: AssetId,
+			amendment: IcoAmendment<T::BlockNumber, MultiBalanceOf<T>>,
+		) -> DispatchResult {
+			let initiator = ensure_signed(origin)?;
+
+			ensure!(
+				amendment.ico_duration > T::BlockNumber::from(0u32),
+				Error::<T>::DurationIsZero
+			);
+			Self::check_lock(
+				amendment.lock_proportion,
+				amendment.unlock_duration,
+				amendment.per_duration_unlock_amount,
+				&amendment.vesting,
+			)?;
+
+			let index =
+				<PendingIco<T>>::try_mutate(currency_id, |pending_info| -> result::Result<u32, DispatchError> {
+					let pending_info = pending_info.as_mut().ok_or(Error::<T>::PendingIcoNotExists123)?;
+					ensure!(&initiator == &pending_info.ico.initiator, Error::<T>::NotInitiator);
+					let ico = &mut pending_info.ico;
+					Self::check_vesting_amount(&amendment.vesting, amendment.lock_proportion, ico.total_ico_amount)?;
+					if let Some(lottery) = Self::lottery_of(currency_id, ico.index.unwrap_or_default()) {
+						ensure!(
+							lottery.registration_duration < amendment.ico_duration,
+							Error::<T>::InvalidLottery
+						);
 					}
+					ico.desc = amendment.desc.clone();
+					ico.official_website = amendment.official_website.clone();
+					ico.ico_duration = amendment.ico_duration;
+					ico.lock_proportion = amendment.lock_proportion;
+					ico.unlock_duration = amendment.unlock_duration;
+					ico.per_duration_unlock_amount = amendment.per_duration_unlock_amount;
+					ico.index.ok_or(Error::<T>::IcoIndexNotExists.into())
+				})?;
+
+			match amendment.vesting.as_ref() {
+				Some(vesting) => VestingOf::<T>::insert(currency_id, index, vesting),
+				None => VestingOf::<T>::remove(currency_id, index),
+			}
+			InitiatedIcoesOf::<T>::mutate(&initiator, |icoes| {
+				if let Some(ico) = icoes
+					.iter_mut()
+					.find(|h| h.currency_id == currency_id && h.index == index)
+				{
+					ico.desc = amendment.desc.clone();
 				}
-				Some(asset_info) => {
-					let total = asset_info.total;
-					let released = asset_info.released;
-					let refund = asset_info.refund;
+			});
 
-					let should_released = ico.already_released_proportion * total;
+			Self::deposit_event(Event::AmendIco(initiator, currency_id, index));
+			Ok(())
+		}
 
-					ensure!(
-						(total.saturating_sub(released)).saturating_sub(refund) > MultiBalanceOf::<T>::from(0u32),
-						Error::<T>::UnreleasedAmountIsZero
-					);
+		/// call id:816
+		///
+		/// Users register for the lottery ico during the registration, with the tickets of their
+		/// power multiplied by their tier.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::register_lottery())]
+		pub fn register_lottery(origin: OriginFor<T>, currency_id: AssetId, index: u32) -> DispatchResult {
+			let user = ensure_signed(origin)?;
 
-					if ico.is_terminated
-						&& &user != &ico.initiator
-						&& refund == MultiBalanceOf::<T>::from(0u32)
-						&& ico.already_released_proportion != Percent::from_percent(100u8)
-					{
-						let remain_amount = total.saturating_sub(should_released);
-						if is_do {
-							Self::terminated_released_user_token(&user, remain_amount, &ico);
-						}
-						this_time_total_release += remain_amount;
-						is_oprate = true;
-					}
+			let ico = <Ico<T>>::get(currency_id, index).ok_or(Error::<T>::IcoNotExists123)?;
+			ensure!(&ico.initiator != &user, Error::<T>::InitiatorIsYourself);
+			let mut lottery = Self::lottery_of(currency_id, index).ok_or(Error::<T>::NotLotteryIco)?;
+			let start_time = ico.start_time.ok_or(Error::<T>::StartTimeNotExists)?;
+			let now = Self::now();
+			ensure!(
+				now >= start_time && now < start_time.saturating_add(lottery.registration_duration),
+				Error::<T>::NotInLotteryRegistration
+			);
 
-					let mut thistime_release_amount = should_released.saturating_sub(released);
+			let tickets = Self::lottery_tickets_of(&user, &lottery);
+			ensure!(tickets > 0, Error::<T>::NoLotteryTickets);
+			LotteryEntrantsOf::<T>::try_mutate(currency_id, index, |entrants| -> DispatchResult {
+				ensure!(
+					!entrants.iter().any(|h| h.0 == user),
+					Error::<T>::AlreadyRegisteredLottery
+				);
+				ensure!(
+					(entrants.len() as u32) < T::MaxLotteryEntrants::get(),
+					Error::<T>::TooManyLotteryEntrants
+				);
+				entrants.push((user.clone(), tickets));
+				Ok(())
+			})?;
+			lottery.total_tickets = lottery.total_tickets.saturating_add(tickets);
+			LotteryOf::<T>::insert(currency_id, index, lottery);
 
-					if thistime_release_amount == MultiBalanceOf::<T>::from(0u32) && is_oprate == false {
-						return Err(Error::<T>::UnreleaseAmountIsZero)?;
-					} else if thistime_release_amount == MultiBalanceOf::<T>::from(0u32) && is_oprate == true {
-						return Ok(this_time_total_release);
-					};
+			Self::deposit_event(Event::RegisterLottery(user, currency_id, index, tickets));
+			Ok(())
+		}
 
-					if is_do {
-						Self::common_release(user, ico, thistime_release_amount);
-					}
+		/// call id:817
+		///
+		/// Anyone draws the winners of the lottery ico after the registration ends, with the relay
+		/// chain randomness taken in `on_initialize` of the block the registration ends. The
+		/// lottery that could not be seeded there takes the randomness of the draw. The draw can be
+		/// reproduced from the entrants and the random seed.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::draw_lottery())]
+		pub fn draw_lottery(origin: OriginFor<T>, currency_id: AssetId, index: u32) -> DispatchResult {
+			ensure_signed(origin)?;
 
-					Ok(this_time_total_release.saturating_add(thistime_release_amount))
-				}
+			let ico = <Ico<T>>::get(currency_id, index).ok_or(Error::<T>::IcoNotExists123)?;
+			let mut lottery = Self::lottery_of(currency_id, index).ok_or(Error::<T>::NotLotteryIco)?;
+			ensure!(!lottery.is_drawn, Error::<T>::LotteryAlreadyDrawn);
+			let start_time = ico.start_time.ok_or(Error::<T>::StartTimeNotExists)?;
+			ensure!(
+				Self::now() >= start_time.saturating_add(lottery.registration_duration),
+				Error::<T>::LotteryRegistrationNotEnd
+			);
+
+			let random_seed = match lottery.random_seed {
+				Some(random_seed) => random_seed,
+				None => Self::lottery_randomness(currency_id, index).ok_or(Error::<T>::LotteryRandomnessNotExists)?,
+			};
+			lottery.random_seed = Some(random_seed);
+			let winners = Self::draw_lottery_winners(
+				Self::lottery_entrants_of(currency_id, index),
+				lottery.total_tickets,
+				lottery.winner_count,
+				random_seed,
+			);
+			for winner in winners.iter() {
+				LotteryWinnerOf::<T>::insert((currency_id, index), winner, true);
 			}
+			lottery.is_drawn = true;
+			LotteryOf::<T>::insert(currency_id, index, lottery);
+
+			Self::deposit_event(Event::DrawLottery(currency_id, index, random_seed, winners));
+			Ok(())
 		}
 
-		fn common_release(
-			user: T::AccountId,
-			ico: IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
-			thistime_release_amount: MultiBalanceOf<T>,
-		) {
-			let project_currency_id = ico.exchange_token;
-			let index = ico.index.unwrap();
-			if user == ico.initiator {
-				T::MultiCurrency::unreserve(project_currency_id, &ico.initiator, thistime_release_amount);
-			} else {
-				let user_keep_lock_amount = ico.lock_proportion * thistime_release_amount;
+		/// call id:818
+		///
+		/// The participants of the ico terminated as a fraud claim the compensation of the
+		/// protection fund.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::claim_compensation())]
+		#[transactional]
+		pub fn claim_compensation(origin: OriginFor<T>, currency_id: AssetId, index: u32) -> DispatchResult {
+			let user = ensure_signed(origin)?;
+
+			let ico = <Ico<T>>::get(currency_id, index).ok_or(Error::<T>::IcoNotExists123)?;
+			ensure!(&ico.initiator != &user, Error::<T>::InitiatorIsYourself);
+			let mut compensation = Self::ico_compensation_of(currency_id, index).ok_or(Error::<T>::IcoNotFraud)?;
+			ensure!(
+				!Self::is_compensation_claimed((currency_id, index), &user),
+				Error::<T>::CompensationAlreadyClaimed
+			);
+			let asset_info =
+				Self::get_unrelease_asset_info(&user, currency_id, index).ok_or(Error::<T>::NotIcoMember)?;
+			let unreleased = asset_info
+				.total
+				.saturating_sub(compensation.released_proportion * asset_info.total);
+
+			let pledge = Self::u256_convert_to_balance(
+				Self::balance_convert_to_u256(compensation.pledge) * Self::balance_convert_to_u256(unreleased)
+					/ Self::balance_convert_to_u256(compensation.total_unreleased),
+			)
+			.min(compensation.pledge.saturating_sub(compensation.claimed_pledge));
+			let amount = Self::u256_convert_to_balance(
+				Self::balance_convert_to_u256(compensation.amount) * Self::balance_convert_to_u256(unreleased)
+					/ Self::balance_convert_to_u256(compensation.total_unreleased),
+			)
+			.min(compensation.amount.saturating_sub(compensation.claimed_amount));
+
+			let fund = Self::protection_fund_account();
+			let native_currency_id = T::GetNativeCurrencyId::get();
+			T::MultiCurrency::transfer(native_currency_id, &fund, &user, pledge)?;
+			T::MultiCurrency::transfer(ico.exchange_token, &fund, &user, amount)?;
+			ProtectionFundCommitted::<T>::mutate(native_currency_id, |h| *h = h.saturating_sub(pledge));
+			ProtectionFundCommitted::<T>::mutate(ico.exchange_token, |h| *h = h.saturating_sub(amount));
+			compensation.claimed_pledge = compensation.claimed_pledge.saturating_add(pledge);
+			compensation.claimed_amount = compensation.claimed_amount.saturating_add(amount);
+			IcoCompensationOf::<T>::insert(currency_id, index, compensation);
+			CompensationClaimedOf::<T>::insert((currency_id, index), &user, true);
+
+			Self::deposit_event(Event::ClaimCompensation(user, currency_id, index, pledge, amount));
+			Ok(())
+		}
+
+		/// call id:819
+		///
+		/// The project party funds the reward pool of the ico with the project tokens, which the
+		/// participants get in proportion to their usdt on top of the DICO reward.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::fund_reward_pool())]
+		#[transactional]
+		pub fn fund_reward_pool(
+			origin: OriginFor<T>,
+			currency_id: AssetId,
+			index: u32,
+			amount: MultiBalanceOf<T>,
+		) -> DispatchResult {
+			let initiator = ensure_signed(origin)?;
+
+			let ico = <Ico<T>>::get(currency_id, index).ok_or(Error::<T>::IcoNotExists123)?;
+			ensure!(&initiator == &ico.initiator, Error::<T>::NotInitiator);
+			let start_time = ico.start_time.ok_or(Error::<T>::StartTimeNotExists)?;
+			// The participants get the reward after the end, so the pool is funded before it.
+			ensure!(
+				!ico.is_terminated && Self::now() <= start_time + ico.ico_duration,
+				Error::<T>::IcoExpire
+			);
+			ensure!(amount > MultiBalanceOf::<T>::from(0u32), Error::<T>::AmountIsZero);
+
+			T::MultiCurrency::reserve(currency_id, &initiator, amount)?;
+			RewardPoolOf::<T>::mutate(currency_id, index, |h| {
+				let pool = h.get_or_insert_with(Default::default);
+				pool.total = pool.total.saturating_add(amount);
+			});
+
+			Self::deposit_event(Event::FundRewardPool(currency_id, index, amount));
+			Ok(())
+		}
+
+		/// call id:820
+		///
+		/// The user gets the share of the reward pool, whether or not it has got the DICO reward.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::get_pool_reward())]
+		#[transactional]
+		pub fn get_pool_reward(
+			origin: OriginFor<T>,
+			currency_id: AssetId,
+			index: u32,
+			receipt: Option<ReceiptIdOf<T>>,
+		) -> DispatchResult {
+			let holder = ensure_signed(origin)?;
+			let user = Self::participant_of(&holder, currency_id, index, receipt)?;
+
+			let ico = <Ico<T>>::get(currency_id, index).ok_or(Error::<T>::IcoNotExists123)?;
+			ensure!(&ico.initiator != &user, Error::<T>::InitiatorIsYourself);
+			ensure!(Self::is_ico_expire(&ico), Error::<T>::IcoNotExpireOrTerminated);
+			ensure!(!Self::is_soft_cap_failed(&ico), Error::<T>::IcoFailed);
+			ensure!(
+				RewardPoolOf::<T>::contains_key(currency_id, index),
+				Error::<T>::RewardPoolNotExists
+			);
+			ensure!(
+				!Self::is_pool_reward_claimed((currency_id, index), &user),
+				Error::<T>::PoolRewardAlreadyClaimed
+			);
+
+			let pool_reward = Self::with_receipt_holder(&user, &holder, currency_id, index, || {
+				Self::reward_from_pool(&user, currency_id, index)
+			})?;
+
+			Self::deposit_event(Event::GetPoolReward(currency_id, index, holder, pool_reward));
+			Ok(())
+		}
+
+		/// call id:821
+		///
+		/// The project party reclaims the project tokens left in the reward pool when all the
+		/// participants have got their share, or `RewardPoolClaimDuration` after the end of the
+		/// ico.
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::reclaim_reward_pool())]
+		#[transactional]
+		pub fn reclaim_reward_pool(origin: OriginFor<T>, currency_id: AssetId, index: u32) -> DispatchResult {
+			let initiator = ensure_signed(origin)?;
+
+			let ico = <Ico<T>>::get(currency_id, index).ok_or(Error::<T>::IcoNotExists123)?;
+			ensure!(&initiator == &ico.initiator, Error::<T>::NotInitiator);
+			let pool = Self::reward_pool_of(currency_id, index).ok_or(Error::<T>::RewardPoolNotExists)?;
+			let start_time = ico.start_time.ok_or(Error::<T>::StartTimeNotExists)?;
+			ensure!(
+				Self::now() > start_time + ico.ico_duration + T::RewardPoolClaimDuration::get()
+					|| (Self::is_ico_expire(&ico) && pool.claimed_usdt >= ico.total_usdt),
+				Error::<T>::RewardPoolClaimNotEnd
+			);
+
+			let amount = Self::refund_reward_pool(&ico);
+
+			Self::deposit_event(Event::ReclaimRewardPool(currency_id, index, amount));
+			Ok(())
+		}
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			let expiries = RequestExpiryQueue::<T>::take(n);
+			let count = expiries.len() as u32;
+			for (currency_id, index) in expiries {
+				Self::expire_request(currency_id, index, n);
+			}
+			let lotteries = LotterySeedQueue::<T>::take(n);
+			let lottery_count = lotteries.len() as u32;
+			for (currency_id, index) in lotteries {
+				Self::seed_lottery(currency_id, index, n);
+			}
+			<T as pallet::Config>::WeightInfo::expire_requests(count)
+				.saturating_add(<T as pallet::Config>::WeightInfo::seed_lotteries(lottery_count))
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			migrations::v1::migrate::<T>()
+				.saturating_add(migrations::v2::migrate::<T>())
+				.saturating_add(migrations::v3::migrate::<T>())
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub (super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		Test(T::AccountId),
+		InitiateIco(T::AccountId, AssetId, MultiBalanceOf<T>),
+		PermitIco(T::AccountId, AssetId),
+		RejectIco(T::AccountId, AssetId),
+		Join(T::AccountId, AssetId, u32, MultiBalanceOf<T>, MultiBalanceOf<T>),
+		TerminateIco(AssetId, u32),
+		RequestRelease(AssetId, u32, Percent),
+		CancelRequest(AssetId, u32),
+		PermitRelease(
+			AssetId,
+			u32,
+			Release<<T as system::Config>::AccountId, <T as system::Config>::BlockNumber, AssetId, BalanceOf<T>>,
+		),
+		UnlockAsset(AssetId, T::AccountId, MultiBalanceOf<T>),
+		GetReward(AssetId, u32, T::AccountId, MultiBalanceOf<T>),
+		UserReleaseIcoAmount(AssetId, u32, MultiBalanceOf<T>),
+		InitiatorSetIcoAmountBound(AssetId, u32, MultiBalanceOf<T>, MultiBalanceOf<T>),
+		SetSystemIcoAmountBound(MultiBalanceOf<T>, MultiBalanceOf<T>),
+		SetIcoMaxCount(AssetId, u32, u8),
+		TerminatedGiveBackAmount(T::AccountId, AssetId, u32, MultiBalanceOf<T>),
+		UnreservedInitiatorRemainPledgeAmount(AssetId, u32, MultiBalanceOf<T>),
+		SetAssetPowerMultiple(AssetId, PowerMultiple),
+		Refund(AssetId, u32, T::AccountId, MultiBalanceOf<T>),
+		ClaimAllocation(AssetId, u32, T::AccountId, MultiBalanceOf<T>, MultiBalanceOf<T>),
+		JoinWithPaymentToken(
+			T::AccountId,
+			AssetId,
+			u32,
+			AssetId,
+			MultiBalanceOf<T>,
+			MultiBalanceOf<T>,
+		),
+		ReleasePaymentToken(AssetId, u32, AssetId, MultiBalanceOf<T>),
+		RegisterInviteCode(T::AccountId, Vec<u8>),
+		/// The user and the referrer.
+		BindReferrer(T::AccountId, T::AccountId),
+		SetReferralRewardProportions(Vec<Percent>),
+		/// The initiator, the ico and the fee paid to the treasury.
+		CancelIco(T::AccountId, AssetId, u32, MultiBalanceOf<T>),
+		AmendIco(T::AccountId, AssetId, u32),
+		/// The delegator and the delegatee.
+		DelegatePower(T::AccountId, T::AccountId),
+		RevokePowerDelegation(T::AccountId, T::AccountId),
+		/// The ico, the asset ID of the liquidity and the liquidity reserved by the initiator.
+		AddIcoLiquidity(AssetId, u32, AssetId, MultiBalanceOf<T>),
+		AddIcoLiquidityFailed(AssetId, u32),
+		UnlockIcoLiquidity(T::AccountId, AssetId, u32, MultiBalanceOf<T>),
+		/// The user, the ico and the tickets registered.
+		RegisterLottery(T::AccountId, AssetId, u32, u32),
+		/// The ico, the random seed and the winners.
+		DrawLottery(AssetId, u32, T::Hash, Vec<T::AccountId>),
+		/// The ico, the pledge slashed and the exchange tokens of the protection fund given to
+		/// the participants.
+		TerminateFraudIco(AssetId, u32, MultiBalanceOf<T>, MultiBalanceOf<T>),
+		/// The user, the ico, the pledge and the exchange tokens claimed.
+		ClaimCompensation(T::AccountId, AssetId, u32, MultiBalanceOf<T>, MultiBalanceOf<T>),
+		/// The nft receipt is minted for the participation of the user.
+		MintIcoReceipt(T::AccountId, AssetId, u32, ReceiptIdOf<T>),
+		SetRewardEconomics(RewardEconomics<MultiBalanceOf<T>>),
+		/// The ico and the project tokens funded to the reward pool.
+		FundRewardPool(AssetId, u32, MultiBalanceOf<T>),
+		/// The ico, the user and the project tokens got from the reward pool.
+		GetPoolReward(AssetId, u32, T::AccountId, MultiBalanceOf<T>),
+		/// The ico and the project tokens left in the reward pool given back to the initiator.
+		ReclaimRewardPool(AssetId, u32, MultiBalanceOf<T>),
+		/// The lottery ico could not be seeded in the next blocks, it is seeded when it is drawn.
+		LotterySeedDeferred(AssetId, u32),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		BeingIco,
+		PendingIcoNotExists123,
+		IcoNotExists123,
+		InExcludeArea,
+		BadOrigin,
+		IsPendingIco,
+		InitiatorIsYourself,
+		AmountIsZero,
+		TokenShouldBeDifferent,
+		DurationIsZero,
+		IcoExpire,
+		IcoNotExpireOrTerminated,
+		IcoTerminated,
+		NotInitiator,
+		AlreadyRequest,
+		RequestNotExists,
+		RequestExpire,
+		LockIsEmpty,
+		UnlockAmountIsZero,
+		NotIcoMember,
+		Overflow,
+		ProportionTooLow,
+		UnreleaseAmountIsZero,
+		RewardIsZero,
+		IcoTimesToMax,
+		NativeCurrencyId,
+		UnreleasedAmountIsZero,
+		AmountNotMeetProjectRequirement,
+		AmountNotMeetSystemRequirement,
+		MaxLessThanMin,
+		MaxIsZero,
+		DuplicateSet,
+		CirculationMoreThanIssuance,
+		IcoAmountMoreThanCirculation,
+		MinAmountTooLow,
+		MaxAmountTooLarge,
+		MaxAmountIsZero,
+		BalanceInsufficient,
+		UserIcoAmountTooLow,
+		UserIcoAmountToMax,
+		ProjectIcoAmountToMax,
+		DivByZero,
+		IsNotStartIcoTime,
+		InviterNotInIco,
+		CanNotInviteYouself,
+		TerminateProtectTime,
+		ExchangeTokenBalanceTooLow,
+		AlreadyGetReward,
+		ReleaseProtectTime,
+		NotKycUser,
+		UserAreaNotExists,
+		IcoIndexNotExists,
+		StartTimeNotExists,
+		MultipleNotChange,
+		DownIsZero,
+		PowerIsZero,
+		PriceNotExists,
+		SoftCapIsZero,
+		SoftCapNotReached,
+		IcoFailed,
+		IcoNotFailed,
+		AlreadyRefund,
+		NotProRataIco,
+		AllocationAlreadyClaimed,
+		AllocationNotClaimed,
+		InvalidDutchAuction,
+		DutchAuctionNotExists,
+		NotInAllowlist,
+		AllowlistProofTooLong,
+		InvalidAllowlistProof,
+		AmountMoreThanAllowlistCap,
+		InvalidVesting,
+		InvalidMilestones,
+		NoMilestoneLeft,
+		NotMilestoneProportion,
+		MilestoneThresholdNotReached,
+		MilestoneNotMissed,
+		InvalidPaymentTokens,
+		PaymentTokenNotAccepted,
+		PaymentTokenCapReached,
+		PowerLessThanMin,
+		PriceMoreThanMax,
+		PriceTooOld,
+		PriceDeviationTooLarge,
+		InvalidInviteCode,
+		InviteCodeAlreadyRegistered,
+		InviteCodeAlreadyTaken,
+		InviteCodeNotExists,
+		ReferrerAlreadyBound,
+		ReferralCycle,
+		TooManyReferralLevels,
+		IcoAlreadyStarted,
+		DelegateToYourself,
+		PowerAlreadyDelegated,
+		PowerDelegationChain,
+		TooManyPowerDelegators,
+		PowerNotDelegated,
+		PowerNotEnough,
+		InvalidLiquidity,
+		IcoLiquidityNotExists,
+		IcoLiquidityLocked,
+		InvalidLottery,
+		NotLotteryIco,
+		NotInLotteryRegistration,
+		LotteryRegistrationNotEnd,
+		AlreadyRegisteredLottery,
+		NoLotteryTickets,
+		TooManyLotteryEntrants,
+		LotteryAlreadyDrawn,
+		LotteryNotDrawn,
+		NotLotteryWinner,
+		AlreadyFraudIco,
+		ProtectionFundNotEnough,
+		NothingToCompensate,
+		IcoNotFraud,
+		CompensationAlreadyClaimed,
+		TooManyRequestExpiries,
+		ReceiptNotExists,
+		ReceiptNotMatchIco,
+		NotReceiptOwner,
+		InvalidAreaCaps,
+		InvalidKycLevelCaps,
+		AreaCapExceeded,
+		RewardPoolNotExists,
+		PoolRewardAlreadyClaimed,
+		RewardPoolClaimNotEnd,
+		LotteryRandomnessNotExists,
+		TooManyLotteryDraws,
+		ReferralRewardTooHigh,
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Whether or not project member(exclude project party）
+		pub fn is_member(who: &T::AccountId, currency_id: AssetId, index: u32, is_exclude_manager: bool) -> bool {
+			let mut unrelease_info_vec = UnReleaseAssets::<T>::get(&who);
+			if let Some(pos) = unrelease_info_vec
+				.iter()
+				.position(|h| h.currency_id == currency_id && h.index == index)
+			{
+				let info = unrelease_info_vec.swap_remove(pos);
+				if is_exclude_manager {
+					if info.unreleased_currency_id != currency_id {
+						return false;
+					}
+				}
+				return true;
+			} else {
+				false
+			}
+		}
+
+		fn do_reward(
+			user: &T::AccountId,
+			currency_id: AssetId,
+			index: u32,
+			is_do: bool,
+		) -> result::Result<MultiBalanceOf<T>, DispatchError> {
+			let mut ico = <Ico<T>>::get(currency_id, index).ok_or(Error::<T>::IcoNotExists123)?;
+			ensure!(&ico.initiator != user, Error::<T>::InitiatorIsYourself);
+
+			ensure!(Self::is_ico_expire(&ico), Error::<T>::IcoNotExpireOrTerminated);
+			ensure!(!Self::is_soft_cap_failed(&ico), Error::<T>::IcoFailed);
+
+			let total_reward = Self::calculate_total_reward(&ico);
+			let asset_info =
+				Self::get_unrelease_asset_info(&user, currency_id, index).ok_or(Error::<T>::NotIcoMember)?;
+			ensure!(asset_info.reward.is_none(), Error::<T>::AlreadyGetReward);
+			let tags = asset_info.tags;
+
+			let classify = Self::split_user_amount(ico.total_usdt, tags);
+			let mut reward = Self::caculate_user_reward(classify, ico.total_usdt, total_reward);
+			ensure!(reward > MultiBalanceOf::<T>::from(0u32), Error::<T>::RewardIsZero);
+			reward = Self::power_weighted_reward(user, reward, asset_info.total_usdt);
+			reward = Self::reward(&user, asset_info.inviter, reward, is_do, currency_id, index);
+			if is_do {
+				UnReleaseAssets::<T>::mutate(user.clone(), |vec| {
+					if let Some(pos) = vec
+						.iter()
+						.position(|h| h.currency_id == currency_id && h.index == index)
+					{
+						let mut unreleased = vec.swap_remove(pos);
+						unreleased.reward = Some(reward);
+						vec.push(unreleased);
+					}
+				});
+
+				if ico.tag == None {
+					let mut new_usdt = TotalUsdt::<T>::get();
+					new_usdt = new_usdt.saturating_add(ico.total_usdt);
+					ico.tag = Some(new_usdt);
+					Ico::<T>::insert(currency_id, index, ico);
+					TotalUsdt::<T>::put(new_usdt);
+				}
+			}
+
+			Ok(reward)
+		}
+
+		/// The user gets the share of the reward pool in proportion to its usdt in the ico.
+		fn reward_from_pool(
+			user: &T::AccountId,
+			currency_id: AssetId,
+			index: u32,
+		) -> result::Result<MultiBalanceOf<T>, DispatchError> {
+			let mut pool = match Self::reward_pool_of(currency_id, index) {
+				Some(pool) if !Self::is_pool_reward_claimed((currency_id, index), user) => pool,
+				_ => return Ok(MultiBalanceOf::<T>::from(0u32)),
+			};
+			let ico = <Ico<T>>::get(currency_id, index).ok_or(Error::<T>::IcoNotExists123)?;
+			let asset_info =
+				Self::get_unrelease_asset_info(user, currency_id, index).ok_or(Error::<T>::NotIcoMember)?;
+			if ico.total_usdt == MultiBalanceOf::<T>::from(0u32) {
+				return Ok(MultiBalanceOf::<T>::from(0u32));
+			}
+
+			let amount = Self::u256_convert_to_balance(
+				Self::balance_convert_to_u256(pool.total) * Self::balance_convert_to_u256(asset_info.total_usdt)
+					/ Self::balance_convert_to_u256(ico.total_usdt),
+			)
+			.min(pool.total.saturating_sub(pool.claimed));
+			T::MultiCurrency::repatriate_reserved(currency_id, &ico.initiator, user, amount, BalanceStatus::Free)?;
+			pool.claimed = pool.claimed.saturating_add(amount);
+			pool.claimed_usdt = pool.claimed_usdt.saturating_add(asset_info.total_usdt);
+			RewardPoolOf::<T>::insert(currency_id, index, pool);
+			PoolRewardClaimedOf::<T>::insert((currency_id, index), user, true);
+			Ok(amount)
+		}
+
+		/// The project tokens left in the reward pool are given back to the initiator.
+		fn refund_reward_pool(
+			ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
+		) -> MultiBalanceOf<T> {
+			match RewardPoolOf::<T>::take(ico.currency_id, ico.index.unwrap()) {
+				Some(pool) => {
+					let amount = pool.total.saturating_sub(pool.claimed);
+					T::MultiCurrency::unreserve(ico.currency_id, &ico.initiator, amount);
+					amount
+				}
+				None => MultiBalanceOf::<T>::from(0u32),
+			}
+		}
+
+		fn reward(
+			user: &T::AccountId,
+			inviter: Option<T::AccountId>,
+			amount: MultiBalanceOf<T>,
+			is_do: bool,
+			currency_id: AssetId,
+			index: u32,
+		) -> MultiBalanceOf<T> {
+			let mut user_total_amount = amount;
+			if let Some(inviter) = inviter {
+				let user_p = Self::reward_economics().invitee_reward_proportion * amount;
+				user_total_amount.saturating_sub(user_p);
+				if is_do {
+					T::MultiCurrency::deposit(T::GetNativeCurrencyId::get(), &user, user_p);
+					Self::reward_referrers(user, inviter, amount, currency_id, index);
+				}
+			}
+			if is_do {
+				T::MultiCurrency::deposit(T::GetNativeCurrencyId::get(), &user, amount);
+			}
+
+			user_total_amount
+		}
+
+		/// The reward raised by `PowerRewardBoost` times `power / (power + usdt)`, where `power` is
+		/// the usable power of the user, including the power delegated to it.
+		fn power_weighted_reward(
+			user: &T::AccountId,
+			reward: MultiBalanceOf<T>,
+			usdt: MultiBalanceOf<T>,
+		) -> MultiBalanceOf<T> {
+			let power = U256::from(Self::usable_power_of(user).saturated_into::<u128>());
+			let total = power.saturating_add(U256::from(usdt.saturated_into::<u128>()));
+			if total.is_zero() {
+				return reward;
+			}
+			let boost = U256::from((T::PowerRewardBoost::get() * reward).saturated_into::<u128>());
+			reward.saturating_add((boost * power / total).as_u128().saturated_into::<MultiBalanceOf<T>>())
+		}
+
+		fn is_already_kyc(who: &T::AccountId) -> bool {
+			if T::KycHandler::get_user_area(who).is_none() {
+				return false;
+			}
+			true
+		}
+
+		fn caculate_user_reward(
+			info: Vec<(u32, MultiBalanceOf<T>)>,
+			total_usdt: MultiBalanceOf<T>,
+			total_reward: MultiBalanceOf<T>,
+		) -> MultiBalanceOf<T> {
+			let mut user_usdt = MultiBalanceOf::<T>::from(0u32);
+			let tmpt = 50u32;
+			for (n, amount) in info.iter() {
+				if *n <= 5u32 {
+					user_usdt += (*amount + (Percent::from_percent((50 - (n - 1) * 10) as u8) * *amount))
+				} else if *n > 5 && *n <= 10 {
+					user_usdt += (*amount - (Percent::from_percent(((n - 5) * 10) as u8) * *amount));
+				}
+			}
+
+			Self::u256_convert_to_balance(
+				Self::balance_convert_to_u256(user_usdt) * Self::balance_convert_to_u256(total_reward)
+					/ Self::balance_convert_to_u256(total_usdt),
+			)
+		}
+
+		/// The inviter and its referrers get the rewards by level.
+		fn reward_referrers(
+			user: &T::AccountId,
+			inviter: T::AccountId,
+			amount: MultiBalanceOf<T>,
+			currency_id: AssetId,
+			index: u32,
+		) {
+			let mut proportions = Self::referral_reward_proportions();
+			if proportions.is_empty() {
+				proportions = vec![Self::reward_economics().inviter_reward_proportion];
+			}
+
+			let mut referrer = Some(inviter);
+			for proportion in proportions {
+				let current = match referrer {
+					Some(r) if &r != user => r,
+					_ => break,
+				};
+				let reward = proportion * amount;
+				T::MultiCurrency::deposit(T::GetNativeCurrencyId::get(), &current, reward);
+				Self::update_invite_info(&current, user, currency_id, index, reward);
+				referrer = Self::referrer_of(&current);
+			}
+		}
+
+		fn update_invite_info(
+			inviter: &T::AccountId,
+			invitee: &T::AccountId,
+			currency_id: AssetId,
+			index: u32,
+			reward: MultiBalanceOf<T>,
+		) {
+			let mut invitees = InviteInfoOf::<T>::get(&inviter, currency_id);
+			match invitees
+				.iter()
+				.position(|h| &h.invitee == invitee && h.currency_id == currency_id && h.index == index)
+			{
+				Some(x) => {
+					let mut info = invitees.swap_remove(x);
+					if info.reward.is_none() {
+						info.reward = Some(reward);
+						invitees.push(info);
+						InviteInfoOf::<T>::insert(&inviter, currency_id, invitees);
+					}
+				}
+				// The referrers above the inviter.
+				None => {
+					invitees.push(InviteInfo {
+						inviter: inviter.clone(),
+						invitee: invitee.clone(),
+						currency_id,
+						index,
+						reward: Some(reward),
+					});
+					InviteInfoOf::<T>::insert(&inviter, currency_id, invitees);
+				}
+			}
+		}
+
+		fn insert_referrer(who: &T::AccountId, referrer: &T::AccountId) {
+			ReferrerOf::<T>::insert(who, referrer);
+			Self::deposit_event(Event::BindReferrer(who.clone(), referrer.clone()));
+		}
+
+		/// Whether the user is one of the referrers of `referrer` within the reward levels.
+		fn is_referral_cycle(who: &T::AccountId, referrer: &T::AccountId) -> bool {
+			let mut upper = Some(referrer.clone());
+			for _ in 0..T::MaxReferralLevels::get() {
+				match upper {
+					Some(account) if &account == who => return true,
+					Some(account) => upper = Self::referrer_of(&account),
+					None => break,
+				}
+			}
+			false
+		}
+
+		/// The referral rewards of the user in each ico.
+		pub fn get_referral_earnings(who: T::AccountId) -> Vec<(AssetId, u32, MultiBalanceOf<T>)> {
+			let mut earnings: BTreeMap<(AssetId, u32), MultiBalanceOf<T>> = BTreeMap::new();
+			for info in InviteInfoOf::<T>::iter_prefix_values(&who).flatten() {
+				if let Some(reward) = info.reward {
+					let total = earnings
+						.entry((info.currency_id, info.index))
+						.or_insert(MultiBalanceOf::<T>::from(0u32));
+					*total = total.saturating_add(reward);
+				}
+			}
+			earnings
+				.into_iter()
+				.map(|((currency_id, index), reward)| (currency_id, index, reward))
+				.collect()
+		}
+
+		/// List the icoes of the stage, ordered by the ico unique id.
+		/// Provided for RPC use
+		pub fn get_icoes(
+			stage: Option<IcoStage>,
+			start: u32,
+			limit: u32,
+		) -> Vec<IcoDetail<T::AccountId, T::BlockNumber, MultiBalanceOf<T>>> {
+			let participants = Self::participant_counts();
+			let mut icoes: Vec<_> = PendingIco::<T>::iter_values()
+				.map(|h| (h.ico, true))
+				.chain(Ico::<T>::iter_values().map(|ico| (ico, false)))
+				.filter_map(|(ico, is_pending)| {
+					let detail = Self::ico_detail(ico, is_pending, &participants)?;
+					match stage {
+						Some(s) if s != detail.stage => None,
+						_ => Some(detail),
+					}
+				})
+				.collect();
+			icoes.sort_by_key(|h| h.index);
+			icoes.into_iter().skip(start as usize).take(limit as usize).collect()
+		}
+
+		/// The detail of a pending or permitted ico.
+		/// Provided for RPC use
+		pub fn get_ico_detail(
+			currency_id: AssetId,
+			index: u32,
+		) -> Option<IcoDetail<T::AccountId, T::BlockNumber, MultiBalanceOf<T>>> {
+			let participants = Self::participant_counts();
+			match Ico::<T>::get(currency_id, index) {
+				Some(ico) => Self::ico_detail(ico, false, &participants),
+				None => PendingIco::<T>::get(currency_id)
+					.filter(|h| h.ico.index == Some(index))
+					.and_then(|h| Self::ico_detail(h.ico, true, &participants)),
+			}
+		}
+
+		/// What the account has in each ico it joined or earned referral rewards from.
+		/// Provided for RPC use
+		pub fn get_portfolio(who: T::AccountId) -> Vec<IcoPosition<MultiBalanceOf<T>>> {
+			let mut positions: Vec<IcoPosition<MultiBalanceOf<T>>> = UnReleaseAssets::<T>::get(&who)
+				.into_iter()
+				.filter(|h| h.unreleased_currency_id == h.currency_id)
+				.map(|h| {
+					let locked_amount = IcoLocks::<T>::get(&who, h.currency_id)
+						.iter()
+						.filter(|l| l.index == h.index)
+						.fold(MultiBalanceOf::<T>::from(0u32), |acc, l| {
+							acc.saturating_add(l.total_amount.saturating_sub(l.unlock_amount))
+						});
+					let locked_amount = VestingSchedules::<T>::get(&who, h.currency_id)
+						.iter()
+						.filter(|v| v.index == h.index)
+						.fold(locked_amount, |acc, v| {
+							acc.saturating_add(v.total_amount().saturating_sub(v.unlock_amount))
+						});
+					IcoPosition {
+						currency_id: h.currency_id,
+						index: h.index,
+						joined_usdt: h.total_usdt,
+						total_amount: h.total,
+						released_amount: h.released,
+						releasable_amount: Self::can_release_amount(who.clone(), h.currency_id, h.index),
+						locked_amount,
+						unlockable_amount: Self::can_unlock_amount(who.clone(), h.currency_id, h.index),
+						reward_amount: Self::get_reward_amount(who.clone(), h.currency_id, h.index),
+						invite_earnings: MultiBalanceOf::<T>::from(0u32),
+					}
+				})
+				.collect();
+			for (currency_id, index, reward) in Self::get_referral_earnings(who.clone()) {
+				match positions
+					.iter_mut()
+					.find(|h| h.currency_id == currency_id && h.index == index)
+				{
+					Some(position) => position.invite_earnings = reward,
+					None => positions.push(IcoPosition {
+						currency_id,
+						index,
+						invite_earnings: reward,
+						..Default::default()
+					}),
+				}
+			}
+			positions
+		}
+
+		fn ico_detail(
+			ico: IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
+			is_pending: bool,
+			participants: &BTreeMap<(AssetId, u32), u32>,
+		) -> Option<IcoDetail<T::AccountId, T::BlockNumber, MultiBalanceOf<T>>> {
+			let index = ico.index?;
+			let stage = if is_pending {
+				IcoStage::Pending
+			} else {
+				match ico.start_time {
+					Some(time) if Self::now() < time => IcoStage::Upcoming,
+					Some(time) if !ico.is_terminated && Self::now() <= time.saturating_add(ico.ico_duration) => {
+						IcoStage::Ongoing
+					}
+					_ => IcoStage::Finished,
+				}
+			};
+			// The initiator receives the exchange token raised.
+			let raised_amount = UnReleaseAssets::<T>::get(&ico.initiator)
+				.into_iter()
+				.find(|h| {
+					h.currency_id == ico.currency_id
+						&& h.index == index && h.unreleased_currency_id == ico.exchange_token
+				})
+				.map(|h| h.total)
+				.unwrap_or_default();
+			Some(IcoDetail {
+				currency_id: ico.currency_id,
+				index,
+				stage,
+				soft_cap: SoftCapOf::<T>::get(ico.currency_id, index),
+				participant_count: participants.get(&(ico.currency_id, index)).copied().unwrap_or_default(),
+				raised_amount,
+				raised_usdt: ico.total_usdt,
+				initiator: ico.initiator,
+				desc: ico.desc,
+				project_name: ico.project_name,
+				token_symbol: ico.token_symbol,
+				decimals: ico.decimals,
+				official_website: ico.official_website,
+				is_must_kyc: ico.is_must_kyc,
+				start_time: ico.start_time,
+				ico_duration: ico.ico_duration,
+				total_ico_amount: ico.total_ico_amount,
+				exchange_token: ico.exchange_token,
+				exchange_token_total_amount: ico.exchange_token_total_amount,
+				user_min_amount: ico.user_min_amount,
+				user_max_amount: ico.user_max_amount,
+				is_terminated: ico.is_terminated,
+			})
+		}
+
+		/// The number of members(exclude project party) of each ico.
+		fn participant_counts() -> BTreeMap<(AssetId, u32), u32> {
+			let mut counts = BTreeMap::new();
+			for info in UnReleaseAssets::<T>::iter_values().flatten() {
+				if info.unreleased_currency_id == info.currency_id {
+					*counts.entry((info.currency_id, info.index)).or_insert(0u32) += 1;
+				}
+			}
+			counts
+		}
+
+		/// The power of the user after it decays, the power halves every `PowerHalfLife` blocks,
+		/// and decays linearly in between.
+		pub fn power_of(who: &T::AccountId) -> MultiBalanceOf<T> {
+			let power = TotalPowerOf::<T>::get(who);
+			let half_life = T::PowerHalfLife::get();
+			if half_life.is_zero() {
+				return power;
+			}
+			let elapsed = Self::now().saturating_sub(PowerCheckpointOf::<T>::get(who));
+			let halvings = (elapsed / half_life).saturated_into::<u32>();
+			if halvings >= 128 {
+				return MultiBalanceOf::<T>::from(0u32);
+			}
+			let power = U256::from(power.saturated_into::<u128>()) >> halvings;
+			let remain = U256::from((elapsed % half_life).saturated_into::<u128>());
+			let half_life = U256::from(half_life.saturated_into::<u128>());
+			let power = power.saturating_sub(power * remain / (half_life * 2));
+			power.as_u128().saturated_into::<MultiBalanceOf<T>>()
+		}
+
+		/// The power the user can use, including the power delegated to the user.
+		pub fn usable_power_of(who: &T::AccountId) -> MultiBalanceOf<T> {
+			let own = if Self::power_delegate_of(who).is_some() {
+				MultiBalanceOf::<T>::from(0u32)
+			} else {
+				Self::power_of(who)
+			};
+			Self::power_delegators_of(who)
+				.iter()
+				.fold(own, |acc, h| acc.saturating_add(Self::power_of(h)))
+		}
+
+		/// Store the decayed power of the user from now on.
+		fn checkpoint_power(who: &T::AccountId) -> MultiBalanceOf<T> {
+			let power = Self::power_of(who);
+			TotalPowerOf::<T>::insert(who, power);
+			PowerCheckpointOf::<T>::insert(who, Self::now());
+			power
+		}
+
+		/// Take back the power the user got by joining the ico.
+		fn reduce_user_power(who: &T::AccountId, amount: MultiBalanceOf<T>) {
+			let power = Self::checkpoint_power(who);
+			TotalPowerOf::<T>::insert(who, power.saturating_sub(amount));
+		}
+
+		fn split_user_amount(
+			total_amount: MultiBalanceOf<T>,
+			info: Vec<(
+				MultiBalanceOf<T>,
+				MultiBalanceOf<T>,
+				MultiBalanceOf<T>,
+				MultiBalanceOf<T>,
+			)>,
+		) -> Vec<(u32, MultiBalanceOf<T>)> {
+			const NUM: u32 = 10;
+			let av_amount = total_amount / NUM.saturated_into::<MultiBalanceOf<T>>();
+			let mut result: Vec<(u32, MultiBalanceOf<T>)> = vec![];
+
+			for (amount, tag_amount, _, _) in info.iter() {
+				let mut start_amount = tag_amount.saturating_sub(*amount);
+				let mut n = (start_amount / av_amount).saturated_into::<u32>() + 1;
+				loop {
+					let next_amount = n.saturated_into::<MultiBalanceOf<T>>() * av_amount;
+					if next_amount > *tag_amount {
+						if tag_amount.saturating_sub(start_amount) > MultiBalanceOf::<T>::from(0u32) {
+							result.push((n, tag_amount.saturating_sub(start_amount)));
+						}
+						break;
+					}
+					result.push((n, next_amount.saturating_sub(start_amount)));
+					start_amount = next_amount;
+					n += 1;
+				}
+			}
+
+			result
+		}
+
+		pub fn calculate_total_reward(
+			ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
+		) -> MultiBalanceOf<T> {
+			let mut this_time_total_usdt = ico.total_usdt;
+			let total_usdt = match ico.tag {
+				Some(tag_usdt) => tag_usdt,
+				None => TotalUsdt::<T>::get().saturating_add(this_time_total_usdt),
+			};
+
+			let mut num = total_usdt / HalfDuration.saturated_into::<MultiBalanceOf<T>>();
+			let mut remain = total_usdt % HalfDuration.saturated_into::<MultiBalanceOf<T>>();
+
+			let mut power = MultiBalanceOf::<T>::from(0u32);
+			loop {
+				if this_time_total_usdt.saturating_sub(remain) > MultiBalanceOf::<T>::from(0u32) {
+					power += (remain / (2u32.pow(num.saturated_into::<u32>())).saturated_into::<MultiBalanceOf<T>>());
+					this_time_total_usdt = this_time_total_usdt.saturating_sub(remain);
+					remain = HalfDuration.saturated_into::<MultiBalanceOf<T>>();
+					num = num.saturating_sub(1u32.saturated_into::<MultiBalanceOf<T>>());
+				} else {
+					power += (this_time_total_usdt
+						/ (2u32.pow(num.saturated_into::<u32>())).saturated_into::<MultiBalanceOf<T>>());
+					break;
+				}
+			}
+
+			let first_total_reward = Self::reward_economics().total_reward / 2u32.saturated_into::<MultiBalanceOf<T>>();
+
+			Self::u256_convert_to_balance(
+				Self::balance_convert_to_u256(power) * Self::balance_convert_to_u256(first_total_reward)
+					/ Self::balance_convert_to_u256(HalfDuration.saturated_into::<MultiBalanceOf<T>>()),
+			)
+		}
+
+		fn do_release(
+			user: T::AccountId,
+			holder: &T::AccountId,
+			currency_id: AssetId,
+			index: u32,
+			is_do: bool,
+		) -> result::Result<MultiBalanceOf<T>, DispatchError> {
+			let mut this_time_total_release = MultiBalanceOf::<T>::from(0u32);
+
+			let mut is_oprate = false;
+			let mut ico = <Ico<T>>::get(currency_id, index).ok_or(Error::<T>::IcoNotExists123)?;
+			ensure!(!Self::is_soft_cap_failed(&ico), Error::<T>::IcoFailed);
+
+			/// For initiator
+			if Self::is_ico_expire(&ico)
+				&& (IsUnservePledge::<T>::contains_key(currency_id, index)
+					&& IsUnservePledge::<T>::get(currency_id, index))
+			{
+				if is_do {
+					Self::unreserve_initiator_remain_token(&ico);
+				}
+				is_oprate = true;
+			}
+
+			/// For users
+			let asset_info_opt = Self::get_unrelease_asset_info(&user, currency_id, index);
+			match asset_info_opt {
+				None => {
+					if is_oprate && &user == &ico.initiator {
+						return Ok(MultiBalanceOf::<T>::from(0u32));
+					} else {
+						return Err(Error::<T>::NotIcoMember)?;
+					}
+				}
+				Some(asset_info) => {
+					ensure!(
+						!Self::is_allocation_pending(&user, &ico),
+						Error::<T>::AllocationNotClaimed
+					);
+					let mut total = asset_info.total;
+					if &user == &ico.initiator {
+						// The exchange tokens above the ico amount are given back to users, and the
+						// exchange tokens added to the amm are not released.
+						total = Self::get_initiator_raised_amount(&ico, total).saturating_sub(
+							Self::ico_liquidity_of(currency_id, index)
+								.map(|h| h.exchange_token_amount)
+								.unwrap_or_default(),
+						);
+					}
+					let released = asset_info.released;
+					let refund = asset_info.refund;
+
+					let should_released = ico.already_released_proportion * total;
+
+					ensure!(
+						(total.saturating_sub(released)).saturating_sub(refund) > MultiBalanceOf::<T>::from(0u32),
+						Error::<T>::UnreleasedAmountIsZero
+					);
+
+					if ico.is_terminated
+						&& &user != &ico.initiator
+						&& refund == MultiBalanceOf::<T>::from(0u32)
+						&& ico.already_released_proportion != Percent::from_percent(100u8)
+					{
+						let remain_amount = total.saturating_sub(should_released);
+						if is_do {
+							Self::terminated_released_user_token(&user, remain_amount, &ico)?;
+						}
+						this_time_total_release += remain_amount;
+						is_oprate = true;
+					}
+
+					let mut thistime_release_amount = should_released.saturating_sub(released);
+
+					if thistime_release_amount == MultiBalanceOf::<T>::from(0u32) && is_oprate == false {
+						return Err(Error::<T>::UnreleaseAmountIsZero)?;
+					} else if thistime_release_amount == MultiBalanceOf::<T>::from(0u32) && is_oprate == true {
+						return Ok(this_time_total_release);
+					};
+
+					if is_do {
+						Self::common_release(user, holder, ico, thistime_release_amount)?;
+					}
+
+					Ok(this_time_total_release.saturating_add(thistime_release_amount))
+				}
+			}
+		}
+
+		fn common_release(
+			user: T::AccountId,
+			holder: &T::AccountId,
+			ico: IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
+			thistime_release_amount: MultiBalanceOf<T>,
+		) -> DispatchResult {
+			let project_currency_id = ico.exchange_token;
+			let index = ico.index.unwrap();
+			if user == ico.initiator {
+				Self::release_to_initiator(project_currency_id, &ico.initiator, thistime_release_amount);
+			} else {
+				let user_keep_lock_amount = ico.lock_proportion * thistime_release_amount;
 				let user_unlock_amount = thistime_release_amount.saturating_sub(user_keep_lock_amount);
 				if user_unlock_amount > MultiBalanceOf::<T>::from(0u32) {
 					T::MultiCurrency::unreserve(ico.currency_id, &user, user_unlock_amount);
 				}
-				if user_keep_lock_amount > MultiBalanceOf::<T>::from(0u32) {
-					<IcoLocks<T>>::mutate(&user, ico.currency_id, |h| {
-						h.push(IcoLock {
-							start_block: Self::now(),
-							index: index,
-							total_amount: user_keep_lock_amount,
-							unlock_amount: 0u32.saturated_into::<MultiBalanceOf<T>>(),
-							unlock_duration: ico.unlock_duration,
-							per_duration_unlock_amount: Self::u256_convert_to_balance(
-								(Self::balance_convert_to_u256(thistime_release_amount)
-									* Self::balance_convert_to_u256(ico.per_duration_unlock_amount))
-									/ Self::balance_convert_to_u256(ico.total_ico_amount),
-							),
-						})
-					});
+				if let Some(vesting) = Self::vesting_of(ico.currency_id, index) {
+					Self::add_vesting_schedule(&user, holder, &ico, vesting, user_keep_lock_amount)?;
+				} else if user_keep_lock_amount > MultiBalanceOf::<T>::from(0u32) {
+					<IcoLocks<T>>::mutate(&user, ico.currency_id, |h| {
+						h.push(IcoLock {
+							start_block: Self::now(),
+							index: index,
+							total_amount: user_keep_lock_amount,
+							unlock_amount: 0u32.saturated_into::<MultiBalanceOf<T>>(),
+							unlock_duration: ico.unlock_duration,
+							per_duration_unlock_amount: Self::u256_convert_to_balance(
+								(Self::balance_convert_to_u256(thistime_release_amount)
+									* Self::balance_convert_to_u256(ico.per_duration_unlock_amount))
+									/ Self::balance_convert_to_u256(ico.total_ico_amount),
+							),
+						})
+					});
+				}
+			}
+
+			Self::update_user_unreleased_assets_info(
+				&user,
+				&ico,
+				thistime_release_amount,
+				MultiBalanceOf::<T>::from(0u32),
+				false,
+				None,
+				&None,
+			);
+
+			Self::deposit_event(Event::UserReleaseIcoAmount(
+				ico.currency_id,
+				index,
+				thistime_release_amount,
+			));
+			Ok(())
+		}
+
+		fn terminated_released_user_token(
+			user: &T::AccountId,
+			remain_amount: MultiBalanceOf<T>,
+			ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
+		) -> DispatchResult {
+			let exchange_token_amount = Self::get_swap_token_amount(false, remain_amount, &ico);
+			let exchange_token_amount = Self::give_back_payment_tokens(user, ico, exchange_token_amount)?;
+
+			/// For users
+			T::MultiCurrency::repatriate_reserved(
+				ico.currency_id,
+				&user,
+				&ico.initiator,
+				remain_amount,
+				BalanceStatus::Free,
+			);
+			Self::update_user_unreleased_assets_info(
+				&ico.initiator,
+				&ico,
+				exchange_token_amount,
+				MultiBalanceOf::<T>::from(0u32),
+				false,
+				Some(true),
+				&None,
+			);
+
+			/// For initiator
+			T::MultiCurrency::repatriate_reserved(
+				ico.exchange_token,
+				&ico.initiator,
+				&user,
+				exchange_token_amount,
+				BalanceStatus::Free,
+			);
+			Self::update_user_unreleased_assets_info(
+				&user,
+				&ico,
+				remain_amount,
+				MultiBalanceOf::<T>::from(0u32),
+				false,
+				Some(true),
+				&None,
+			);
+
+			Self::deposit_event(Event::TerminatedGiveBackAmount(
+				user.clone(),
+				ico.currency_id,
+				ico.index.unwrap(),
+				remain_amount,
+			));
+			Ok(())
+		}
+
+		fn unreserve_initiator_remain_token(
+			ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
+		) -> MultiBalanceOf<T> {
+			let index = ico.index.unwrap();
+			let remain_project_token = match Self::dutch_auction_of(ico.currency_id, index) {
+				Some(auction) => ico.total_ico_amount.saturating_sub(auction.sold_amount),
+				None => {
+					let project_total_amount = Self::get_raised_amount(&ico);
+					let unico_amount = ico.exchange_token_total_amount.saturating_sub(project_total_amount);
+					Self::get_swap_token_amount(true, unico_amount, &ico)
+				}
+			};
+			T::MultiCurrency::unreserve(ico.currency_id, &ico.initiator, remain_project_token);
+			Self::add_ico_liquidity(ico, ico.total_ico_amount.saturating_sub(remain_project_token));
+			IsUnservePledge::<T>::remove(ico.currency_id, index);
+			Self::unreserve_initiator_pledge(ico);
+			Self::deposit_event(Event::UnreservedInitiatorRemainPledgeAmount(
+				ico.currency_id,
+				index,
+				remain_project_token,
+			));
+			remain_project_token
+		}
+
+		/// The project tokens reserved by the initiator to add liquidity.
+		fn liquidity_project_token_reserved(
+			ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
+		) -> MultiBalanceOf<T> {
+			ico.index
+				.and_then(|index| Self::liquidity_of(ico.currency_id, index))
+				.map(|h| h.proportion * ico.total_ico_amount)
+				.unwrap_or_default()
+		}
+
+		/// Pair part of the raised exchange tokens and the project tokens sold into the amm at the
+		/// ico price, and reserve the liquidity for the initiator until it is unlocked. The ico
+		/// goes on as usual if the liquidity can not be added.
+		fn add_ico_liquidity(
+			ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
+			sold_amount: MultiBalanceOf<T>,
+		) {
+			let index = ico.index.unwrap();
+			let liquidity = match Self::liquidity_of(ico.currency_id, index) {
+				Some(x) => x,
+				None => return,
+			};
+			T::MultiCurrency::unreserve(
+				ico.currency_id,
+				&ico.initiator,
+				liquidity.proportion * ico.total_ico_amount,
+			);
+			if ico.is_terminated || Self::is_soft_cap_failed(ico) {
+				return;
+			}
+
+			let raised_amount = Self::get_initiator_raised_amount(
+				ico,
+				Self::get_total_and_released_amount(ico.currency_id, index, &ico.initiator).0,
+			);
+			let exchange_token_amount = liquidity.proportion * raised_amount;
+			let project_token_amount = liquidity.proportion * sold_amount;
+			if exchange_token_amount.is_zero() || project_token_amount.is_zero() {
+				return;
+			}
+
+			let result = with_transaction(|| {
+				T::MultiCurrency::unreserve(ico.exchange_token, &ico.initiator, exchange_token_amount);
+				let result = T::LiquidityHandler::add_liquidity(
+					&ico.initiator,
+					ico.currency_id,
+					ico.exchange_token,
+					project_token_amount,
+					exchange_token_amount,
+				)
+				.and_then(|(liquidity_id, liquidity_amount)| {
+					T::MultiCurrency::reserve(liquidity_id, &ico.initiator, liquidity_amount)?;
+					Ok((liquidity_id, liquidity_amount))
+				});
+				match result {
+					Ok(x) => TransactionOutcome::Commit(Ok(x)),
+					Err(e) => TransactionOutcome::Rollback(Err(e)),
+				}
+			});
+
+			match result {
+				Ok((liquidity_id, liquidity_amount)) => {
+					IcoLiquidityOf::<T>::insert(
+						ico.currency_id,
+						index,
+						IcoLiquidity {
+							liquidity_id,
+							exchange_token_amount,
+							project_token_amount,
+							liquidity_amount,
+							unlock_block: Self::now().saturating_add(liquidity.lock_duration),
+							is_unlocked: false,
+						},
+					);
+					Self::deposit_event(Event::AddIcoLiquidity(
+						ico.currency_id,
+						index,
+						liquidity_id,
+						liquidity_amount,
+					));
+				}
+				Err(_) => Self::deposit_event(Event::AddIcoLiquidityFailed(ico.currency_id, index)),
+			}
+		}
+
+		fn unreserve_initiator_pledge(
+			ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
+		) {
+			let pledge = InitiatorPledgeOf::<T>::take(ico.currency_id, ico.index.unwrap());
+			if pledge > MultiBalanceOf::<T>::from(0u32) {
+				T::MultiCurrency::unreserve(T::GetNativeCurrencyId::get(), &ico.initiator, pledge);
+			}
+		}
+
+		fn is_soft_cap_reached(
+			ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
+		) -> bool {
+			match SoftCapOf::<T>::get(ico.currency_id, ico.index.unwrap()) {
+				Some(soft_cap) => ico.total_usdt >= soft_cap,
+				None => true,
+			}
+		}
+
+		/// The ico is over, but it did not reach the soft cap.
+		fn is_soft_cap_failed(
+			ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
+		) -> bool {
+			!ico.is_terminated && Self::is_ico_expire(ico) && !Self::is_soft_cap_reached(ico)
+		}
+
+		fn refund_user(
+			user: &T::AccountId,
+			ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
+		) -> result::Result<MultiBalanceOf<T>, DispatchError> {
+			let index = ico.index.unwrap();
+			let asset_info =
+				Self::get_unrelease_asset_info(user, ico.currency_id, index).ok_or(Error::<T>::NotIcoMember)?;
+			ensure!(
+				asset_info.refund == MultiBalanceOf::<T>::from(0u32),
+				Error::<T>::AlreadyRefund
+			);
+
+			let exchange_token_amount = asset_info
+				.tags
+				.iter()
+				.fold(MultiBalanceOf::<T>::from(0u32), |acc, h| acc.saturating_add(h.2));
+
+			if Self::is_allocation_pending(user, ico) {
+				// The exchange tokens are still reserved by the user.
+				T::MultiCurrency::unreserve(ico.exchange_token, user, exchange_token_amount);
+				Self::add_refund_amount(user, ico.currency_id, index, asset_info.total);
+				Self::add_refund_amount(&ico.initiator, ico.currency_id, index, exchange_token_amount);
+				Self::reduce_user_power(user, asset_info.total_usdt);
+				return Ok(exchange_token_amount);
+			}
+
+			let exchange_token_amount = Self::give_back_payment_tokens(user, ico, exchange_token_amount)?;
+			T::MultiCurrency::repatriate_reserved(
+				ico.currency_id,
+				user,
+				&ico.initiator,
+				asset_info.total,
+				BalanceStatus::Free,
+			)?;
+			T::MultiCurrency::repatriate_reserved(
+				ico.exchange_token,
+				&ico.initiator,
+				user,
+				exchange_token_amount,
+				BalanceStatus::Free,
+			)?;
+
+			Self::add_refund_amount(user, ico.currency_id, index, asset_info.total);
+			Self::add_refund_amount(&ico.initiator, ico.currency_id, index, exchange_token_amount);
+			Self::reduce_user_power(user, asset_info.total_usdt);
+
+			Ok(exchange_token_amount)
+		}
+
+		fn refund_initiator(
+			ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
+		) -> result::Result<MultiBalanceOf<T>, DispatchError> {
+			let index = ico.index.unwrap();
+			ensure!(
+				IsUnservePledge::<T>::get(ico.currency_id, index),
+				Error::<T>::AlreadyRefund
+			);
+
+			let remain_project_token = Self::unreserve_initiator_remain_token(ico);
+			Self::refund_reward_pool(ico);
+			Self::remove_passed_icoes(ico.currency_id, index);
+
+			Ok(remain_project_token)
+		}
+
+		/// The user joined the pro-rata or dutch auction ico, but has not claimed the allocation.
+		fn is_allocation_pending(
+			user: &T::AccountId,
+			ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
+		) -> bool {
+			let index = ico.index.unwrap();
+			user != &ico.initiator
+				&& Self::ico_mode_of(ico.currency_id, index).is_settled_at_end()
+				&& !Self::is_allocation_claimed((ico.currency_id, index), user)
+		}
+
+		/// Returns the project token allocation and the excess exchange tokens of the user.
+		fn settle_allocation(
+			user: &T::AccountId,
+			ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
+		) -> result::Result<(MultiBalanceOf<T>, MultiBalanceOf<T>), DispatchError> {
+			let index = ico.index.unwrap();
+			let asset_info =
+				Self::get_unrelease_asset_info(user, ico.currency_id, index).ok_or(Error::<T>::NotIcoMember)?;
+
+			let contribution = asset_info
+				.tags
+				.iter()
+				.fold(MultiBalanceOf::<T>::from(0u32), |acc, h| acc.saturating_add(h.2));
+			if Self::ico_mode_of(ico.currency_id, index) == IcoMode::DutchAuction {
+				let (allocation, excess) = Self::settle_dutch_auction(user, ico, contribution, asset_info.total)?;
+				Self::remove_excess_usdt(user, ico, asset_info.total_usdt, contribution, excess)?;
+				return Ok((allocation, excess));
+			}
+			let total_contribution = Self::get_total_and_released_amount(ico.currency_id, index, &ico.initiator).0;
+
+			let accepted = if total_contribution > ico.exchange_token_total_amount {
+				let accepted = U256::from(contribution.saturated_into::<u128>())
+					* U256::from(ico.exchange_token_total_amount.saturated_into::<u128>())
+					/ U256::from(total_contribution.saturated_into::<u128>());
+				<u128 as TryFrom<U256>>::try_from(accepted)
+					.map_err(|_| Error::<T>::Overflow)?
+					.saturated_into::<MultiBalanceOf<T>>()
+			} else {
+				contribution
+			};
+			let excess = contribution.saturating_sub(accepted);
+			let allocation = U256::from(accepted.saturated_into::<u128>())
+				* U256::from(ico.total_ico_amount.saturated_into::<u128>())
+				/ U256::from(ico.exchange_token_total_amount.saturated_into::<u128>());
+			let allocation = <u128 as TryFrom<U256>>::try_from(allocation)
+				.map_err(|_| Error::<T>::Overflow)?
+				.saturated_into::<MultiBalanceOf<T>>();
+
+			T::MultiCurrency::repatriate_reserved(
+				ico.exchange_token,
+				user,
+				&ico.initiator,
+				accepted,
+				BalanceStatus::Reserved,
+			)?;
+			if excess > MultiBalanceOf::<T>::from(0u32) {
+				T::MultiCurrency::unreserve(ico.exchange_token, user, excess);
+			}
+			T::MultiCurrency::repatriate_reserved(
+				ico.currency_id,
+				&ico.initiator,
+				user,
+				allocation,
+				BalanceStatus::Reserved,
+			)?;
+
+			UnReleaseAssets::<T>::mutate(user, |vec| {
+				if let Some(pos) = vec
+					.iter()
+					.position(|h| h.currency_id == ico.currency_id && h.index == index)
+				{
+					let mut unreleased = vec.swap_remove(pos);
+					unreleased.total = allocation;
+					vec.push(unreleased);
+				}
+			});
+			TotalAllocationOf::<T>::mutate(ico.currency_id, index, |h| {
+				*h = h.saturating_sub(asset_info.total).saturating_add(allocation)
+			});
+			AllocationClaimedOf::<T>::insert((ico.currency_id, index), user, true);
+			Self::remove_excess_usdt(user, ico, asset_info.total_usdt, contribution, excess)?;
+
+			Ok((allocation, excess))
+		}
+
+		/// The usdt of the excess given back is removed from the power of the user and the usdt of
+		/// the user and the ico.
+		fn remove_excess_usdt(
+			user: &T::AccountId,
+			ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
+			total_usdt: MultiBalanceOf<T>,
+			contribution: MultiBalanceOf<T>,
+			excess: MultiBalanceOf<T>,
+		) -> DispatchResult {
+			if excess == MultiBalanceOf::<T>::from(0u32) || contribution == MultiBalanceOf::<T>::from(0u32) {
+				return Ok(());
+			}
+			let index = ico.index.unwrap();
+			let excess_usdt = U256::from(total_usdt.saturated_into::<u128>())
+				* U256::from(excess.saturated_into::<u128>())
+				/ U256::from(contribution.saturated_into::<u128>());
+			let excess_usdt = <u128 as TryFrom<U256>>::try_from(excess_usdt)
+				.map_err(|_| Error::<T>::Overflow)?
+				.saturated_into::<MultiBalanceOf<T>>();
+
+			UnReleaseAssets::<T>::mutate(user, |vec| {
+				if let Some(unreleased) = vec
+					.iter_mut()
+					.find(|h| h.currency_id == ico.currency_id && h.index == index)
+				{
+					unreleased.total_usdt = unreleased.total_usdt.saturating_sub(excess_usdt);
+				}
+			});
+			Ico::<T>::mutate(ico.currency_id, index, |h| {
+				if let Some(ico) = h {
+					ico.total_usdt = ico.total_usdt.saturating_sub(excess_usdt);
+				}
+			});
+			Self::reduce_user_power(user, excess_usdt);
+			Ok(())
+		}
+
+		/// Everyone pays the clearing price, the bid is paid at the price of the block that joined.
+		fn settle_dutch_auction(
+			user: &T::AccountId,
+			ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
+			contribution: MultiBalanceOf<T>,
+			allocation: MultiBalanceOf<T>,
+		) -> result::Result<(MultiBalanceOf<T>, MultiBalanceOf<T>), DispatchError> {
+			let index = ico.index.unwrap();
+			let auction = Self::dutch_auction_of(ico.currency_id, index).ok_or(Error::<T>::DutchAuctionNotExists)?;
+			let clearing_price = Self::get_dutch_auction_clearing_price(ico, &auction);
+
+			let payment = U256::from(allocation.saturated_into::<u128>())
+				* U256::from(clearing_price.saturated_into::<u128>())
+				/ U256::from(ico.total_ico_amount.saturated_into::<u128>());
+			let payment = <u128 as TryFrom<U256>>::try_from(payment)
+				.map_err(|_| Error::<T>::Overflow)?
+				.saturated_into::<MultiBalanceOf<T>>()
+				.min(contribution);
+			let excess = contribution.saturating_sub(payment);
+
+			T::MultiCurrency::repatriate_reserved(
+				ico.exchange_token,
+				user,
+				&ico.initiator,
+				payment,
+				BalanceStatus::Reserved,
+			)?;
+			if excess > MultiBalanceOf::<T>::from(0u32) {
+				T::MultiCurrency::unreserve(ico.exchange_token, user, excess);
+			}
+			T::MultiCurrency::repatriate_reserved(
+				ico.currency_id,
+				&ico.initiator,
+				user,
+				allocation,
+				BalanceStatus::Reserved,
+			)?;
+			AllocationClaimedOf::<T>::insert((ico.currency_id, index), user, true);
+
+			Ok((allocation, excess))
+		}
+
+		/// The ico with the current price of the dutch auction as `exchange_token_total_amount`.
+		fn get_priced_ico(
+			ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
+		) -> result::Result<IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>, DispatchError>
+		{
+			let mut priced_ico = ico.clone();
+			let index = ico.index.ok_or(Error::<T>::IcoIndexNotExists)?;
+			if Self::ico_mode_of(ico.currency_id, index) == IcoMode::DutchAuction {
+				let auction =
+					Self::dutch_auction_of(ico.currency_id, index).ok_or(Error::<T>::DutchAuctionNotExists)?;
+				priced_ico.exchange_token_total_amount = Self::get_dutch_auction_price(ico, &auction, Self::now());
+			}
+			Ok(priced_ico)
+		}
+
+		fn get_dutch_auction_price(
+			ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
+			auction: &DutchAuctionInfo<MultiBalanceOf<T>>,
+			at: T::BlockNumber,
+		) -> MultiBalanceOf<T> {
+			let start_time = ico.start_time.unwrap_or(at);
+			let blocks = at.saturating_sub(start_time).saturated_into::<u32>();
+			let decay = auction
+				.decay_per_block
+				.saturating_mul(blocks.saturated_into::<MultiBalanceOf<T>>());
+			auction
+				.start_exchange_token_total_amount
+				.saturating_sub(decay)
+				.max(ico.exchange_token_total_amount)
+		}
+
+		/// The price when the ico amount is sold out, or the price at the end of the ico.
+		fn get_dutch_auction_clearing_price(
+			ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
+			auction: &DutchAuctionInfo<MultiBalanceOf<T>>,
+		) -> MultiBalanceOf<T> {
+			match auction.clearing_exchange_token_total_amount {
+				Some(price) => price,
+				None => {
+					let end_time = ico.start_time.unwrap_or_default().saturating_add(ico.ico_duration);
+					Self::get_dutch_auction_price(ico, auction, end_time)
+				}
+			}
+		}
+
+		fn add_dutch_auction_sold_amount(
+			priced_ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
+			amount: MultiBalanceOf<T>,
+		) -> DispatchResult {
+			let index = priced_ico.index.ok_or(Error::<T>::IcoIndexNotExists)?;
+			DutchAuctionOf::<T>::try_mutate(priced_ico.currency_id, index, |h| -> DispatchResult {
+				let auction = h.as_mut().ok_or(Error::<T>::DutchAuctionNotExists)?;
+				auction.sold_amount = auction.sold_amount.saturating_add(amount);
+				if auction.sold_amount >= priced_ico.total_ico_amount {
+					auction.clearing_exchange_token_total_amount = Some(priced_ico.exchange_token_total_amount);
+				}
+				Ok(())
+			})
+		}
+
+		/// The amount of exchange token that the initiator gets from the ico.
+		fn get_initiator_raised_amount(
+			ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
+			total: MultiBalanceOf<T>,
+		) -> MultiBalanceOf<T> {
+			let index = ico.index.unwrap();
+			match Self::ico_mode_of(ico.currency_id, index) {
+				IcoMode::FirstComeFirstServed | IcoMode::Lottery => total,
+				IcoMode::ProRata => total.min(ico.exchange_token_total_amount),
+				IcoMode::DutchAuction => match Self::dutch_auction_of(ico.currency_id, index) {
+					Some(auction) => Self::u256_convert_to_balance(
+						Self::balance_convert_to_u256(auction.sold_amount)
+							* Self::balance_convert_to_u256(Self::get_dutch_auction_clearing_price(ico, &auction))
+							/ Self::balance_convert_to_u256(ico.total_ico_amount),
+					)
+					.min(total),
+					None => total,
+				},
+			}
+		}
+
+		fn add_refund_amount(who: &T::AccountId, currency_id: AssetId, index: u32, amount: MultiBalanceOf<T>) {
+			UnReleaseAssets::<T>::mutate(who, |vec| {
+				if let Some(pos) = vec
+					.iter()
+					.position(|h| h.currency_id == currency_id && h.index == index)
+				{
+					let mut unreleased = vec.swap_remove(pos);
+					unreleased.refund = unreleased.refund.saturating_add(amount);
+					vec.push(unreleased);
+				}
+			});
+		}
+
+		fn is_ico_expire(ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>) -> bool {
+			if cfg!(any(feature = "std", feature = "runtime-benchmarks", test)) {
+				return true;
+			}
+			if let Some(time) = ico.start_time {
+				if !(ico.is_terminated || (time + ico.ico_duration < Self::now())) {
+					return false;
+				}
+			}
+			true
+		}
+
+		fn is_pending_ico(currency_id: &AssetId) -> bool {
+			<PendingIco<T>>::contains_key(currency_id)
+		}
+
+		fn insert_invite_info(invitee: &T::AccountId, inviter: Option<T::AccountId>, currency_id: AssetId, index: u32) {
+			match inviter {
+				Some(inviter) => {
+					let invitees = InviteInfoOf::<T>::get(&inviter, currency_id);
+					if let None = invitees
+						.iter()
+						.position(|h| &h.invitee == invitee && h.currency_id == currency_id && h.index == index)
+					{
+						InviteInfoOf::<T>::mutate(&inviter, currency_id, |k| {
+							k.push(InviteInfo {
+								inviter: inviter.clone(),
+								invitee: invitee.clone(),
+								currency_id: currency_id,
+								index: index,
+								reward: None,
+							})
+						});
+					}
+				}
+				None => {}
+			}
+		}
+
+		fn update_user_icoes(who: &T::AccountId, currency_id: AssetId, index: u32) {
+			let mut icoes = IcoesOf::<T>::get(&who);
+			if let Some(pos) = icoes.iter().position(|h| h.0 == currency_id && h.1 == index) {
+			} else {
+				icoes.push((currency_id, index))
+			}
+			IcoesOf::<T>::insert(who, icoes);
+		}
+
+		fn insert_ico_assets_info(
+			who: &T::AccountId,
+			ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
+			amount: MultiBalanceOf<T>,
+			total_usdt: MultiBalanceOf<T>,
+			inviter: &Option<T::AccountId>,
+		) {
+			Self::update_user_unreleased_assets_info(&who, &ico, amount, total_usdt, true, None, &inviter);
+			Self::update_user_unreleased_assets_info(&ico.initiator, ico, amount, total_usdt, true, None, &inviter);
+		}
+
+		#[transactional]
+		fn swap(
+			who: &T::AccountId,
+			amount: MultiBalanceOf<T>,
+			ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
+		) -> result::Result<MultiBalanceOf<T>, DispatchError> {
+			let initiator = &ico.initiator;
+			let this_time_project_token_amount = Self::get_swap_token_amount(true, amount, &ico);
+
+			T::MultiCurrency::transfer(ico.exchange_token, &who, &initiator, amount)?;
+			T::MultiCurrency::reserve(ico.exchange_token, &initiator, amount)?;
+
+			T::MultiCurrency::repatriate_reserved(
+				ico.currency_id,
+				&initiator,
+				who,
+				this_time_project_token_amount,
+				BalanceStatus::Reserved,
+			)?;
+			Ok(this_time_project_token_amount)
+		}
+
+		fn get_swap_token_amount(
+			is_main: bool,
+			amount: MultiBalanceOf<T>,
+			ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
+		) -> MultiBalanceOf<T> {
+			let total_project_token_amount = ico.total_ico_amount;
+			let total_exchange_token_amount = ico.exchange_token_total_amount;
+			let mut result: U256;
+			if is_main {
+				result = Self::balance_convert_to_u256(amount)
+					* Self::balance_convert_to_u256(total_project_token_amount)
+					/ Self::balance_convert_to_u256(total_exchange_token_amount)
+			} else {
+				result = Self::balance_convert_to_u256(amount)
+					* Self::balance_convert_to_u256(total_exchange_token_amount)
+					/ Self::balance_convert_to_u256(total_project_token_amount)
+			}
+			Self::u256_convert_to_balance(result)
+		}
+
+		fn update_user_unreleased_assets_info(
+			who: &T::AccountId,
+			ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
+			amount: MultiBalanceOf<T>,
+			total_usdt: MultiBalanceOf<T>,
+			is_join: bool,
+			is_terminated: Option<bool>,
+			inviter: &Option<T::AccountId>,
+		) {
+			let is_initiator = who == &ico.initiator;
+			let mut unreleased_currency_id = ico.currency_id;
+			let mut total = amount;
+			let mut user = who;
+
+			let index = ico.index.unwrap();
+			let main_token_amount = amount;
+
+			if is_initiator {
+				user = &ico.initiator;
+				unreleased_currency_id = ico.exchange_token;
+			} else {
+				if is_join {
+					total = Self::get_swap_token_amount(true, amount, &ico);
+					TotalAllocationOf::<T>::mutate(ico.currency_id, index, |h| *h = h.saturating_add(total));
+				}
+			}
+
+			let mut assets_info = <UnReleaseAssets<T>>::get(&user);
+			let mut new_info: UnRelease<MultiBalanceOf<T>, AssetId, T::AccountId>;
+			if let Some(pos) = assets_info
+				.iter()
+				.position(|h| h.currency_id == ico.currency_id && h.index == index)
+			{
+				new_info = assets_info.swap_remove(pos);
+
+				if is_join {
+					if who != &ico.initiator {
+						new_info.tags.push((
+							total_usdt,
+							ico.total_usdt.saturating_add(total_usdt),
+							main_token_amount,
+							total,
+						));
+					} else {
+						new_info.tags = vec![];
+					}
+					new_info.total_usdt = new_info.total_usdt.saturating_add(total_usdt);
+					new_info.total = new_info.total.saturating_add(total);
+					if new_info.inviter.is_none() {
+						new_info.inviter = inviter.clone();
+					}
+				} else {
+					if is_terminated.is_some() {
+						new_info.refund = total;
+					} else {
+						new_info.released = new_info.released.saturating_add(total);
+					}
+				}
+			} else {
+				if who != &ico.initiator {
+					new_info = UnRelease {
+						currency_id: ico.currency_id,
+						index: index,
+						inviter: inviter.clone(),
+						tags: vec![(
+							total_usdt,
+							ico.total_usdt.saturating_add(total_usdt),
+							main_token_amount,
+							total,
+						)],
+						total_usdt: total_usdt,
+						unreleased_currency_id: unreleased_currency_id,
+						total: total,
+						refund: MultiBalanceOf::<T>::from(0u32),
+						released: MultiBalanceOf::<T>::from(0u32),
+						reward: None,
+					};
+				} else {
+					new_info = UnRelease {
+						currency_id: ico.currency_id.clone(),
+						index: index,
+						inviter: inviter.clone(),
+						tags: vec![],
+						total_usdt: total_usdt,
+						unreleased_currency_id: unreleased_currency_id,
+						total: total,
+						refund: MultiBalanceOf::<T>::from(0u32),
+						released: MultiBalanceOf::<T>::from(0u32),
+						reward: None,
+					};
+				}
+			};
+			assets_info.push(new_info);
+			UnReleaseAssets::<T>::insert(user, assets_info);
+		}
+
+		/// The `user_max_amount` multiplied by the tier of the user.
+		fn get_user_max_amount(who: &T::AccountId, user_max_amount: MultiBalanceOf<T>) -> MultiBalanceOf<T> {
+			let percent = T::TierHandler::get_user_max_amount_percent(who);
+			user_max_amount
+				.saturating_mul(MultiBalanceOf::<T>::from(percent))
+				.checked_div(&MultiBalanceOf::<T>::from(100u32))
+				.unwrap_or(user_max_amount)
+		}
+
+		/// Verify the allowlist proof of the user and record the max allocation.
+		fn check_allowlist(
+			who: &T::AccountId,
+			currency_id: AssetId,
+			index: u32,
+			allowlist_proof: Option<AllowlistProof<MultiBalanceOf<T>>>,
+		) -> DispatchResult {
+			let root = match Self::allowlist_root_of(currency_id, index) {
+				Some(x) => x,
+				None => return Ok(()),
+			};
+			let allowlist_proof = match allowlist_proof {
+				Some(x) => x,
+				// The user has proved it before.
+				None => return Ok(()),
+			};
+			ensure!(
+				allowlist_proof.proof.len() <= T::MaxAllowlistProofLength::get() as usize,
+				Error::<T>::AllowlistProofTooLong
+			);
+
+			let leaf = Self::allowlist_leaf_hash(who, allowlist_proof.max_allocation);
+			ensure!(
+				Self::verify_allowlist_proof(root, leaf, &allowlist_proof.proof),
+				Error::<T>::InvalidAllowlistProof
+			);
+			AllowlistCapOf::<T>::insert((currency_id, index), who, allowlist_proof.max_allocation);
+			Ok(())
+		}
+
+		/// The leaf of the `(account, max_allocation)` entry in the allowlist.
+		pub fn allowlist_leaf_hash(who: &T::AccountId, max_allocation: MultiBalanceOf<T>) -> H256 {
+			H256::from(blake2_256(&(who, max_allocation).encode()))
+		}
+
+		/// The two children of a node are hashed in ascending order.
+		pub fn verify_allowlist_proof(root: H256, leaf: H256, proof: &[H256]) -> bool {
+			let computed_root = proof.iter().fold(leaf, |node, sibling| {
+				let pair = if node <= *sibling {
+					[node.as_bytes(), sibling.as_bytes()].concat()
+				} else {
+					[sibling.as_bytes(), node.as_bytes()].concat()
+				};
+				H256::from(blake2_256(&pair))
+			});
+
+			computed_root == root
+		}
+
+		fn join_simple_check(
+			ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
+			amount: MultiBalanceOf<T>,
+			currency_id: AssetId,
+			index: u32,
+			who: &T::AccountId,
+			total_usdt: MultiBalanceOf<T>,
+		) -> result::Result<bool, DispatchError> {
+			ensure!(
+				amount != 0u128.saturated_into::<MultiBalanceOf<T>>(),
+				Error::<T>::AmountIsZero
+			);
+
+			match ico.start_time {
+				Some(time) => {
+					#[cfg(test)]
+					println!("time: {:?}, now:{:?}", time, Self::now());
+					ensure!(time <= Self::now(), Error::<T>::IsNotStartIcoTime);
+					if cfg!(any(feature = "std", feature = "runtime-benchmarks", test)) == false {
+						ensure!(ico.ico_duration + time >= Self::now(), Error::<T>::IcoExpire);
+					}
+				}
+
+				None => Err(Error::<T>::StartTimeNotExists)?,
+			}
+			ensure!(!ico.is_terminated, Error::<T>::IcoTerminated);
+			ensure!(&ico.initiator != who, Error::<T>::InitiatorIsYourself);
+			ensure!(
+				!Self::is_exclude_area(&who, &currency_id, index)?,
+				Error::<T>::InExcludeArea
+			);
+
+			Self::check_area_cap(who, currency_id, index, total_usdt)?;
+
+			let mut total_usdt = total_usdt;
+			let unreleased_info_opt = Self::get_unrelease_asset_info(&who, currency_id, index);
+			match unreleased_info_opt.as_ref() {
+				None => {}
+				Some(x) => {
+					total_usdt = total_usdt.checked_add(&x.total_usdt).ok_or(Error::<T>::Overflow)?;
+				}
+			}
+			runtime_print!(
+				"total_usdt: {:?}, user_min_amount: {:?}, user_max_amount: {:?}",
+				total_usdt,
+				ico.user_min_amount,
+				ico.user_max_amount
+			);
+			let user_max_amount = Self::kyc_level_caps_of(currency_id, index)
+				.into_iter()
+				.find(|h| h.0 == T::KycHandler::get_user_level(who))
+				.map(|h| h.1)
+				.unwrap_or(ico.user_max_amount);
+			ensure!(
+				total_usdt >= ico.user_min_amount && total_usdt <= Self::get_user_max_amount(who, user_max_amount),
+				Error::<T>::AmountNotMeetProjectRequirement
+			);
+			ensure!(
+				total_usdt >= IcoMinUsdtAmount::<T>::get() && total_usdt <= IcoMaxUsdtAmount::<T>::get(),
+				Error::<T>::AmountNotMeetSystemRequirement
+			);
+			if AllowlistRootOf::<T>::contains_key(currency_id, index) {
+				let max_allocation =
+					Self::allowlist_cap_of((currency_id, index), &who).ok_or(Error::<T>::NotInAllowlist)?;
+				ensure!(total_usdt <= max_allocation, Error::<T>::AmountMoreThanAllowlistCap);
+			}
+
+			if let Some(info) = unreleased_info_opt {
+				ensure!(
+					info.tags.len().saturating_add(1) <= ico.user_ico_max_times.into(),
+					Error::<T>::IcoTimesToMax
+				);
+			}
+
+			if Self::ico_mode_of(currency_id, index) == IcoMode::Lottery {
+				let lottery = Self::lottery_of(currency_id, index).ok_or(Error::<T>::NotLotteryIco)?;
+				ensure!(lottery.is_drawn, Error::<T>::LotteryNotDrawn);
+				ensure!(
+					Self::is_lottery_winner((currency_id, index), who),
+					Error::<T>::NotLotteryWinner
+				);
+			}
+
+			match Self::ico_mode_of(currency_id, index) {
+				IcoMode::FirstComeFirstServed | IcoMode::Lottery => {
+					let initiator_total_amount = Self::get_raised_amount(&ico);
+
+					ensure!(
+						ico.exchange_token_total_amount.saturating_sub(initiator_total_amount) >= amount,
+						Error::<T>::ProjectIcoAmountToMax
+					);
+				}
+				IcoMode::ProRata => {}
+				IcoMode::DutchAuction => {
+					let auction =
+						Self::dutch_auction_of(currency_id, index).ok_or(Error::<T>::DutchAuctionNotExists)?;
+					ensure!(
+						ico.total_ico_amount.saturating_sub(auction.sold_amount)
+							>= Self::get_swap_token_amount(true, amount, &ico),
+						Error::<T>::ProjectIcoAmountToMax
+					);
+				}
+			}
+			Ok(true)
+		}
+
+		fn get_unrelease_asset_info(
+			who: &T::AccountId,
+			currency_id: AssetId,
+			index: u32,
+		) -> Option<UnRelease<MultiBalanceOf<T>, AssetId, T::AccountId>> {
+			let mut unrelease_info_vec = UnReleaseAssets::<T>::get(&who);
+			if let Some(pos) = unrelease_info_vec
+				.iter()
+				.position(|h| h.currency_id == currency_id && h.index == index)
+			{
+				return Some(unrelease_info_vec.swap_remove(pos));
+			}
+			None
+		}
+
+		fn update_initiate_icoes(currency_id: AssetId, index: u32, who: &T::AccountId, status: IcoStatus) {
+			let mut icoes = InitiatedIcoesOf::<T>::get(&who);
+			if let Some(pos) = icoes
+				.iter()
+				.position(|h| h.currency_id == currency_id && h.index == index)
+			{
+				let mut ico = icoes.swap_remove(pos);
+				ico.status = status;
+				icoes.push(ico);
+				InitiatedIcoesOf::<T>::insert(who, icoes);
+			}
+		}
+
+		fn now() -> T::BlockNumber {
+			<system::Pallet<T>>::block_number()
+		}
+
+		fn is_exclude_area(
+			who: &T::AccountId,
+			currency_id: &AssetId,
+			index: u32,
+		) -> result::Result<bool, DispatchError> {
+			let ico = <Ico<T>>::get(currency_id, index).ok_or(Error::<T>::IcoNotExists123)?;
+			if &ico.is_must_kyc == &true {
+				if Self::is_already_kyc(&who) {
+					let nations = ico.exclude_area;
+					if !nations.is_empty() {
+						let nation_opt = Self::get_uesr_area(&who);
+						match nation_opt {
+							None => {
+								return Err(Error::<T>::UserAreaNotExists)?;
+							}
+							Some(nation) => {
+								if let Some(pos) = nations.iter().position(|h| *h == nation) {
+									return Ok(true);
+								}
+							}
+						};
+					}
+				} else {
+					Err(Error::<T>::NotKycUser)?;
 				}
 			}
 
-			Self::update_user_unreleased_assets_info(
-				&user,
-				&ico,
-				thistime_release_amount,
-				MultiBalanceOf::<T>::from(0u32),
-				false,
-				None,
-				&None,
+			Ok(false)
+		}
+
+		fn exchange_token_convert_usdt(
+			currency_id: AssetId,
+			decimals: u8,
+			amount: MultiBalanceOf<T>,
+		) -> result::Result<MultiBalanceOf<T>, DispatchError> {
+			let price = Self::get_token_price(currency_id);
+			ensure!(price != MultiBalanceOf::<T>::from(0u32), Error::<T>::PriceNotExists);
+			let decimals_amount = 10u128
+				.saturating_pow(decimals as u32)
+				.saturated_into::<MultiBalanceOf<T>>();
+			let mut usdt = Self::u256_convert_to_balance(
+				Self::balance_convert_to_u256(price) * Self::balance_convert_to_u256(amount)
+					/ Self::balance_convert_to_u256(decimals_amount),
 			);
 
-			Self::deposit_event(Event::UserReleaseIcoAmount(
-				ico.currency_id,
-				index,
-				thistime_release_amount,
-			));
-		}
+			let multiple = PowerMultipleOf::<T>::get(currency_id);
 
-		fn terminated_released_user_token(
-			user: &T::AccountId,
-			remain_amount: MultiBalanceOf<T>,
-			ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
-		) {
-			let exchange_token_amount = Self::get_swap_token_amount(false, remain_amount, &ico);
+			usdt = (usdt * multiple.up.saturated_into::<MultiBalanceOf<T>>())
+				.checked_div(&multiple.down.saturated_into::<MultiBalanceOf<T>>())
+				.ok_or(Error::<T>::DivByZero)?;
 
-			/// For users
-			T::MultiCurrency::repatriate_reserved(
-				ico.currency_id,
-				&user,
-				&ico.initiator,
-				remain_amount,
-				BalanceStatus::Free,
-			);
-			Self::update_user_unreleased_assets_info(
-				&ico.initiator,
-				&ico,
-				exchange_token_amount,
-				MultiBalanceOf::<T>::from(0u32),
-				false,
-				Some(true),
-				&None,
-			);
+			Ok(usdt)
+		}
 
-			/// For initiator
-			T::MultiCurrency::repatriate_reserved(
-				ico.exchange_token,
-				&ico.initiator,
-				&user,
-				exchange_token_amount,
-				BalanceStatus::Free,
-			);
-			Self::update_user_unreleased_assets_info(
-				&user,
-				&ico,
-				remain_amount,
-				MultiBalanceOf::<T>::from(0u32),
-				false,
-				Some(true),
-				&None,
-			);
+		pub fn get_token_price(currency_id: AssetId) -> MultiBalanceOf<T> {
+			if currency_id == T::USDCurrencyId::get() {
+				return USD.saturated_into::<MultiBalanceOf<T>>();
+			}
+			match T::PriceData::get_price(currency_id, T::USDCurrencyId::get()) {
+				Some(x) => {
+					runtime_print!(
+						" ---------------the token {:?}, price is {:?} ------------------",
+						currency_id,
+						x
+					);
+					return x.saturated_into::<MultiBalanceOf<T>>();
+				}
+				None => return MultiBalanceOf::<T>::from(0u32),
+			}
+		}
 
-			Self::deposit_event(Event::TerminatedGiveBackAmount(
-				user.clone(),
-				ico.currency_id,
-				ico.index.unwrap(),
-				remain_amount,
-			));
+		fn get_uesr_area(who: &T::AccountId) -> Option<AreaCode> {
+			T::KycHandler::get_user_area(who)
 		}
 
-		fn unreserve_initiator_remain_token(
-			ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
-		) {
-			let index = ico.index.unwrap();
-			let (project_total_amount, _, _) =
-				Self::get_total_and_released_amount(ico.currency_id, index, &ico.initiator);
-			let unico_amount = ico.exchange_token_total_amount.saturating_sub(project_total_amount);
-			let remain_project_token = Self::get_swap_token_amount(true, unico_amount, &ico);
-			T::MultiCurrency::unreserve(ico.currency_id, &ico.initiator, remain_project_token);
-			IsUnservePledge::<T>::remove(ico.currency_id, index);
-			Self::deposit_event(Event::UnreservedInitiatorRemainPledgeAmount(
-				ico.currency_id,
-				index,
-				remain_project_token,
-			));
+		/// The users of the area join no more than the cap of the area in total.
+		fn check_area_cap(
+			who: &T::AccountId,
+			currency_id: AssetId,
+			index: u32,
+			usdt: MultiBalanceOf<T>,
+		) -> DispatchResult {
+			if let Some(area) = Self::get_uesr_area(who) {
+				if let Some((_, cap)) = Self::area_caps_of(currency_id, index).into_iter().find(|h| h.0 == area) {
+					ensure!(
+						Self::area_raised_of((currency_id, index), area).saturating_add(usdt) <= cap,
+						Error::<T>::AreaCapExceeded
+					);
+				}
+			}
+			Ok(())
 		}
 
-		fn is_ico_expire(ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>) -> bool {
-			if cfg!(any(feature = "std", feature = "runtime-benchmarks", test)) {
-				return true;
+		fn add_area_raised(who: &T::AccountId, currency_id: AssetId, index: u32, usdt: MultiBalanceOf<T>) {
+			if let Some(area) = Self::get_uesr_area(who) {
+				if AreaCapsOf::<T>::get(currency_id, index).iter().any(|h| h.0 == area) {
+					AreaRaisedOf::<T>::mutate((currency_id, index), area, |h| *h = h.saturating_add(usdt));
+				}
 			}
-			if let Some(time) = ico.start_time {
-				if !(ico.is_terminated || (time + ico.ico_duration < Self::now())) {
-					return false;
+		}
+
+		fn unlock_asset(
+			who: &T::AccountId,
+			currency_id: &AssetId,
+			index: u32,
+			is_do: bool,
+			locks: &mut [IcoLock<MultiBalanceOf<T>, T::BlockNumber>],
+		) -> (MultiBalanceOf<T>, Vec<IcoLock<MultiBalanceOf<T>, T::BlockNumber>>) {
+			let mut total = <MultiBalanceOf<T>>::from(0u32);
+			for i in 0..locks.len() {
+				if locks[i].index != index {
+					continue;
+				}
+
+				let time = Self::now().saturating_sub(locks[i].start_block);
+				if locks[i].unlock_duration == T::BlockNumber::from(0u32) {
+					let unlock_amount = locks[i].total_amount.saturating_sub(locks[i].unlock_amount);
+					if is_do {
+						T::MultiCurrency::unreserve(*currency_id, &who, unlock_amount);
+					}
+					total += unlock_amount;
+				} else {
+					let num = time / locks[i].unlock_duration;
+					let total_unlock_amount = (num
+						.saturated_into::<u32>()
+						.saturated_into::<MultiBalanceOf<T>>()
+						.saturating_mul(locks[i].per_duration_unlock_amount))
+					.min(locks[i].total_amount);
+					let this_time_unlock_amount = total_unlock_amount.saturating_sub(locks[i].unlock_amount);
+					if is_do {
+						locks[i].unlock_amount = total_unlock_amount;
+						T::MultiCurrency::unreserve(*currency_id, &who, this_time_unlock_amount);
+					}
+					total += this_time_unlock_amount;
 				}
 			}
-			true
+			if is_do {
+				let mut locks = locks.to_vec();
+				locks.retain(|h| h.total_amount != h.unlock_amount);
+			}
+
+			(total, locks.to_vec())
 		}
 
-		fn is_pending_ico(currency_id: &AssetId) -> bool {
-			let pending_ico = <PendingIco<T>>::get();
-			let pos_opt = pending_ico.iter().position(|h| currency_id == &h.ico.currency_id);
-			match pos_opt {
+		fn do_join(
+			user: T::AccountId,
+			currency_id: AssetId,
+			index: u32,
+			payment_token: Option<AssetId>,
+			amount: MultiBalanceOf<T>,
+			inviter: Option<<T::Lookup as StaticLookup>::Source>,
+			allowlist_proof: Option<AllowlistProof<MultiBalanceOf<T>>>,
+			min_power: Option<MultiBalanceOf<T>>,
+			max_price: Option<MultiBalanceOf<T>>,
+		) -> DispatchResult {
+			// The referrer of the user is the inviter if it is in the ico.
+			let inviter = match inviter {
 				None => {
-					return false;
+					Self::referrer_of(&user).filter(|referrer| Self::is_member(referrer, currency_id, index, false))
 				}
-				Some(x) => {
-					return true;
+				Some(x) => Some(T::Lookup::lookup(x)?),
+			};
+
+			if let Some(inv) = inviter.as_ref() {
+				ensure!(&user != inv, Error::<T>::CanNotInviteYouself);
+				ensure!(
+					Self::is_member(inv, currency_id, index, false),
+					Error::<T>::InviterNotInIco
+				);
+			}
+
+			let mut ico = <Ico<T>>::get(currency_id, index).ok_or(Error::<T>::IcoNotExists123)?;
+			let payment_token = payment_token.filter(|token| token != &ico.exchange_token);
+			Self::ensure_receipt_owner(&user, currency_id, index)?;
+			Self::check_price_guard(ico.exchange_token)?;
+			if let Some(token) = payment_token {
+				Self::check_price_guard(token)?;
+			}
+			// The amount of the other payment tokens is joined as the amount in the exchange token.
+			let (total_usdt, exchange_token_amount) = match payment_token {
+				Some(token) => Self::convert_payment_token(&ico, token, amount)?,
+				None => {
+					let exchange_token_decimals = T::CurrenciesHandler::get_metadata(ico.exchange_token)?.decimals;
+					(
+						Self::exchange_token_convert_usdt(ico.exchange_token, exchange_token_decimals, amount)?,
+						amount,
+					)
+				}
+			};
+			ensure!(total_usdt != MultiBalanceOf::<T>::from(0u32), Error::<T>::PowerIsZero);
+			if let Some(min_power) = min_power {
+				ensure!(total_usdt >= min_power, Error::<T>::PowerLessThanMin);
+			}
+			let new_total_usdt = ico.total_usdt.checked_add(&total_usdt).ok_or(Error::<T>::Overflow)?;
+
+			Self::check_allowlist(&user, currency_id, index, allowlist_proof)?;
+
+			// The dutch auction ico is priced at the current block.
+			let priced_ico = Self::get_priced_ico(&ico)?;
+			if let Some(max_price) = max_price {
+				let project_token_decimals = T::CurrenciesHandler::get_metadata(currency_id)?.decimals;
+				let one_project_token = 10u128
+					.saturating_pow(project_token_decimals as u32)
+					.saturated_into::<MultiBalanceOf<T>>();
+				ensure!(
+					Self::get_swap_token_amount(false, one_project_token, &priced_ico) <= max_price,
+					Error::<T>::PriceMoreThanMax
+				);
+			}
+			Self::join_simple_check(
+				&priced_ico,
+				exchange_token_amount,
+				ico.currency_id,
+				index,
+				&user,
+				total_usdt,
+			)?;
+
+			let user_exchange_amount = match (payment_token, Self::ico_mode_of(currency_id, index)) {
+				(Some(token), _) => Self::swap_payment_token(&user, token, amount, exchange_token_amount, &priced_ico)?,
+				(None, IcoMode::FirstComeFirstServed) | (None, IcoMode::Lottery) => {
+					Self::swap(&user, amount, &priced_ico)?
 				}
+				(None, IcoMode::ProRata) => {
+					// The allocation is not known until the end of the ico.
+					T::MultiCurrency::reserve(ico.exchange_token, &user, amount)?;
+					Self::get_swap_token_amount(true, amount, &priced_ico)
+				}
+				(None, IcoMode::DutchAuction) => {
+					// The clearing price is not known until the end of the ico.
+					T::MultiCurrency::reserve(ico.exchange_token, &user, amount)?;
+					let project_token_amount = Self::get_swap_token_amount(true, amount, &priced_ico);
+					Self::add_dutch_auction_sold_amount(&priced_ico, project_token_amount)?;
+					project_token_amount
+				}
+			};
+
+			if payment_token.is_some() {
+				// The initiator gets the other payment tokens by `PaymentRaisedOf`.
+				Self::update_user_unreleased_assets_info(
+					&user,
+					&priced_ico,
+					exchange_token_amount,
+					total_usdt,
+					true,
+					None,
+					&inviter,
+				);
+			} else {
+				Self::insert_ico_assets_info(&user, &priced_ico, amount, total_usdt, &inviter);
+			}
+			Self::update_user_icoes(&user, currency_id, index);
+			Self::add_area_raised(&user, currency_id, index, total_usdt);
+			// Must be at the end
+			ico.total_usdt = new_total_usdt;
+			Ico::<T>::insert(currency_id, index, &ico);
+			Self::add_user_power(&user, total_usdt)?;
+			if let Some(inv) = inviter.as_ref() {
+				// The first inviter becomes the referrer of the user.
+				if Self::referrer_of(&user).is_none() && !Self::is_referral_cycle(&user, inv) {
+					Self::insert_referrer(&user, inv);
+				}
+			}
+			Self::insert_invite_info(&user, inviter, currency_id, index);
+			// The allocation of the icoes settled at the end is known when it is claimed.
+			if !Self::ico_mode_of(currency_id, index).is_settled_at_end() {
+				Self::update_receipt(&user, currency_id, index)?;
 			}
-		}
 
-		fn insert_invite_info(invitee: &T::AccountId, inviter: Option<T::AccountId>, currency_id: AssetId, index: u32) {
-			match inviter {
-				Some(inviter) => {
-					let invitees = InviteInfoOf::<T>::get(&inviter, currency_id);
-					if let None = invitees
-						.iter()
-						.position(|h| &h.invitee == invitee && h.currency_id == currency_id && h.index == index)
-					{
-						InviteInfoOf::<T>::mutate(&inviter, currency_id, |k| {
-							k.push(InviteInfo {
-								inviter: inviter.clone(),
-								invitee: invitee.clone(),
-								currency_id: currency_id,
-								index: index,
-								reward: None,
-							})
-						});
-					}
-				}
-				None => {}
+			match payment_token {
+				Some(token) => Self::deposit_event(Event::JoinWithPaymentToken(
+					user,
+					currency_id,
+					index,
+					token,
+					amount,
+					user_exchange_amount,
+				)),
+				None => Self::deposit_event(Event::Join(user, currency_id, index, amount, user_exchange_amount)),
 			}
+			Ok(())
 		}
 
-		fn update_user_icoes(who: &T::AccountId, currency_id: AssetId, index: u32) {
-			let mut icoes = IcoesOf::<T>::get(&who);
-			if let Some(pos) = icoes.iter().position(|h| h.0 == currency_id && h.1 == index) {
-			} else {
-				icoes.push((currency_id, index))
+		/// The oracle price must be fresh and close to the TWAP of the swap, if they exist.
+		fn check_price_guard(currency_id: AssetId) -> DispatchResult {
+			if currency_id == T::USDCurrencyId::get() {
+				return Ok(());
 			}
-			IcoesOf::<T>::insert(who, icoes);
+			if let Some(age) = T::PriceData::get_oracle_price_age(currency_id) {
+				ensure!(age <= T::MaxPriceAge::get(), Error::<T>::PriceTooOld);
+			}
+			if let Some(twap) = T::PriceData::get_twap_price(currency_id, T::USDCurrencyId::get()) {
+				let twap = twap.saturated_into::<MultiBalanceOf<T>>();
+				let price = Self::get_token_price(currency_id);
+				let deviation = if price > twap { price - twap } else { twap - price };
+				ensure!(
+					deviation <= T::MaxTwapDeviation::get() * twap,
+					Error::<T>::PriceDeviationTooLarge
+				);
+			}
+			Ok(())
 		}
 
-		fn insert_ico_assets_info(
-			who: &T::AccountId,
+		/// The payment token in USD and in the exchange token.
+		fn convert_payment_token(
 			ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
+			payment_token: AssetId,
 			amount: MultiBalanceOf<T>,
-			total_usdt: MultiBalanceOf<T>,
-			inviter: &Option<T::AccountId>,
-		) {
-			Self::update_user_unreleased_assets_info(&who, &ico, amount, total_usdt, true, None, &inviter);
-			Self::update_user_unreleased_assets_info(&ico.initiator, ico, amount, total_usdt, true, None, &inviter);
+		) -> result::Result<(MultiBalanceOf<T>, MultiBalanceOf<T>), DispatchError> {
+			let index = ico.index.unwrap();
+			let info = Self::payment_tokens_of(ico.currency_id, index)
+				.into_iter()
+				.find(|h| h.currency_id == payment_token)
+				.ok_or(Error::<T>::PaymentTokenNotAccepted)?;
+			let raised = Self::payment_raised_of((ico.currency_id, index), payment_token);
+			ensure!(
+				raised.raised.saturating_add(amount) <= info.cap,
+				Error::<T>::PaymentTokenCapReached
+			);
+
+			let decimals = T::CurrenciesHandler::get_metadata(payment_token)?.decimals;
+			let usdt = Self::exchange_token_convert_usdt(payment_token, decimals, amount)?;
+
+			let price = Self::get_token_price(payment_token);
+			let exchange_token_price = Self::get_token_price(ico.exchange_token);
+			ensure!(
+				price != MultiBalanceOf::<T>::from(0u32) && exchange_token_price != MultiBalanceOf::<T>::from(0u32),
+				Error::<T>::PriceNotExists
+			);
+			let exchange_token_decimals = T::CurrenciesHandler::get_metadata(ico.exchange_token)?.decimals;
+			let exchange_token_amount = Self::u256_convert_to_balance(
+				Self::balance_convert_to_u256(amount)
+					* Self::balance_convert_to_u256(price)
+					* U256::from(10u128.saturating_pow(exchange_token_decimals as u32))
+					/ (U256::from(10u128.saturating_pow(decimals as u32))
+						* Self::balance_convert_to_u256(exchange_token_price)),
+			);
+			Ok((usdt, exchange_token_amount))
 		}
 
 		#[transactional]
-		fn swap(
+		fn swap_payment_token(
 			who: &T::AccountId,
+			payment_token: AssetId,
 			amount: MultiBalanceOf<T>,
+			exchange_token_amount: MultiBalanceOf<T>,
 			ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
 		) -> result::Result<MultiBalanceOf<T>, DispatchError> {
 			let initiator = &ico.initiator;
-			let this_time_project_token_amount = Self::get_swap_token_amount(true, amount, &ico);
+			let index = ico.index.unwrap();
+			let this_time_project_token_amount = Self::get_swap_token_amount(true, exchange_token_amount, &ico);
 
-			T::MultiCurrency::transfer(ico.exchange_token, &who, &initiator, amount)?;
-			T::MultiCurrency::reserve(ico.exchange_token, &initiator, amount)?;
+			T::MultiCurrency::transfer(payment_token, &who, &initiator, amount)?;
+			T::MultiCurrency::reserve(payment_token, &initiator, amount)?;
 
 			T::MultiCurrency::repatriate_reserved(
 				ico.currency_id,
@@ -1533,379 +4670,534 @@ pub mod pallet {
 				this_time_project_token_amount,
 				BalanceStatus::Reserved,
 			)?;
+
+			PaymentRaisedOf::<T>::mutate((ico.currency_id, index), payment_token, |h| {
+				h.raised = h.raised.saturating_add(amount);
+				h.equivalent = h.equivalent.saturating_add(exchange_token_amount);
+			});
+			UserPaymentsOf::<T>::mutate(who, (ico.currency_id, index), |h| {
+				match h.iter_mut().find(|x| x.0 == payment_token) {
+					Some(x) => {
+						x.1 = x.1.saturating_add(amount);
+						x.2 = x.2.saturating_add(exchange_token_amount);
+					}
+					None => h.push((payment_token, amount, exchange_token_amount)),
+				}
+			});
 			Ok(this_time_project_token_amount)
 		}
 
-		fn get_swap_token_amount(
-			is_main: bool,
-			amount: MultiBalanceOf<T>,
+		/// The exchange tokens raised by the ico, with the other payment tokens in the exchange
+		/// token.
+		fn get_raised_amount(
 			ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
 		) -> MultiBalanceOf<T> {
-			let total_project_token_amount = ico.total_ico_amount;
-			let total_exchange_token_amount = ico.exchange_token_total_amount;
-			let mut result: U256;
-			if is_main {
-				result = Self::balance_convert_to_u256(amount)
-					* Self::balance_convert_to_u256(total_project_token_amount)
-					/ Self::balance_convert_to_u256(total_exchange_token_amount)
-			} else {
-				result = Self::balance_convert_to_u256(amount)
-					* Self::balance_convert_to_u256(total_exchange_token_amount)
-					/ Self::balance_convert_to_u256(total_project_token_amount)
-			}
-			Self::u256_convert_to_balance(result)
+			let index = ico.index.unwrap();
+			Self::payment_tokens_of(ico.currency_id, index).iter().fold(
+				Self::get_total_and_released_amount(ico.currency_id, index, &ico.initiator).0,
+				|acc, h| {
+					acc.saturating_add(Self::payment_raised_of((ico.currency_id, index), h.currency_id).equivalent)
+				},
+			)
 		}
 
-		fn update_user_unreleased_assets_info(
-			who: &T::AccountId,
+		/// Give back `exchange_token_amount` of the user in the tokens that the user paid, returns
+		/// the part in the exchange token.
+		fn give_back_payment_tokens(
+			user: &T::AccountId,
 			ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
-			amount: MultiBalanceOf<T>,
-			total_usdt: MultiBalanceOf<T>,
-			is_join: bool,
-			is_terminated: Option<bool>,
-			inviter: &Option<T::AccountId>,
-		) {
-			let is_initiator = who == &ico.initiator;
-			let mut unreleased_currency_id = ico.currency_id;
-			let mut total = amount;
-			let mut user = who;
-
+			exchange_token_amount: MultiBalanceOf<T>,
+		) -> result::Result<MultiBalanceOf<T>, DispatchError> {
 			let index = ico.index.unwrap();
-			let main_token_amount = amount;
+			let payments = UserPaymentsOf::<T>::get(user, (ico.currency_id, index));
+			let total = match Self::get_unrelease_asset_info(user, ico.currency_id, index) {
+				Some(info) => info
+					.tags
+					.iter()
+					.fold(MultiBalanceOf::<T>::from(0u32), |acc, h| acc.saturating_add(h.2)),
+				None => MultiBalanceOf::<T>::from(0u32),
+			};
+			if payments.is_empty() || total == MultiBalanceOf::<T>::from(0u32) {
+				return Ok(exchange_token_amount);
+			}
 
-			if is_initiator {
-				user = &ico.initiator;
-				unreleased_currency_id = ico.exchange_token;
-			} else {
-				if is_join {
-					total = Self::get_swap_token_amount(true, amount, &ico);
-				}
+			let mut result = exchange_token_amount;
+			for (payment_token, paid, equivalent) in payments {
+				let back = Self::u256_convert_to_balance(
+					Self::balance_convert_to_u256(paid) * Self::balance_convert_to_u256(exchange_token_amount)
+						/ Self::balance_convert_to_u256(total),
+				);
+				let equivalent_back = Self::u256_convert_to_balance(
+					Self::balance_convert_to_u256(equivalent) * Self::balance_convert_to_u256(exchange_token_amount)
+						/ Self::balance_convert_to_u256(total),
+				);
+				T::MultiCurrency::repatriate_reserved(payment_token, &ico.initiator, user, back, BalanceStatus::Free)?;
+				PaymentRaisedOf::<T>::mutate((ico.currency_id, index), payment_token, |h| {
+					h.refund = h.refund.saturating_add(back);
+				});
+				result = result.saturating_sub(equivalent_back);
 			}
+			Ok(result)
+		}
 
-			let mut assets_info = <UnReleaseAssets<T>>::get(&user);
-			let mut new_info: UnRelease<MultiBalanceOf<T>, AssetId, T::AccountId>;
-			if let Some(pos) = assets_info
-				.iter()
-				.position(|h| h.currency_id == ico.currency_id && h.index == index)
-			{
-				new_info = assets_info.swap_remove(pos);
+		/// The milestones must release at most all the funds, and the deadlines must increase.
+		fn check_milestones(info: &MilestoneInfo<T::BlockNumber>) -> DispatchResult {
+			ensure!(
+				!info.milestones.is_empty() && info.threshold > Percent::from_percent(0u8),
+				Error::<T>::InvalidMilestones
+			);
+			let mut total_percent = 0u8;
+			let mut last_deadline = Self::now();
+			for milestone in info.milestones.iter() {
+				total_percent = total_percent.saturating_add(milestone.percent.deconstruct());
+				ensure!(
+					milestone.percent > Percent::from_percent(0u8)
+						&& total_percent <= 100 && milestone.deadline > last_deadline,
+					Error::<T>::InvalidMilestones
+				);
+				last_deadline = milestone.deadline;
+			}
+			Ok(())
+		}
 
-				if is_join {
-					if who != &ico.initiator {
-						new_info.tags.push((
-							total_usdt,
-							ico.total_usdt.saturating_add(total_usdt),
-							main_token_amount,
-							total,
-						));
-					} else {
-						new_info.tags = vec![];
-					}
-					new_info.total_usdt = new_info.total_usdt.saturating_add(total_usdt);
-					new_info.total = new_info.total.saturating_add(total);
-					if new_info.inviter.is_none() {
-						new_info.inviter = inviter.clone();
-					}
-				} else {
-					if is_terminated.is_some() {
-						new_info.refund = total;
-					} else {
-						new_info.released = new_info.released.saturating_add(total);
-					}
-				}
-			} else {
-				if who != &ico.initiator {
-					new_info = UnRelease {
-						currency_id: ico.currency_id,
-						index: index,
-						inviter: inviter.clone(),
-						tags: vec![(
-							total_usdt,
-							ico.total_usdt.saturating_add(total_usdt),
-							main_token_amount,
-							total,
-						)],
-						total_usdt: total_usdt,
-						unreleased_currency_id: unreleased_currency_id,
-						total: total,
-						refund: MultiBalanceOf::<T>::from(0u32),
-						released: MultiBalanceOf::<T>::from(0u32),
-						reward: None,
-					};
-				} else {
-					new_info = UnRelease {
-						currency_id: ico.currency_id.clone(),
-						index: index,
-						inviter: inviter.clone(),
-						tags: vec![],
-						total_usdt: total_usdt,
-						unreleased_currency_id: unreleased_currency_id,
-						total: total,
-						refund: MultiBalanceOf::<T>::from(0u32),
-						released: MultiBalanceOf::<T>::from(0u32),
-						reward: None,
-					};
+		/// The milestone that has not been released, and the release proportion after it.
+		fn get_next_milestone(
+			info: &MilestoneInfo<T::BlockNumber>,
+			already_released_proportion: Percent,
+		) -> Option<(Milestone<T::BlockNumber>, Percent)> {
+			let mut release_proportion = Percent::from_percent(0u8);
+			for milestone in info.milestones.iter() {
+				release_proportion = release_proportion.saturating_add(milestone.percent);
+				if release_proportion > already_released_proportion {
+					return Some((milestone.clone(), release_proportion));
 				}
+			}
+			None
+		}
+
+		/// Whether the initiator did not get the next milestone released before its deadline.
+		pub fn is_milestone_missed(
+			ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
+		) -> bool {
+			let info = match Self::milestones_of(ico.currency_id, ico.index.unwrap_or_default()) {
+				Some(x) => x,
+				None => return false,
 			};
-			assets_info.push(new_info);
-			UnReleaseAssets::<T>::insert(user, assets_info);
+			match Self::get_next_milestone(&info, ico.already_released_proportion) {
+				Some((milestone, _)) => milestone.deadline < Self::now(),
+				None => false,
+			}
 		}
 
-		fn join_simple_check(
+		/// Unreserve the locked tokens, and lock them with a vesting schedule instead. The tokens
+		/// are vested on the account of `holder`, the holder of the receipt of the participation.
+		fn add_vesting_schedule(
+			user: &T::AccountId,
+			holder: &T::AccountId,
 			ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
+			vesting: VestingParameters<T::BlockNumber>,
 			amount: MultiBalanceOf<T>,
-			currency_id: AssetId,
-			index: u32,
-			who: &T::AccountId,
-			total_usdt: MultiBalanceOf<T>,
-		) -> result::Result<bool, DispatchError> {
-			ensure!(
-				amount != 0u128.saturated_into::<MultiBalanceOf<T>>(),
-				Error::<T>::AmountIsZero
-			);
+		) -> DispatchResult {
+			if user == holder {
+				T::MultiCurrency::unreserve(ico.currency_id, user, amount);
+			} else {
+				T::MultiCurrency::repatriate_reserved(ico.currency_id, user, holder, amount, BalanceStatus::Free)?;
+			}
 
-			match ico.start_time {
-				Some(time) => {
-					#[cfg(test)]
-					println!("time: {:?}, now:{:?}", time, Self::now());
-					ensure!(time <= Self::now(), Error::<T>::IsNotStartIcoTime);
-					if cfg!(any(feature = "std", feature = "runtime-benchmarks", test)) == false {
-						ensure!(ico.ico_duration + time >= Self::now(), Error::<T>::IcoExpire);
-					}
+			let period_count = MultiBalanceOf::<T>::from(vesting.period_count);
+			let per_period = amount / period_count;
+			let remainder = amount % period_count;
+			let start = Self::now().saturating_add(vesting.cliff);
+			VestingSchedules::<T>::mutate(holder, ico.currency_id, |h| {
+				if per_period != MultiBalanceOf::<T>::from(0u32) {
+					h.push(VestingSchedule {
+						index: ico.index.unwrap(),
+						start,
+						period: vesting.period,
+						period_count: vesting.period_count,
+						per_period,
+						unlock_amount: MultiBalanceOf::<T>::from(0u32),
+					});
 				}
+				// The remainder vests with the final period.
+				if remainder != MultiBalanceOf::<T>::from(0u32) {
+					h.push(VestingSchedule {
+						index: ico.index.unwrap(),
+						start: start.saturating_add(
+							vesting
+								.period
+								.saturating_mul(T::BlockNumber::from(vesting.period_count - 1)),
+						),
+						period: vesting.period,
+						period_count: 1,
+						per_period: remainder,
+						unlock_amount: MultiBalanceOf::<T>::from(0u32),
+					});
+				}
+			});
+			Self::update_vesting_lock(holder, ico.currency_id);
+			Ok(())
+		}
 
-				None => Err(Error::<T>::StartTimeNotExists)?,
+		/// Lock the tokens that have not vested of all the icoes of `currency_id`.
+		fn update_vesting_lock(user: &T::AccountId, currency_id: AssetId) {
+			let now = Self::now();
+			let locked = VestingSchedules::<T>::get(user, currency_id)
+				.iter()
+				.fold(MultiBalanceOf::<T>::from(0u32), |acc, h| {
+					acc.saturating_add(h.locked_amount(now))
+				});
+			if locked == MultiBalanceOf::<T>::from(0u32) {
+				let _ = T::MultiCurrency::remove_lock(ICO_ID, currency_id, user);
+				VestingSchedules::<T>::remove(user, currency_id);
+			} else {
+				let _ = T::MultiCurrency::set_lock(ICO_ID, currency_id, user, locked);
 			}
-			ensure!(!ico.is_terminated, Error::<T>::IcoTerminated);
-			ensure!(&ico.initiator != who, Error::<T>::InitiatorIsYourself);
-			ensure!(
-				!Self::is_exclude_area(&who, &currency_id, index)?,
-				Error::<T>::InExcludeArea
-			);
+		}
 
-			let mut total_usdt = total_usdt;
-			let unreleased_info_opt = Self::get_unrelease_asset_info(&who, currency_id, index);
-			match unreleased_info_opt.as_ref() {
-				None => {}
-				Some(x) => {
-					total_usdt = total_usdt.checked_add(&x.total_usdt).ok_or(Error::<T>::Overflow)?;
-				}
+		/// The amount that vested since the last unlock.
+		fn unlock_vesting(who: &T::AccountId, currency_id: AssetId, index: u32, is_do: bool) -> MultiBalanceOf<T> {
+			let now = Self::now();
+			let mut total = MultiBalanceOf::<T>::from(0u32);
+			let mut schedules = VestingSchedules::<T>::get(who, currency_id);
+			for schedule in schedules.iter_mut().filter(|h| h.index == index) {
+				let vested = schedule.total_amount().saturating_sub(schedule.locked_amount(now));
+				total = total.saturating_add(vested.saturating_sub(schedule.unlock_amount));
+				schedule.unlock_amount = vested;
 			}
-			runtime_print!(
-				"total_usdt: {:?}, user_min_amount: {:?}, user_max_amount: {:?}",
-				total_usdt,
-				ico.user_min_amount,
-				ico.user_max_amount
-			);
-			ensure!(
-				total_usdt >= ico.user_min_amount && total_usdt <= ico.user_max_amount,
-				Error::<T>::AmountNotMeetProjectRequirement
-			);
-			ensure!(
-				total_usdt >= IcoMinUsdtAmount::<T>::get() && total_usdt <= IcoMaxUsdtAmount::<T>::get(),
-				Error::<T>::AmountNotMeetSystemRequirement
-			);
+			if is_do && total != MultiBalanceOf::<T>::from(0u32) {
+				schedules.retain(|h| h.unlock_amount != h.total_amount());
+				VestingSchedules::<T>::insert(who, currency_id, schedules);
+				Self::update_vesting_lock(who, currency_id);
+			}
+			total
+		}
 
-			if let Some(info) = unreleased_info_opt {
+		fn check_bound(min: MultiBalanceOf<T>, max: MultiBalanceOf<T>) -> DispatchResult {
+			ensure!(min >= IcoMinUsdtAmount::<T>::get(), Error::<T>::MinAmountTooLow);
+			ensure!(max != MultiBalanceOf::<T>::from(0u32), Error::<T>::MaxAmountIsZero);
+			ensure!(min <= max, Error::<T>::MaxLessThanMin);
+			ensure!(max <= IcoMaxUsdtAmount::<T>::get(), Error::<T>::MaxAmountTooLarge);
+			Ok(())
+		}
+
+		/// Each period of the vesting unlocks some of the tokens locked from the ico.
+		fn check_vesting_amount(
+			vesting: &Option<VestingParameters<T::BlockNumber>>,
+			lock_proportion: Percent,
+			total_ico_amount: MultiBalanceOf<T>,
+		) -> DispatchResult {
+			if let Some(vesting) = vesting.as_ref() {
 				ensure!(
-					info.tags.len().saturating_add(1) <= ico.user_ico_max_times.into(),
-					Error::<T>::IcoTimesToMax
+					MultiBalanceOf::<T>::from(vesting.period_count) <= lock_proportion * total_ico_amount,
+					Error::<T>::InvalidVesting
 				);
 			}
+			Ok(())
+		}
 
-			let initiator_total_amount = Self::get_total_and_released_amount(ico.currency_id, index, &ico.initiator).0;
-
-			ensure!(
-				ico.exchange_token_total_amount.saturating_sub(initiator_total_amount) >= amount,
-				Error::<T>::ProjectIcoAmountToMax
-			);
-			Ok(true)
+		fn check_lock(
+			lock_proportion: Percent,
+			unlock_duration: T::BlockNumber,
+			per_duration_unlock_amount: MultiBalanceOf<T>,
+			vesting: &Option<VestingParameters<T::BlockNumber>>,
+		) -> DispatchResult {
+			if let Some(vesting) = vesting.as_ref() {
+				ensure!(
+					vesting.period > T::BlockNumber::from(0u32) && vesting.period_count > 0,
+					Error::<T>::InvalidVesting
+				);
+			} else if lock_proportion > Percent::from_percent(0u8) {
+				ensure!(unlock_duration > T::BlockNumber::from(0u32), Error::<T>::DurationIsZero);
+				if cfg!(any(feature = "std", feature = "runtime-benchmarks", test)) == false {
+					ensure!(
+						per_duration_unlock_amount > 0u128.saturated_into::<MultiBalanceOf<T>>(),
+						Error::<T>::UnlockAmountIsZero
+					);
+				}
+			}
+			Ok(())
 		}
 
-		fn get_unrelease_asset_info(
-			who: &T::AccountId,
+		/// The members of an ico only act on their own ico, returns their approved amount and the
+		/// total amount.
+		fn ico_members_of(
+			origin: &OriginFor<T>,
 			currency_id: AssetId,
 			index: u32,
-		) -> Option<UnRelease<MultiBalanceOf<T>, AssetId, T::AccountId>> {
-			let mut unrelease_info_vec = UnReleaseAssets::<T>::get(&who);
-			if let Some(pos) = unrelease_info_vec
-				.iter()
-				.position(|h| h.currency_id == currency_id && h.index == index)
-			{
-				return Some(unrelease_info_vec.swap_remove(pos));
+		) -> result::Result<Option<(MultiBalanceOf<T>, MultiBalanceOf<T>)>, DispatchError> {
+			match T::IcoMembersOrigin::try_origin(origin.clone()) {
+				Ok((members_currency_id, members_index, yes, total)) => {
+					ensure!(
+						members_currency_id == currency_id && members_index == index,
+						Error::<T>::BadOrigin
+					);
+					Ok(Some((yes, total)))
+				}
+				Err(_) => Ok(None),
 			}
-			None
 		}
 
-		fn update_initiate_icoes(currency_id: AssetId, index: u32, who: &T::AccountId, status: IcoStatus) {
-			let mut icoes = InitiatedIcoesOf::<T>::get(&who);
-			if let Some(pos) = icoes
-				.iter()
-				.position(|h| h.currency_id == currency_id && h.index == index)
-			{
-				let mut ico = icoes.swap_remove(pos);
-				ico.status = status;
-				icoes.push(ico);
-				InitiatedIcoesOf::<T>::insert(who, icoes);
+		/// Remove the settings of the ico besides `IcoInfo`.
+		fn remove_ico_settings(currency_id: AssetId, index: u32) {
+			SoftCapOf::<T>::remove(currency_id, index);
+			IcoModeOf::<T>::remove(currency_id, index);
+			DutchAuctionOf::<T>::remove(currency_id, index);
+			AllowlistRootOf::<T>::remove(currency_id, index);
+			AreaCapsOf::<T>::remove(currency_id, index);
+			KycLevelCapsOf::<T>::remove(currency_id, index);
+			VestingOf::<T>::remove(currency_id, index);
+			MilestonesOf::<T>::remove(currency_id, index);
+			PaymentTokensOf::<T>::remove(currency_id, index);
+			LiquidityOf::<T>::remove(currency_id, index);
+			LotteryOf::<T>::remove(currency_id, index);
+			IsReceiptIco::<T>::remove(currency_id, index);
+		}
+
+		/// Mint the nft receipt of the participation of the user, or update the unreleased
+		/// amount it carries.
+		fn update_receipt(who: &T::AccountId, currency_id: AssetId, index: u32) -> DispatchResult {
+			if !Self::is_receipt_ico(currency_id, index) {
+				return Ok(());
+			}
+			let ico = <Ico<T>>::get(currency_id, index).ok_or(Error::<T>::IcoNotExists123)?;
+			if who == &ico.initiator {
+				return Ok(());
+			}
+			let unreleased = Self::get_unrelease_asset_info(who, currency_id, index)
+				.map(|h| h.total.saturating_sub(h.released).saturating_sub(h.refund))
+				.unwrap_or_default();
+			match Self::receipt_of((currency_id, index), who) {
+				Some(receipt) => T::ReceiptHandler::update_receipt(&receipt, unreleased),
+				None => {
+					let receipt = T::ReceiptHandler::mint_receipt(&ico.initiator, who, currency_id, index, unreleased)?;
+					ReceiptOf::<T>::insert((currency_id, index), who, &receipt);
+					ReceiptPositionOf::<T>::insert(&receipt, (currency_id, index, who.clone()));
+					Self::deposit_event(Event::MintIcoReceipt(who.clone(), currency_id, index, receipt));
+					Ok(())
+				}
 			}
 		}
 
-		fn now() -> T::BlockNumber {
-			<system::Pallet<T>>::block_number()
+		/// The participation with a receipt is controlled by the owner of the receipt.
+		fn ensure_receipt_owner(who: &T::AccountId, currency_id: AssetId, index: u32) -> DispatchResult {
+			if let Some(receipt) = Self::receipt_of((currency_id, index), who) {
+				ensure!(
+					T::ReceiptHandler::owner_of(&receipt).as_ref() == Some(who),
+					Error::<T>::NotReceiptOwner
+				);
+			}
+			Ok(())
 		}
 
-		fn is_exclude_area(
-			who: &T::AccountId,
-			currency_id: &AssetId,
+		/// The participant of the ico whose participation `holder` controls, it is the participant
+		/// of the receipt if `receipt` is given.
+		fn participant_of(
+			holder: &T::AccountId,
+			currency_id: AssetId,
 			index: u32,
-		) -> result::Result<bool, DispatchError> {
-			let ico = <Ico<T>>::get(currency_id, index).ok_or(Error::<T>::IcoNotExists123)?;
-			if &ico.is_must_kyc == &true {
-				if Self::is_already_kyc(&who) {
-					let nations = ico.exclude_area;
-					if !nations.is_empty() {
-						let nation_opt = Self::get_uesr_area(&who);
-						match nation_opt {
-							None => {
-								return Err(Error::<T>::UserAreaNotExists)?;
-							}
-							Some(nation) => {
-								if let Some(pos) = nations.iter().position(|h| *h == nation) {
-									return Ok(true);
-								}
-							}
-						};
-					}
-				} else {
-					Err(Error::<T>::NotKycUser)?;
+			receipt: Option<ReceiptIdOf<T>>,
+		) -> result::Result<T::AccountId, DispatchError> {
+			match receipt {
+				None => {
+					Self::ensure_receipt_owner(holder, currency_id, index)?;
+					Ok(holder.clone())
+				}
+				Some(receipt) => {
+					let (receipt_currency_id, receipt_index, participant) =
+						Self::receipt_position_of(&receipt).ok_or(Error::<T>::ReceiptNotExists)?;
+					ensure!(
+						receipt_currency_id == currency_id && receipt_index == index,
+						Error::<T>::ReceiptNotMatchIco
+					);
+					ensure!(
+						T::ReceiptHandler::owner_of(&receipt).as_ref() == Some(holder),
+						Error::<T>::NotReceiptOwner
+					);
+					Ok(participant)
 				}
 			}
-
-			Ok(false)
 		}
 
-		fn exchange_token_convert_usdt(
+		/// The tokens that the participant gets from `f` are given to the holder of the receipt.
+		fn with_receipt_holder<R>(
+			participant: &T::AccountId,
+			holder: &T::AccountId,
 			currency_id: AssetId,
-			decimals: u8,
-			amount: MultiBalanceOf<T>,
-		) -> result::Result<MultiBalanceOf<T>, DispatchError> {
-			let price = Self::get_token_price(currency_id);
-			ensure!(price != MultiBalanceOf::<T>::from(0u32), Error::<T>::PriceNotExists);
-			let decimals_amount = 10u128
-				.saturating_pow(decimals as u32)
-				.saturated_into::<MultiBalanceOf<T>>();
-			let mut usdt = Self::u256_convert_to_balance(
-				Self::balance_convert_to_u256(price) * Self::balance_convert_to_u256(amount)
-					/ Self::balance_convert_to_u256(decimals_amount),
+			index: u32,
+			f: impl FnOnce() -> result::Result<R, DispatchError>,
+		) -> result::Result<R, DispatchError> {
+			if participant == holder {
+				return f();
+			}
+			let ico = <Ico<T>>::get(currency_id, index).ok_or(Error::<T>::IcoNotExists123)?;
+			let mut currencies = vec![ico.currency_id, ico.exchange_token, T::GetNativeCurrencyId::get()];
+			currencies.extend(
+				Self::payment_tokens_of(currency_id, index)
+					.iter()
+					.map(|h| h.currency_id),
 			);
+			currencies.sort();
+			currencies.dedup();
+			let before: Vec<_> = currencies
+				.iter()
+				.map(|id| T::MultiCurrency::free_balance(*id, participant))
+				.collect();
 
-			let multiple = PowerMultipleOf::<T>::get(currency_id);
-
-			usdt = (usdt * multiple.up.saturated_into::<MultiBalanceOf<T>>())
-				.checked_div(&multiple.down.saturated_into::<MultiBalanceOf<T>>())
-				.ok_or(Error::<T>::DivByZero)?;
-
-			Ok(usdt)
-		}
+			let result = f()?;
 
-		pub fn get_token_price(currency_id: AssetId) -> MultiBalanceOf<T> {
-			if currency_id == T::USDCurrencyId::get() {
-				return USD.saturated_into::<MultiBalanceOf<T>>();
-			}
-			match T::PriceData::get_price(currency_id, T::USDCurrencyId::get()) {
-				Some(x) => {
-					runtime_print!(
-						" ---------------the token {:?}, price is {:?} ------------------",
-						currency_id,
-						x
-					);
-					return x.saturated_into::<MultiBalanceOf<T>>();
+			for (id, before) in currencies.into_iter().zip(before) {
+				let gained = T::MultiCurrency::free_balance(id, participant).saturating_sub(before);
+				if gained > MultiBalanceOf::<T>::from(0u32) {
+					T::MultiCurrency::transfer(id, participant, holder, gained)?;
 				}
-				None => return MultiBalanceOf::<T>::from(0u32),
 			}
+			Ok(result)
 		}
 
-		fn get_uesr_area(who: &T::AccountId) -> Option<AreaCode> {
-			T::KycHandler::get_user_area(who)
-		}
-
-		fn unlock_asset(
+		/// The tickets of the user are the power over `ticket_power`, multiplied by the tier of
+		/// the user.
+		pub fn lottery_tickets_of(
 			who: &T::AccountId,
-			currency_id: &AssetId,
-			index: u32,
-			is_do: bool,
-			locks: &mut [IcoLock<MultiBalanceOf<T>, T::BlockNumber>],
-		) -> (MultiBalanceOf<T>, Vec<IcoLock<MultiBalanceOf<T>, T::BlockNumber>>) {
-			let mut total = <MultiBalanceOf<T>>::from(0u32);
-			for i in 0..locks.len() {
-				if locks[i].index != index {
-					continue;
-				}
+			lottery: &LotteryInfo<T::BlockNumber, MultiBalanceOf<T>, T::Hash>,
+		) -> u32 {
+			let tickets = Self::usable_power_of(who)
+				.checked_div(&lottery.ticket_power)
+				.unwrap_or_default()
+				.saturating_mul(MultiBalanceOf::<T>::from(T::TierHandler::get_user_max_amount_percent(
+					who,
+				))) / MultiBalanceOf::<T>::from(100u32);
+			tickets.saturated_into::<u32>().min(lottery.max_tickets_per_user)
+		}
 
-				let time = Self::now().saturating_sub(locks[i].start_block);
-				if locks[i].unlock_duration == T::BlockNumber::from(0u32) {
-					let unlock_amount = locks[i].total_amount.saturating_sub(locks[i].unlock_amount);
-					if is_do {
-						T::MultiCurrency::unreserve(*currency_id, &who, unlock_amount);
+		/// Draws the winners one by one without replacement, each with the chance of its
+		/// tickets over the tickets that remain.
+		fn draw_lottery_winners(
+			mut entrants: Vec<(T::AccountId, u32)>,
+			total_tickets: u32,
+			winner_count: u32,
+			random_seed: T::Hash,
+		) -> Vec<T::AccountId> {
+			let mut winners = Vec::new();
+			let mut remain_tickets = total_tickets;
+			let mut nonce = 0u32;
+			while (winners.len() as u32) < winner_count && remain_tickets > 0 {
+				let random = T::Hashing::hash_of(&(random_seed, nonce));
+				nonce = nonce.saturating_add(1);
+				let mut ticket = <u32>::decode(&mut random.as_ref()).unwrap_or_default() % remain_tickets;
+				let pos = entrants.iter().position(|h| {
+					if ticket < h.1 {
+						true
+					} else {
+						ticket -= h.1;
+						false
 					}
-					total += unlock_amount;
-				} else {
-					let num = time / locks[i].unlock_duration;
-					let total_unlock_amount = (num
-						.saturated_into::<u32>()
-						.saturated_into::<MultiBalanceOf<T>>()
-						.saturating_mul(locks[i].per_duration_unlock_amount))
-					.min(locks[i].total_amount);
-					let this_time_unlock_amount = total_unlock_amount.saturating_sub(locks[i].unlock_amount);
-					if is_do {
-						locks[i].unlock_amount = total_unlock_amount;
-						T::MultiCurrency::unreserve(*currency_id, &who, this_time_unlock_amount);
+				});
+				match pos {
+					Some(pos) => {
+						let (winner, tickets) = entrants.remove(pos);
+						remain_tickets = remain_tickets.saturating_sub(tickets);
+						winners.push(winner);
 					}
-					total += this_time_unlock_amount;
+					None => break,
 				}
 			}
-			if is_do {
-				let mut locks = locks.to_vec();
-				locks.retain(|h| h.total_amount != h.unlock_amount);
-			}
+			winners
+		}
 
-			(total, locks.to_vec())
+		pub fn protection_fund_account() -> T::AccountId {
+			T::ProtectionFundId::get().into_account_truncating()
 		}
 
-		fn check_bound(min: MultiBalanceOf<T>, max: MultiBalanceOf<T>) -> DispatchResult {
-			ensure!(min >= IcoMinUsdtAmount::<T>::get(), Error::<T>::MinAmountTooLow);
-			ensure!(max != MultiBalanceOf::<T>::from(0u32), Error::<T>::MaxAmountIsZero);
-			ensure!(min <= max, Error::<T>::MaxLessThanMin);
-			ensure!(max <= IcoMaxUsdtAmount::<T>::get(), Error::<T>::MaxAmountTooLarge);
-			Ok(())
+		/// The raised funds are given to the initiator, except the share of the protection fund.
+		fn release_to_initiator(currency_id: AssetId, initiator: &T::AccountId, amount: MultiBalanceOf<T>) {
+			let fund_amount = T::ProtectionFundProportion::get() * amount;
+			let fund_amount = fund_amount.saturating_sub(
+				T::MultiCurrency::repatriate_reserved(
+					currency_id,
+					initiator,
+					&Self::protection_fund_account(),
+					fund_amount,
+					BalanceStatus::Free,
+				)
+				.unwrap_or(fund_amount),
+			);
+			T::MultiCurrency::unreserve(currency_id, initiator, amount.saturating_sub(fund_amount));
+		}
+
+		/// The unreleased amount of all the participants of the ico.
+		fn total_unreleased_amount(
+			ico: &IcoInfo<T::BlockNumber, MultiBalanceOf<T>, AssetId, AreaCode, T::AccountId>,
+		) -> MultiBalanceOf<T> {
+			let total = Self::total_allocation_of(ico.currency_id, ico.index.unwrap_or_default());
+			total.saturating_sub(ico.already_released_proportion * total)
 		}
 
 		fn remove_passed_icoes(currency_id: AssetId, index: u32) {
 			PassedIcoes::<T>::mutate(|h| h.retain(|i| !(i.0 == currency_id && i.1 == index)));
 		}
 
-		fn remove_request_release_info(currency_id: Option<AssetId>, index: u32, is_all: bool) {
-			let mut release_info = <RequestReleaseInfo<T>>::get();
-			if is_all {
-				release_info.retain(|h| {
-					if h.request_time + T::RequestExpire::get() > Self::now() {
-						true
-					} else {
-						T::OnSlash::on_unbalanced(T::NativeCurrency::slash_reserved(&h.who, h.pledge).0);
-						false
+		/// Put the expiry of the release request into the first block from `at` that is not
+		/// full, so that the expiries removed per block are bounded.
+		pub(crate) fn schedule_request_expiry(currency_id: AssetId, index: u32, at: T::BlockNumber) -> DispatchResult {
+			let mut at = at;
+			for _ in 0..T::MaxRequestExpiriesPerBlock::get() {
+				if RequestExpiryQueue::<T>::mutate(at, |h| h.try_push((currency_id, index)).is_ok()) {
+					return Ok(());
+				}
+				at = at.saturating_add(T::BlockNumber::from(1u32));
+			}
+			Err(Error::<T>::TooManyRequestExpiries)?
+		}
+
+		pub(crate) fn schedule_lottery_seed(currency_id: AssetId, index: u32, at: T::BlockNumber) -> DispatchResult {
+			let mut at = at;
+			for _ in 0..T::MaxLotteryDrawsPerBlock::get() {
+				if LotterySeedQueue::<T>::mutate(at, |h| h.try_push((currency_id, index)).is_ok()) {
+					return Ok(());
+				}
+				at = at.saturating_add(T::BlockNumber::from(1u32));
+			}
+			Err(Error::<T>::TooManyLotteryDraws)?
+		}
+
+		/// The random seed of the lottery is the randomness of the block the registration ends,
+		/// or of the next block that has it, so that no one chooses when the winners are drawn.
+		fn seed_lottery(currency_id: AssetId, index: u32, now: T::BlockNumber) {
+			if let Some(mut lottery) = Self::lottery_of(currency_id, index) {
+				if lottery.random_seed.is_some() {
+					return;
+				}
+				match Self::lottery_randomness(currency_id, index) {
+					Some(random_seed) => {
+						lottery.random_seed = Some(random_seed);
+						LotteryOf::<T>::insert(currency_id, index, lottery);
 					}
-				});
-			} else {
-				if let Some(currency_id) = currency_id {
-					if let Some(pos) = release_info
-						.iter()
-						.position(|h| h.currency_id == currency_id && h.index == index)
-					{
-						release_info.swap_remove(pos);
+					None => {
+						// When the next blocks are full, `draw_lottery` takes the seed instead.
+						if Self::schedule_lottery_seed(currency_id, index, now.saturating_add(1u32.into())).is_err() {
+							Self::deposit_event(Event::LotterySeedDeferred(currency_id, index));
+						}
 					}
 				}
 			}
-			<RequestReleaseInfo<T>>::put(release_info);
+		}
+
+		fn lottery_randomness(currency_id: AssetId, index: u32) -> Option<T::Hash> {
+			T::Randomness::random(&(b"dico/ico/lottery", currency_id, index).encode()[..]).0
+		}
+
+		/// The pledge of the request is slashed if the request is not permitted or canceled before
+		/// it expires.
+		fn expire_request(currency_id: AssetId, index: u32, now: T::BlockNumber) {
+			if let Some(release_info) = Self::release_info(currency_id, index) {
+				// The request was canceled and requested again.
+				if release_info.request_time.saturating_add(T::RequestExpire::get()) > now {
+					return;
+				}
+				T::OnSlash::on_unbalanced(T::NativeCurrency::slash_reserved(&release_info.who, release_info.pledge).0);
+				<RequestReleaseInfo<T>>::remove(currency_id, index);
+			}
 		}
 
 		fn initiate_ico_simple_check(
@@ -1933,23 +5225,85 @@ pub mod pallet {
 
 			Self::check_bound(info.user_min_amount, info.user_max_amount)?;
 
+			if let Some(soft_cap) = info.soft_cap {
+				ensure!(soft_cap > MultiBalanceOf::<T>::from(0u32), Error::<T>::SoftCapIsZero);
+			}
+
+			if let Some(liquidity) = info.liquidity.as_ref() {
+				// The exchange tokens of the other modes are not given to the initiator when the ico
+				// ends.
+				ensure!(
+					liquidity.proportion > Percent::from_percent(0u8) && !info.mode.is_settled_at_end(),
+					Error::<T>::InvalidLiquidity
+				);
+			}
+
+			if info.mode == IcoMode::Lottery {
+				let lottery = info.lottery.as_ref().ok_or(Error::<T>::InvalidLottery)?;
+				ensure!(
+					lottery.registration_duration > T::BlockNumber::from(0u32)
+						&& lottery.registration_duration < info.ico_duration
+						&& lottery.ticket_power > MultiBalanceOf::<T>::from(0u32)
+						&& lottery.max_tickets_per_user > 0
+						&& lottery.winner_count > 0,
+					Error::<T>::InvalidLottery
+				);
+			}
+
+			if info.mode == IcoMode::DutchAuction {
+				let auction = info.dutch_auction.as_ref().ok_or(Error::<T>::InvalidDutchAuction)?;
+				ensure!(
+					auction.start_exchange_token_total_amount >= info.exchange_token_total_amount,
+					Error::<T>::InvalidDutchAuction
+				);
+			}
+
 			ensure!(
 				info.ico_duration > T::BlockNumber::from(0u32),
 				Error::<T>::DurationIsZero
 			);
 
-			if info.lock_proportion > Percent::from_percent(0u8) {
+			if let Some(milestones) = info.milestones.as_ref() {
+				Self::check_milestones(milestones)?;
+			}
+
+			for (i, (area, _)) in info.area_caps.iter().enumerate() {
 				ensure!(
-					info.unlock_duration > T::BlockNumber::from(0u32),
-					Error::<T>::DurationIsZero
+					!info.exclude_area.contains(area) && !info.area_caps[..i].iter().any(|h| &h.0 == area),
+					Error::<T>::InvalidAreaCaps
 				);
-				if cfg!(any(feature = "std", feature = "runtime-benchmarks", test)) == false {
-					ensure!(
-						info.per_duration_unlock_amount > 0u128.saturated_into::<MultiBalanceOf<T>>(),
-						Error::<T>::UnlockAmountIsZero
-					);
-				}
 			}
+			for (i, (level, _)) in info.kyc_level_caps.iter().enumerate() {
+				ensure!(
+					!info.kyc_level_caps[..i].iter().any(|h| &h.0 == level),
+					Error::<T>::InvalidKycLevelCaps
+				);
+			}
+
+			if !info.payment_tokens.is_empty() {
+				// The allocation of the other modes is settled in the exchange token only.
+				ensure!(!info.mode.is_settled_at_end(), Error::<T>::InvalidPaymentTokens);
+			}
+			for (i, token) in info.payment_tokens.iter().enumerate() {
+				ensure!(
+					token.currency_id != info.currency_id
+						&& token.currency_id != info.exchange_token
+						&& token.cap > MultiBalanceOf::<T>::from(0u32)
+						&& !info.payment_tokens[..i]
+							.iter()
+							.any(|h| h.currency_id == token.currency_id),
+					Error::<T>::InvalidPaymentTokens
+				);
+				T::CurrenciesHandler::get_metadata(token.currency_id)?;
+			}
+
+			Self::check_lock(
+				info.lock_proportion,
+				info.unlock_duration,
+				info.per_duration_unlock_amount,
+				&info.vesting,
+			)?;
+			Self::check_vesting_amount(&info.vesting, info.lock_proportion, info.total_ico_amount)?;
 
 			ensure!(!Self::is_pending_ico(&info.currency_id), Error::<T>::IsPendingIco);
 			ensure!(
@@ -1991,20 +5345,6 @@ pub mod pallet {
 			)
 		}
 
-		fn get_request_release_info(
-			currency_id: AssetId,
-			index: u32,
-		) -> Option<Release<T::AccountId, T::BlockNumber, AssetId, BalanceOf<T>>> {
-			let mut release_info = <RequestReleaseInfo<T>>::get();
-			if let Some(pos) = release_info
-				.iter()
-				.position(|h| h.currency_id == currency_id && h.index == index)
-			{
-				return Some(release_info.swap_remove(pos));
-			}
-			None
-		}
-
 		pub fn balance_convert_to_u256(amount: MultiBalanceOf<T>) -> U256 {
 			amount.saturated_into::<u128>().saturating_add(1u128).into()
 		}
@@ -2019,7 +5359,7 @@ pub mod pallet {
 		/// The total amount that can release.
 		/// Provided for RPC use
 		pub fn can_release_amount(user: T::AccountId, currency_id: AssetId, index: u32) -> MultiBalanceOf<T> {
-			Self::do_release(user, currency_id, index, false).unwrap_or_default()
+			Self::do_release(user.clone(), &user, currency_id, index, false).unwrap_or_default()
 		}
 
 		/// The total amount that can unlock.
@@ -2032,7 +5372,7 @@ pub mod pallet {
 				false,
 				&mut <IcoLocks<T>>::get(&user, &currency_id),
 			);
-			total
+			total.saturating_add(Self::unlock_vesting(&user, currency_id, index, false))
 		}
 
 		/// The reward amount
@@ -2054,7 +5394,7 @@ pub mod pallet {
 			};
 			let exchange_token_id = ico.exchange_token;
 			let max_times = ico.user_ico_max_times;
-			let user_max_amount = ico.user_max_amount;
+			let user_max_amount = Self::get_user_max_amount(&user, ico.user_max_amount);
 			let mut user_min_amount = ico.user_min_amount.max(IcoMinUsdtAmount::<T>::get());
 
 			if user == ico.initiator {
@@ -2079,6 +5419,11 @@ pub mod pallet {
 				None => return (MultiBalanceOf::<T>::from(0u32), MultiBalanceOf::<T>::from(0u32)),
 			}
 
+			let user_max_amount = match Self::allowlist_cap_of((currency_id, index), &user) {
+				Some(max_allocation) => user_max_amount.min(max_allocation),
+				None => user_max_amount,
+			};
+
 			let user_remain_usdt = match Self::get_unrelease_asset_info(&user, currency_id, index) {
 				Some(x) => {
 					user_min_amount = user_min_amount.saturating_sub(x.total_usdt);
@@ -2121,7 +5466,22 @@ pub mod pallet {
 					Some(x) => ico.exchange_token_total_amount.saturating_sub(x.total),
 				};
 
-			let max_join_amount = project_remain_exchange_amount.min(user_remain_exchange_amount);
+			let max_join_amount = match Self::ico_mode_of(currency_id, index) {
+				IcoMode::FirstComeFirstServed | IcoMode::Lottery => {
+					project_remain_exchange_amount.min(user_remain_exchange_amount)
+				}
+				IcoMode::ProRata => user_remain_exchange_amount,
+				IcoMode::DutchAuction => match (Self::dutch_auction_of(currency_id, index), Self::get_priced_ico(&ico))
+				{
+					(Some(auction), Ok(priced_ico)) => Self::get_swap_token_amount(
+						false,
+						ico.total_ico_amount.saturating_sub(auction.sold_amount),
+						&priced_ico,
+					)
+					.min(user_remain_exchange_amount),
+					_ => return (MultiBalanceOf::<T>::from(0u32), MultiBalanceOf::<T>::from(0u32)),
+				},
+			};
 			runtime_print!(
 				"project can join exchange token amount is {:?}",
 				project_remain_exchange_amount
@@ -2234,6 +5594,7 @@ impl<T: Config> IcoHandler<AssetId, MultiBalanceOf<T>, T::AccountId, DispatchErr
 		<UnReleaseAssets<T>>::mutate(joiner, |h| h.push(info1));
 		<UnReleaseAssets<T>>::mutate(joiner1, |h| h.push(info3));
 		<UnReleaseAssets<T>>::mutate(initiator, |h| h.push(info2));
+		TotalAllocationOf::<T>::insert(currency_id, 1, (10000 * DOLLARS).saturated_into::<MultiBalanceOf<T>>());
 
 		Ok(())
 	}
@@ -2254,24 +5615,41 @@ impl<T: Config> IcoHandler<AssetId, MultiBalanceOf<T>, T::AccountId, DispatchErr
 	fn get_project_total_ico_amount(currency_id: AssetId, index: u32) -> Result<MultiBalanceOf<T>, DispatchError> {
 		runtime_print!("currency_id:{:?}, index: {:?}", currency_id, index);
 		let ico = Ico::<T>::get(currency_id, index).ok_or(Error::<T>::IcoNotExists123)?;
+		if let Some(auction) = Self::dutch_auction_of(currency_id, index) {
+			return Ok(auction.sold_amount);
+		}
 		let result = Self::balance_convert_to_u256(ico.total_ico_amount)
-			* Self::balance_convert_to_u256(Self::get_total_and_released_amount(currency_id, index, &ico.initiator).0)
+			* Self::balance_convert_to_u256(Self::get_raised_amount(&ico))
 			/ Self::balance_convert_to_u256(ico.exchange_token_total_amount);
-		let amount = Self::u256_convert_to_balance(result);
+		let amount = Self::u256_convert_to_balance(result).min(ico.total_ico_amount);
 		Ok(amount)
 	}
 }
 
 impl<T: Config> PowerHandler<T::AccountId, DispatchResult, MultiBalanceOf<T>> for Pallet<T> {
+	/// Use the power of the user first, then the power delegated to the user.
 	fn sub_user_power(user: &T::AccountId, amount: MultiBalanceOf<T>) -> DispatchResult {
-		let old = TotalPowerOf::<T>::get(user);
-		let new = old.checked_sub(&amount).ok_or(Error::<T>::Overflow)?;
-		TotalPowerOf::<T>::insert(user, new);
+		ensure!(Self::usable_power_of(user) >= amount, Error::<T>::PowerNotEnough);
+
+		let mut remain = amount;
+		let mut owners = Self::power_delegators_of(user);
+		if Self::power_delegate_of(user).is_none() {
+			owners.insert(0, user.clone());
+		}
+		for owner in owners.iter() {
+			if remain.is_zero() {
+				break;
+			}
+			let power = Self::checkpoint_power(owner);
+			let used = power.min(remain);
+			TotalPowerOf::<T>::insert(owner, power.saturating_sub(used));
+			remain = remain.saturating_sub(used);
+		}
 		Ok(())
 	}
 
 	fn add_user_power(user: &T::AccountId, amount: MultiBalanceOf<T>) -> DispatchResult {
-		let old = TotalPowerOf::<T>::get(user);
+		let old = Self::checkpoint_power(user);
 		let new = old.checked_add(&amount).ok_or(Error::<T>::Overflow)?;
 		TotalPowerOf::<T>::insert(user, new);
 
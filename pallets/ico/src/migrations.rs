@@ -0,0 +1,132 @@
+use super::*;
+use frame_support::{log, storage_alias, traits::StorageVersion};
+
+pub mod v1 {
+	use super::*;
+
+	#[storage_alias]
+	type PendingIco<T: Config> = StorageValue<
+		Pallet<T>,
+		Vec<
+			PendingInfo<
+				IcoInfo<
+					<T as frame_system::Config>::BlockNumber,
+					MultiBalanceOf<T>,
+					AssetId,
+					AreaCode,
+					<T as frame_system::Config>::AccountId,
+				>,
+				MultiBalanceOf<T>,
+			>,
+		>,
+	>;
+
+	#[storage_alias]
+	type RequestReleaseInfo<T: Config> = StorageValue<
+		Pallet<T>,
+		Vec<
+			Release<
+				<T as frame_system::Config>::AccountId,
+				<T as frame_system::Config>::BlockNumber,
+				AssetId,
+				BalanceOf<T>,
+			>,
+		>,
+	>;
+
+	/// Move the pending icoes and the release requests out of the vectors into maps, and
+	/// schedule the expiries of the release requests.
+	pub fn migrate<T: Config>() -> Weight {
+		if StorageVersion::get::<Pallet<T>>() >= 1 {
+			return Weight::zero();
+		}
+
+		log::info!("🚀 Ico module start migrate ✅",);
+
+		let mut reads_writes = 2u64;
+		for pending_info in PendingIco::<T>::take().unwrap_or_default() {
+			reads_writes = reads_writes.saturating_add(1);
+			crate::PendingIco::<T>::insert(pending_info.ico.currency_id, pending_info);
+		}
+
+		let now = frame_system::Pallet::<T>::block_number();
+		for release_info in RequestReleaseInfo::<T>::take().unwrap_or_default() {
+			reads_writes = reads_writes.saturating_add(2);
+			let expire_at = release_info
+				.request_time
+				.saturating_add(T::RequestExpire::get())
+				.max(now);
+			if Pallet::<T>::schedule_request_expiry(release_info.currency_id, release_info.index, expire_at).is_err() {
+				log::warn!(
+					"release request of {:?} can not be scheduled to expire",
+					release_info.currency_id
+				);
+			}
+			crate::RequestReleaseInfo::<T>::insert(release_info.currency_id, release_info.index, release_info);
+		}
+
+		StorageVersion::new(1).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(reads_writes, reads_writes.saturating_add(1))
+	}
+}
+
+pub mod v2 {
+	use super::*;
+
+	/// The power of the users gained before it decayed starts to decay from the upgrade, instead
+	/// of from the genesis.
+	pub fn migrate<T: Config>() -> Weight {
+		if StorageVersion::get::<Pallet<T>>() >= 2 {
+			return Weight::zero();
+		}
+
+		log::info!("🚀 Ico module start migrate power checkpoints ✅",);
+
+		let now = frame_system::Pallet::<T>::block_number();
+		let mut reads = 1u64;
+		let mut writes = 1u64;
+		for who in TotalPowerOf::<T>::iter_keys() {
+			reads = reads.saturating_add(2);
+			if !PowerCheckpointOf::<T>::contains_key(&who) {
+				writes = writes.saturating_add(1);
+				PowerCheckpointOf::<T>::insert(&who, now);
+			}
+		}
+
+		StorageVersion::new(2).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+}
+
+pub mod v3 {
+	use super::*;
+
+	/// Sum the project tokens of the participants of each ico into `TotalAllocationOf`.
+	pub fn migrate<T: Config>() -> Weight {
+		if StorageVersion::get::<Pallet<T>>() >= 3 {
+			return Weight::zero();
+		}
+
+		log::info!("🚀 Ico module start migrate total allocations ✅",);
+
+		let mut totals: BTreeMap<(AssetId, u32), MultiBalanceOf<T>> = BTreeMap::new();
+		let mut reads = 1u64;
+		for info in UnReleaseAssets::<T>::iter_values() {
+			reads = reads.saturating_add(1);
+			for h in info.into_iter().filter(|h| h.unreleased_currency_id == h.currency_id) {
+				let total = totals.entry((h.currency_id, h.index)).or_default();
+				*total = total.saturating_add(h.total);
+			}
+		}
+		let writes = (totals.len() as u64).saturating_add(1);
+		for ((currency_id, index), total) in totals {
+			TotalAllocationOf::<T>::insert(currency_id, index, total);
+		}
+
+		StorageVersion::new(3).put::<Pallet<T>>();
+
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+}
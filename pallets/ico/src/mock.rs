@@ -1,17 +1,17 @@
 #![cfg(test)]
 
 pub use super::*;
-use orml_traits::{DataFeeder, DataProvider};
+use orml_traits::{DataFeeder, DataProvider, DataProviderExtended};
 // use pallet_pricedao::
 use dico_treasury;
 use frame_support::{
 	construct_runtime, parameter_types,
-	traits::{Contains, LockIdentifier, Time},
+	traits::{Contains, LockIdentifier, Time, UnixTime},
 	PalletId,
 };
 use orml_traits::parameter_type_with_key;
-use pallet_oracle;
-use pallet_pricedao;
+use pallet_oracle::{self, TimestampedValue};
+use pallet_pricedao::{self, Moment};
 use pallet_randomness_collective_flip;
 use sp_core::H256;
 use sp_runtime::{
@@ -43,7 +43,10 @@ pub const KSM: CurrencyId = 20;
 pub const NewDAYS: u64 = 1000;
 pub const NEW_USDT: CurrencyId = 5;
 pub const kUSD: CurrencyId = 10;
+pub const KICO: CurrencyId = 30;
 pub const DAVE: AccountId = 3;
+/// The signed origin of the account is the origin of the ico members.
+pub const ICO_MEMBERS: AccountId = 100;
 
 construct_runtime!(
 	pub enum Test where
@@ -67,6 +70,10 @@ construct_runtime!(
 
 thread_local! {
 	static TIME: RefCell<u32> = RefCell::new(0);
+	static RECEIPTS: RefCell<Vec<(AccountId, Balance)>> = RefCell::new(vec![]);
+	static KYC: RefCell<Vec<(AccountId, AreaCode, KycLevel)>> = RefCell::new(vec![]);
+	static ICO_MEMBERS_VOTES: RefCell<(CurrencyId, u32, Balance, Balance)> = RefCell::new((KSM, 1, 1, 1));
+	static RANDOMNESS_AVAILABLE: RefCell<bool> = RefCell::new(true);
 }
 
 pub struct Timestamp;
@@ -84,6 +91,108 @@ impl Timestamp {
 	}
 }
 
+/// The receipts are indexed by their position, they carry the owner and the unreleased amount.
+pub struct MockReceipts;
+impl IcoReceiptHandler<AccountId, CurrencyId, Balance> for MockReceipts {
+	type ReceiptId = u32;
+
+	fn mint_receipt(
+		_: &AccountId,
+		owner: &AccountId,
+		_: CurrencyId,
+		_: u32,
+		unreleased: Balance,
+	) -> Result<u32, DispatchError> {
+		RECEIPTS.with(|v| {
+			v.borrow_mut().push((*owner, unreleased));
+			Ok(v.borrow().len() as u32 - 1)
+		})
+	}
+
+	fn update_receipt(receipt: &u32, unreleased: Balance) -> DispatchResult {
+		RECEIPTS.with(|v| v.borrow_mut()[*receipt as usize].1 = unreleased);
+		Ok(())
+	}
+
+	fn owner_of(receipt: &u32) -> Option<AccountId> {
+		RECEIPTS.with(|v| v.borrow().get(*receipt as usize).map(|h| h.0))
+	}
+}
+
+/// The users without kyc are unverified.
+pub struct MockKyc;
+impl KycHandler<AccountId, AreaCode> for MockKyc {
+	fn get_user_area(user: &AccountId) -> Option<AreaCode> {
+		KYC.with(|v| v.borrow().iter().find(|h| &h.0 == user).map(|h| h.1))
+	}
+
+	fn get_user_level(user: &AccountId) -> KycLevel {
+		KYC.with(|v| {
+			v.borrow()
+				.iter()
+				.find(|h| &h.0 == user)
+				.map(|h| h.2)
+				.unwrap_or(KycLevel::Unverified)
+		})
+	}
+}
+
+impl MockKyc {
+	pub fn set_kyc(who: AccountId, area: AreaCode, level: KycLevel) {
+		KYC.with(|v| v.borrow_mut().push((who, area, level)));
+	}
+}
+
+/// The ico members approve with the votes set by `set_votes`.
+pub struct MockIcoMembers;
+impl EnsureOrigin<Origin> for MockIcoMembers {
+	type Success = (CurrencyId, u32, Balance, Balance);
+
+	fn try_origin(o: Origin) -> Result<Self::Success, Origin> {
+		o.into().and_then(|o| match o {
+			frame_system::RawOrigin::Signed(ICO_MEMBERS) => Ok(ICO_MEMBERS_VOTES.with(|v| *v.borrow())),
+			r => Err(Origin::from(r)),
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> Origin {
+		Origin::signed(ICO_MEMBERS)
+	}
+}
+
+impl MockIcoMembers {
+	pub fn set_votes(currency_id: CurrencyId, index: u32, yes: Balance, total: Balance) {
+		ICO_MEMBERS_VOTES.with(|v| *v.borrow_mut() = (currency_id, index, yes, total));
+	}
+}
+
+/// The randomness of `RandomnessCollectiveFlip`, unless it is made unavailable by
+/// `set_available`.
+pub struct MockRandomness;
+impl Randomness<Option<H256>, u64> for MockRandomness {
+	fn random(subject: &[u8]) -> (Option<H256>, u64) {
+		let (random, block_number) = RandomnessCollectiveFlip::random(subject);
+		(RANDOMNESS_AVAILABLE.with(|v| *v.borrow()).then(|| random), block_number)
+	}
+}
+
+impl MockRandomness {
+	pub fn set_available(available: bool) {
+		RANDOMNESS_AVAILABLE.with(|v| *v.borrow_mut() = available);
+	}
+}
+
+impl MockReceipts {
+	pub fn transfer(receipt: u32, to: AccountId) {
+		RECEIPTS.with(|v| v.borrow_mut()[receipt as usize].0 = to);
+	}
+
+	pub fn unreleased_of(receipt: u32) -> Balance {
+		RECEIPTS.with(|v| v.borrow()[receipt as usize].1)
+	}
+}
+
 parameter_types! {
 	pub const DicoProposalBond: Balance = 100 * DOLLARS;
 	pub const DicoSpendPeriod: BlockNumber = 7 * DAYS;
@@ -132,6 +241,7 @@ impl pallet_amm::Config for Test {
 	type LiquidityAssetIdBase = LiquidityAssetIdBase;
 	type Currency = Currencies;
 	type PalletId = AMMPalletId;
+	type TwapPeriod = frame_support::traits::ConstU64<10>;
 	type WeightInfo = ();
 }
 
@@ -148,6 +258,7 @@ impl DataProvider<CurrencyId, Price> for MockDataProvider {
 			2 => Some(50000),
 			3 => Some(100),
 			4 => Some(u128::zero()),
+			KICO => Some(2 * USD),
 			_ => None,
 		}
 	}
@@ -159,9 +270,27 @@ impl DataFeeder<CurrencyId, Price, AccountId> for MockDataProvider {
 	}
 }
 
+impl DataProviderExtended<CurrencyId, TimestampedValue<Price, Moment>> for MockDataProvider {
+	fn get_no_op(currency_id: &CurrencyId) -> Option<TimestampedValue<Price, Moment>> {
+		Self::get(currency_id).map(|value| TimestampedValue { value, timestamp: 0 })
+	}
+
+	fn get_all_values() -> Vec<(CurrencyId, Option<TimestampedValue<Price, Moment>>)> {
+		vec![]
+	}
+}
+
+pub struct MockUnixTime;
+impl UnixTime for MockUnixTime {
+	fn now() -> core::time::Duration {
+		core::time::Duration::from_millis(<Timestamp as Time>::now() as u64)
+	}
+}
+
 impl pallet_pricedao::Config for Test {
 	type Event = Event;
 	type Source = MockDataProvider;
+	type UnixTime = MockUnixTime;
 	type FeedOrigin = frame_system::EnsureRoot<AccountId>;
 	type UpdateOraclesStorgage = DicoOracle;
 	type BaseCurrency = Balances;
@@ -244,7 +373,12 @@ parameter_types! {
 	pub const InviterRewardProportion: Percent = Percent::from_percent(10u8);
 	pub const InviteeRewardProportion: Percent = Percent::from_percent(10u8);
 	pub const USDCurrencyId: AssetId = kUSD;
-
+	pub const MaxPriceAge: u64 = 60_000;
+	pub const MaxTwapDeviation: Percent = Percent::from_percent(10u8);
+	pub const CancelIcoFee: Percent = Percent::from_percent(10u8);
+	pub const ProtectionFundId: PalletId = PalletId(*b"dico/ipf");
+	pub const ProtectionFundProportion: Percent = Percent::from_percent(10u8);
+	pub static PowerRewardBoost: Percent = Percent::from_percent(0u8);
 }
 
 impl Config for Test {
@@ -254,6 +388,7 @@ impl Config for Test {
 	type RejectIcoOrigin = frame_system::EnsureRoot<AccountId>;
 	type PermitReleaseOrigin = frame_system::EnsureRoot<AccountId>;
 	type TerminateIcoOrigin = frame_system::EnsureRoot<AccountId>;
+	type SetRewardEconomicsOrigin = frame_system::EnsureRoot<AccountId>;
 	type OnSlash = ();
 	type MultiCurrency = Currencies; //
 	type NativeCurrency = Balances; //
@@ -271,8 +406,29 @@ impl Config for Test {
 	type InviterRewardProportion = InviterRewardProportion;
 	type InviteeRewardProportion = InviteeRewardProportion;
 	type PriceData = PriceDao;
-	type KycHandler = Kyc;
+	type KycHandler = MockKyc;
+	type TierHandler = ();
 	type USDCurrencyId = USDCurrencyId;
+	type MaxAllowlistProofLength = frame_support::traits::ConstU32<32>;
+	type MaxPriceAge = MaxPriceAge;
+	type MaxTwapDeviation = MaxTwapDeviation;
+	type MaxInviteCodeLength = frame_support::traits::ConstU32<16>;
+	type MaxReferralLevels = frame_support::traits::ConstU32<3>;
+	type CancelIcoFee = CancelIcoFee;
+	type PowerHalfLife = frame_support::traits::ConstU64<10_000>;
+	type MaxPowerDelegators = frame_support::traits::ConstU32<2>;
+	type PowerRewardBoost = PowerRewardBoost;
+	type MaxLotteryEntrants = frame_support::traits::ConstU32<10>;
+	type MaxLotteryDrawsPerBlock = frame_support::traits::ConstU32<10>;
+	type MaxRequestExpiriesPerBlock = frame_support::traits::ConstU32<10>;
+	type RewardPoolClaimDuration = frame_support::traits::ConstU64<100>;
+	type ProtectionFundId = ProtectionFundId;
+	type ProtectionFundProportion = ProtectionFundProportion;
+	type IcoProposalHandler = ();
+	type LiquidityHandler = AMM;
+	type ReceiptHandler = MockReceipts;
+	type Randomness = MockRandomness;
+	type IcoMembersOrigin = MockIcoMembers;
 }
 
 parameter_types! {
@@ -335,6 +491,42 @@ impl dico_currencies::Config for Test {
 	type MaxCreatableCurrencyId = MaxCreatableCurrencyId;
 }
 
+/// A first come first served ico of `KSM` for `DOT` without lock, the tests override the fields
+/// they need.
+pub(crate) fn ico_parameters() -> IcoParameters<u64, Balance, CurrencyId, AreaCode> {
+	IcoParameters {
+		desc: vec![],
+		currency_id: KSM,
+		official_website: vec![],
+		is_must_kyc: false,
+		user_ico_max_times: 2,
+		total_issuance: 10000 * DOLLARS,
+		total_circulation: 1000 * DOLLARS,
+		ico_duration: NewDAYS,
+		total_ico_amount: 1000 * DOLLARS,
+		user_min_amount: 100 * DOLLARS,
+		user_max_amount: 500 * DOLLARS,
+		exchange_token: DOT,
+		exchange_token_total_amount: 1000 * DOLLARS,
+		exclude_area: vec![],
+		area_caps: vec![],
+		kyc_level_caps: vec![],
+		lock_proportion: Percent::from_percent(0u8),
+		unlock_duration: 0,
+		per_duration_unlock_amount: 0,
+		soft_cap: None,
+		mode: IcoMode::FirstComeFirstServed,
+		dutch_auction: None,
+		allowlist_root: None,
+		vesting: None,
+		milestones: None,
+		payment_tokens: vec![],
+		liquidity: None,
+		lottery: None,
+		mint_receipts: false,
+	}
+}
+
 pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
 	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
 
@@ -2,7 +2,7 @@
 
 use super::*;
 use crate::mock::*;
-use frame_support::{assert_noop, assert_ok, debug};
+use frame_support::{assert_noop, assert_ok, debug, traits::OnInitialize};
 
 fn initialize() {
 	Balances::set_balance(Origin::root(), Alice, 100_000 * DOLLARS, 100 * DOLLARS).unwrap();
@@ -85,23 +85,10 @@ fn initialize() {
 fn initiate_ico_should_work() {
 	new_test_ext().execute_with(|| {
 		let info = IcoParameters {
-			desc: vec![],
 			currency_id: DOT,
-			official_website: vec![],
-			is_must_kyc: false,
-			user_ico_max_times: 2,
-			total_issuance: 10000 * DOLLARS,
-			total_circulation: 1000 * DOLLARS,
-			ico_duration: NewDAYS,
-			total_ico_amount: 1000 * DOLLARS,
-			user_min_amount: 100 * DOLLARS,
-			user_max_amount: 500 * DOLLARS,
 			exchange_token: KSM,
 			exchange_token_total_amount: 10000 * DOLLARS,
-			exclude_area: vec![],
-			lock_proportion: Default::default(),
-			unlock_duration: NewDAYS,
-			per_duration_unlock_amount: 0 * DOLLARS,
+			..ico_parameters()
 		};
 		initialize();
 		assert_ok!(IcoTest::initiate_ico(Origin::signed(Alice), info));
@@ -146,11 +133,879 @@ fn join_should_work() {
 			},
 		);
 		initialize();
-		let result = IcoTest::join(Origin::signed(Bob), KSM, 1u32, 100 * DOLLARS, None);
+		let result = IcoTest::join(Origin::signed(Bob), KSM, 1u32, 100 * DOLLARS, None, None, None, None);
 		println!("result: {:?}", result);
 	});
 }
 
+#[test]
+fn refund_should_work() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		let mut info = IcoParameters {
+			soft_cap: Some(0),
+			..ico_parameters()
+		};
+		assert_noop!(
+			IcoTest::initiate_ico(Origin::signed(Alice), info.clone()),
+			Error::<Test>::SoftCapIsZero
+		);
+		info.soft_cap = Some(1000 * DOLLARS);
+		assert_ok!(IcoTest::initiate_ico(Origin::signed(Alice), info));
+		assert_eq!(IcoTest::soft_cap_of(KSM, 1), Some(1000 * DOLLARS));
+		assert_ok!(IcoTest::permit_ico(Origin::root(), KSM));
+		// The pledge is kept until the end of the ico.
+		assert_eq!(IcoTest::initiator_pledge_of(KSM, 1), DOLLARS);
+		assert_eq!(Balances::reserved_balance(Alice), 100 * DOLLARS + DOLLARS);
+
+		System::set_block_number(Ico::<Test>::get(KSM, 1).unwrap().start_time.unwrap());
+		assert_ok!(IcoTest::join(
+			Origin::signed(Bob),
+			KSM,
+			1,
+			200 * DOLLARS,
+			None,
+			None,
+			None,
+			None
+		));
+		assert_eq!(Tokens::free_balance(DOT, &Bob), 94_800 * DOLLARS);
+		assert_eq!(Tokens::reserved_balance(DOT, &Alice), 200 * DOLLARS);
+
+		// The total usdt is less than the soft cap.
+		assert_noop!(
+			IcoTest::user_release_ico_amount(Origin::signed(Bob), KSM, 1, None),
+			Error::<Test>::IcoFailed
+		);
+		assert_noop!(
+			IcoTest::request_release(Origin::signed(Alice), KSM, 1, Percent::from_percent(10u8)),
+			Error::<Test>::SoftCapNotReached
+		);
+
+		assert_ok!(IcoTest::refund(Origin::signed(Bob), KSM, 1));
+		assert_noop!(
+			IcoTest::refund(Origin::signed(Bob), KSM, 1),
+			Error::<Test>::AlreadyRefund
+		);
+		assert_eq!(Tokens::free_balance(DOT, &Bob), 95_000 * DOLLARS);
+		assert_eq!(Tokens::reserved_balance(DOT, &Alice), 0);
+		assert_eq!(Tokens::reserved_balance(KSM, &Bob), 0);
+		assert_eq!(IcoTest::total_power_of(Bob), 0);
+
+		assert_ok!(IcoTest::refund(Origin::signed(Alice), KSM, 1));
+		assert_noop!(
+			IcoTest::refund(Origin::signed(Alice), KSM, 1),
+			Error::<Test>::AlreadyRefund
+		);
+		assert_eq!(Tokens::free_balance(KSM, &Alice), 100_000 * DOLLARS);
+		assert_eq!(Tokens::reserved_balance(KSM, &Alice), 0);
+		assert_eq!(IcoTest::initiator_pledge_of(KSM, 1), 0);
+		assert_eq!(Balances::reserved_balance(Alice), 100 * DOLLARS);
+	});
+}
+
+#[test]
+fn claim_allocation_should_work() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		Currencies::transfer(Origin::signed(Bob), DAVE, DOT, 1000 * DOLLARS).unwrap();
+		let info = IcoParameters {
+			exchange_token_total_amount: 300 * DOLLARS,
+			mode: IcoMode::ProRata,
+			..ico_parameters()
+		};
+		assert_ok!(IcoTest::initiate_ico(Origin::signed(Alice), info));
+		assert_ok!(IcoTest::permit_ico(Origin::root(), KSM));
+		assert_eq!(IcoTest::ico_mode_of(KSM, 1), IcoMode::ProRata);
+
+		// Users can join above the ico amount.
+		System::set_block_number(Ico::<Test>::get(KSM, 1).unwrap().start_time.unwrap());
+		assert_ok!(IcoTest::join(
+			Origin::signed(Bob),
+			KSM,
+			1,
+			200 * DOLLARS,
+			None,
+			None,
+			None,
+			None
+		));
+		assert_ok!(IcoTest::join(
+			Origin::signed(DAVE),
+			KSM,
+			1,
+			200 * DOLLARS,
+			None,
+			None,
+			None,
+			None
+		));
+		assert_eq!(Tokens::reserved_balance(DOT, &Bob), 200 * DOLLARS);
+		assert_eq!(Tokens::reserved_balance(DOT, &DAVE), 200 * DOLLARS);
+
+		assert_eq!(Ico::<Test>::get(KSM, 1).unwrap().total_usdt, 400 * DOLLARS);
+		let power = IcoTest::total_power_of(Bob);
+
+		// Each user gets 3/4 of the contribution accepted, and only the usdt accepted counts.
+		assert_ok!(IcoTest::claim_allocation(Origin::signed(Bob), KSM, 1));
+		assert_noop!(
+			IcoTest::claim_allocation(Origin::signed(Bob), KSM, 1),
+			Error::<Test>::AllocationAlreadyClaimed
+		);
+		assert_eq!(Tokens::reserved_balance(DOT, &Bob), 0);
+		assert_eq!(Tokens::free_balance(DOT, &Bob), 93_850 * DOLLARS);
+		assert_eq!(Tokens::reserved_balance(KSM, &Bob), 500 * DOLLARS);
+		assert_eq!(IcoTest::get_total_and_released_amount(KSM, 1, &Bob).0, 500 * DOLLARS);
+		assert_eq!(UnReleaseAssets::<Test>::get(Bob)[0].total_usdt, 150 * DOLLARS);
+		assert_eq!(Ico::<Test>::get(KSM, 1).unwrap().total_usdt, 350 * DOLLARS);
+		assert_eq!(IcoTest::total_power_of(Bob), power - 50 * DOLLARS);
+
+		assert_ok!(IcoTest::claim_allocation(Origin::signed(DAVE), KSM, 1));
+		assert_eq!(Tokens::free_balance(DOT, &DAVE), 850 * DOLLARS);
+		assert_eq!(Tokens::reserved_balance(KSM, &DAVE), 500 * DOLLARS);
+		assert_eq!(Ico::<Test>::get(KSM, 1).unwrap().total_usdt, 300 * DOLLARS);
+
+		assert_eq!(Tokens::reserved_balance(DOT, &Alice), 300 * DOLLARS);
+		assert_eq!(Tokens::reserved_balance(KSM, &Alice), 0);
+	});
+}
+
+#[test]
+fn dutch_auction_should_work() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		Currencies::transfer(Origin::signed(Bob), DAVE, DOT, 1000 * DOLLARS).unwrap();
+		let mut info = IcoParameters {
+			exchange_token_total_amount: 100 * DOLLARS,
+			mode: IcoMode::DutchAuction,
+			dutch_auction: Some(DutchAuctionParameters {
+				start_exchange_token_total_amount: 50 * DOLLARS,
+				decay_per_block: DOLLARS,
+			}),
+			..ico_parameters()
+		};
+		assert_noop!(
+			IcoTest::initiate_ico(Origin::signed(Alice), info.clone()),
+			Error::<Test>::InvalidDutchAuction
+		);
+		info.dutch_auction = Some(DutchAuctionParameters {
+			start_exchange_token_total_amount: 500 * DOLLARS,
+			decay_per_block: DOLLARS,
+		});
+		assert_ok!(IcoTest::initiate_ico(Origin::signed(Alice), info));
+		assert_ok!(IcoTest::permit_ico(Origin::root(), KSM));
+
+		// Bob bids at 500 DOT for the total ico amount.
+		let start_time = Ico::<Test>::get(KSM, 1).unwrap().start_time.unwrap();
+		System::set_block_number(start_time);
+		assert_ok!(IcoTest::join(
+			Origin::signed(Bob),
+			KSM,
+			1,
+			200 * DOLLARS,
+			None,
+			None,
+			None,
+			None
+		));
+		// Dave bids at 200 DOT for the total ico amount.
+		System::set_block_number(start_time + 300);
+		assert_noop!(
+			IcoTest::join(Origin::signed(DAVE), KSM, 1, 200 * DOLLARS, None, None, None, None),
+			Error::<Test>::ProjectIcoAmountToMax
+		);
+		assert_ok!(IcoTest::join(
+			Origin::signed(DAVE),
+			KSM,
+			1,
+			100 * DOLLARS,
+			None,
+			None,
+			None,
+			None
+		));
+		assert_eq!(
+			IcoTest::dutch_auction_of(KSM, 1).unwrap().sold_amount,
+			900 * DOLLARS + 5
+		);
+
+		// The ico amount is not sold out, everyone pays the floor price.
+		assert_ok!(IcoTest::claim_allocation(Origin::signed(Bob), KSM, 1));
+		assert_eq!(Tokens::free_balance(DOT, &Bob), 93_960 * DOLLARS);
+		assert_eq!(Tokens::reserved_balance(DOT, &Bob), 0);
+		assert_eq!(Tokens::reserved_balance(KSM, &Bob), 400 * DOLLARS + 2);
+
+		assert_ok!(IcoTest::claim_allocation(Origin::signed(DAVE), KSM, 1));
+		assert_eq!(Tokens::free_balance(DOT, &DAVE), 950 * DOLLARS);
+		assert_eq!(Tokens::reserved_balance(KSM, &DAVE), 500 * DOLLARS + 3);
+
+		assert_eq!(Tokens::reserved_balance(DOT, &Alice), 90 * DOLLARS);
+		assert_eq!(Tokens::reserved_balance(KSM, &Alice), 100 * DOLLARS - 5);
+	});
+}
+
+#[test]
+fn allowlist_should_work() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		let bob_leaf = IcoTest::allowlist_leaf_hash(&Bob, 300 * DOLLARS);
+		let dave_leaf = IcoTest::allowlist_leaf_hash(&DAVE, 300 * DOLLARS);
+		let pair = if bob_leaf <= dave_leaf {
+			[bob_leaf.as_bytes(), dave_leaf.as_bytes()].concat()
+		} else {
+			[dave_leaf.as_bytes(), bob_leaf.as_bytes()].concat()
+		};
+		let root = H256::from(blake2_256(&pair));
+
+		let info = IcoParameters {
+			allowlist_root: Some(root),
+			..ico_parameters()
+		};
+		assert_ok!(IcoTest::initiate_ico(Origin::signed(Alice), info));
+		assert_ok!(IcoTest::permit_ico(Origin::root(), KSM));
+		System::set_block_number(Ico::<Test>::get(KSM, 1).unwrap().start_time.unwrap());
+
+		assert_noop!(
+			IcoTest::join(Origin::signed(Bob), KSM, 1, 200 * DOLLARS, None, None, None, None),
+			Error::<Test>::NotInAllowlist
+		);
+		assert_noop!(
+			IcoTest::join(
+				Origin::signed(Bob),
+				KSM,
+				1,
+				200 * DOLLARS,
+				None,
+				Some(AllowlistProof {
+					max_allocation: 500 * DOLLARS,
+					proof: vec![dave_leaf],
+				}),
+				None,
+				None
+			),
+			Error::<Test>::InvalidAllowlistProof
+		);
+		assert_ok!(IcoTest::join(
+			Origin::signed(Bob),
+			KSM,
+			1,
+			200 * DOLLARS,
+			None,
+			Some(AllowlistProof {
+				max_allocation: 300 * DOLLARS,
+				proof: vec![dave_leaf],
+			}),
+			None,
+			None
+		));
+		assert_eq!(IcoTest::allowlist_cap_of((KSM, 1), Bob), Some(300 * DOLLARS));
+
+		// The cap is enforced on top of `user_max_amount`.
+		assert_noop!(
+			IcoTest::join(Origin::signed(Bob), KSM, 1, 200 * DOLLARS, None, None, None, None),
+			Error::<Test>::AmountMoreThanAllowlistCap
+		);
+	});
+}
+
+#[test]
+fn vesting_should_work() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		let mut info = IcoParameters {
+			lock_proportion: Percent::from_percent(50u8),
+			vesting: Some(VestingParameters {
+				cliff: 10,
+				period: 10,
+				period_count: 0,
+			}),
+			..ico_parameters()
+		};
+		assert_noop!(
+			IcoTest::initiate_ico(Origin::signed(Alice), info.clone()),
+			Error::<Test>::InvalidVesting
+		);
+		info.vesting = Some(VestingParameters {
+			cliff: 10,
+			period: 10,
+			period_count: 51,
+		});
+		// Each period unlocks some of the locked tokens.
+		info.total_ico_amount = 100;
+		assert_noop!(
+			IcoTest::initiate_ico(Origin::signed(Alice), info.clone()),
+			Error::<Test>::InvalidVesting
+		);
+		info.total_ico_amount = 1000 * DOLLARS;
+		info.vesting = Some(VestingParameters {
+			cliff: 10,
+			period: 10,
+			period_count: 3,
+		});
+		assert_ok!(IcoTest::initiate_ico(Origin::signed(Alice), info));
+		assert_ok!(IcoTest::permit_ico(Origin::root(), KSM));
+
+		let start_time = Ico::<Test>::get(KSM, 1).unwrap().start_time.unwrap();
+		System::set_block_number(start_time);
+		assert_ok!(IcoTest::join(
+			Origin::signed(Bob),
+			KSM,
+			1,
+			200 * DOLLARS,
+			None,
+			None,
+			None,
+			None
+		));
+		let total = Tokens::reserved_balance(KSM, &Bob);
+
+		let release_time = start_time + NewDAYS;
+		System::set_block_number(release_time);
+		assert_ok!(IcoTest::request_release(
+			Origin::signed(Alice),
+			KSM,
+			1,
+			Percent::from_percent(100u8)
+		));
+		assert_ok!(IcoTest::permit_release(Origin::root(), KSM, 1));
+		assert_ok!(IcoTest::user_release_ico_amount(Origin::signed(Bob), KSM, 1, None));
+
+		// The locked tokens are free, but can not be transferred until they vest, the remainder
+		// vests with the final period.
+		let locked = Percent::from_percent(50u8) * total;
+		let per_period = locked / 3;
+		let remainder = locked % 3;
+		assert!(remainder != 0);
+		assert_eq!(Tokens::free_balance(KSM, &Bob), total);
+		assert_eq!(Tokens::reserved_balance(KSM, &Bob), 0);
+		assert_eq!(Tokens::accounts(Bob, KSM).frozen, locked);
+		assert_eq!(IcoTest::vesting_schedules(Bob, KSM).len(), 2);
+
+		// Nothing vests before the cliff and the first period end.
+		System::set_block_number(release_time + 10);
+		assert_eq!(IcoTest::can_unlock_amount(Bob, KSM, 1), 0);
+		assert_noop!(
+			IcoTest::unlock(Origin::signed(Bob), KSM, 1, None),
+			Error::<Test>::UnlockAmountIsZero
+		);
+
+		// The locks without vesting in the currency are unlocked together with the vesting.
+		assert_ok!(Tokens::reserve(KSM, &Bob, 10 * DOLLARS));
+		IcoLocks::<Test>::insert(
+			Bob,
+			KSM,
+			vec![IcoLock {
+				start_block: release_time + 10,
+				index: 1,
+				total_amount: 10 * DOLLARS,
+				unlock_amount: 0,
+				unlock_duration: 1,
+				per_duration_unlock_amount: DOLLARS,
+			}],
+		);
+
+		System::set_block_number(release_time + 20);
+		assert_eq!(IcoTest::can_unlock_amount(Bob, KSM, 1), per_period + 10 * DOLLARS);
+		assert_ok!(IcoTest::unlock(Origin::signed(Bob), KSM, 1, None));
+		assert_eq!(Tokens::accounts(Bob, KSM).frozen, locked - per_period);
+		assert_eq!(Tokens::reserved_balance(KSM, &Bob), 0);
+
+		System::set_block_number(release_time + 30);
+		assert_ok!(IcoTest::unlock(Origin::signed(Bob), KSM, 1, None));
+		assert_eq!(Tokens::accounts(Bob, KSM).frozen, per_period + remainder);
+
+		System::set_block_number(release_time + 40);
+		assert_eq!(IcoTest::can_unlock_amount(Bob, KSM, 1), per_period + remainder);
+		assert_ok!(IcoTest::unlock(Origin::signed(Bob), KSM, 1, None));
+		assert_eq!(Tokens::accounts(Bob, KSM).frozen, 0);
+		assert!(IcoTest::vesting_schedules(Bob, KSM).is_empty());
+		// The user is still a member of the ico.
+		assert!(IcoTest::is_member(&Bob, KSM, 1, false));
+	});
+}
+
+#[test]
+fn milestones_should_work() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		let milestone = |percent: u8, deadline: u64| Milestone {
+			desc_hash: H256::repeat_byte(percent),
+			percent: Percent::from_percent(percent),
+			deadline,
+		};
+		let mut info = IcoParameters {
+			milestones: Some(MilestoneInfo {
+				milestones: vec![milestone(60, 3 * NewDAYS), milestone(50, 4 * NewDAYS)],
+				threshold: Percent::from_percent(60u8),
+			}),
+			..ico_parameters()
+		};
+		// The milestones release more than all the funds.
+		assert_noop!(
+			IcoTest::initiate_ico(Origin::signed(Alice), info.clone()),
+			Error::<Test>::InvalidMilestones
+		);
+		info.milestones = Some(MilestoneInfo {
+			milestones: vec![milestone(60, 3 * NewDAYS), milestone(40, 4 * NewDAYS)],
+			threshold: Percent::from_percent(60u8),
+		});
+		assert_ok!(IcoTest::initiate_ico(Origin::signed(Alice), info));
+		assert_ok!(IcoTest::permit_ico(Origin::root(), KSM));
+
+		let start_time = Ico::<Test>::get(KSM, 1).unwrap().start_time.unwrap();
+		System::set_block_number(start_time);
+		assert_ok!(IcoTest::join(
+			Origin::signed(Bob),
+			KSM,
+			1,
+			200 * DOLLARS,
+			None,
+			None,
+			None,
+			None
+		));
+
+		System::set_block_number(start_time + NewDAYS);
+		// The release proportion must be the one of the next milestone.
+		assert_noop!(
+			IcoTest::request_release(Origin::signed(Alice), KSM, 1, Percent::from_percent(50u8)),
+			Error::<Test>::NotMilestoneProportion
+		);
+		assert_ok!(IcoTest::request_release(
+			Origin::signed(Alice),
+			KSM,
+			1,
+			Percent::from_percent(60u8)
+		));
+		// The members of another ico can not permit the release.
+		MockIcoMembers::set_votes(KSM, 2, 200 * DOLLARS, 200 * DOLLARS);
+		assert_noop!(
+			IcoTest::permit_release(Origin::signed(ICO_MEMBERS), KSM, 1),
+			Error::<Test>::BadOrigin
+		);
+		MockIcoMembers::set_votes(KSM, 1, 100 * DOLLARS, 200 * DOLLARS);
+		assert_noop!(
+			IcoTest::permit_release(Origin::signed(ICO_MEMBERS), KSM, 1),
+			Error::<Test>::MilestoneThresholdNotReached
+		);
+		MockIcoMembers::set_votes(KSM, 1, 120 * DOLLARS, 200 * DOLLARS);
+		assert_ok!(IcoTest::permit_release(Origin::signed(ICO_MEMBERS), KSM, 1));
+		assert_eq!(
+			Ico::<Test>::get(KSM, 1).unwrap().already_released_proportion,
+			Percent::from_percent(60u8)
+		);
+
+		let ico = Ico::<Test>::get(KSM, 1).unwrap();
+		assert!(!IcoTest::is_milestone_missed(&ico));
+		assert_noop!(
+			IcoTest::terminate_ico(Origin::signed(ICO_MEMBERS), KSM, 1),
+			Error::<Test>::MilestoneNotMissed
+		);
+		// The second milestone is not released before its deadline.
+		System::set_block_number(4 * NewDAYS + 1);
+		assert!(IcoTest::is_milestone_missed(&ico));
+		assert_ok!(IcoTest::terminate_ico(Origin::signed(ICO_MEMBERS), KSM, 1));
+		assert!(Ico::<Test>::get(KSM, 1).unwrap().is_terminated);
+	});
+}
+
+#[test]
+fn payment_tokens_should_work() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		Currencies::create_asset(
+			Origin::signed(Bob),
+			KICO,
+			100_000 * DOLLARS,
+			Some(DicoAssetMetadata {
+				name: b"KICO".to_vec(),
+				symbol: b"KICO".to_vec(),
+				decimals: 12,
+			}),
+		)
+		.unwrap();
+		let mut info = IcoParameters {
+			payment_tokens: vec![PaymentToken {
+				currency_id: DOT,
+				cap: 100 * DOLLARS,
+			}],
+			..ico_parameters()
+		};
+		// The exchange token is not another payment token.
+		assert_noop!(
+			IcoTest::initiate_ico(Origin::signed(Alice), info.clone()),
+			Error::<Test>::InvalidPaymentTokens
+		);
+		info.payment_tokens = vec![PaymentToken {
+			currency_id: KICO,
+			cap: 100 * DOLLARS,
+		}];
+		assert_ok!(IcoTest::initiate_ico(Origin::signed(Alice), info));
+		assert_ok!(IcoTest::permit_ico(Origin::root(), KSM));
+
+		let start_time = Ico::<Test>::get(KSM, 1).unwrap().start_time.unwrap();
+		System::set_block_number(start_time);
+		assert_noop!(
+			IcoTest::join_with_payment_token(
+				Origin::signed(Bob),
+				KSM,
+				1,
+				NEW_USDT,
+				100 * DOLLARS,
+				None,
+				None,
+				None,
+				None
+			),
+			Error::<Test>::PaymentTokenNotAccepted
+		);
+		assert_noop!(
+			IcoTest::join_with_payment_token(Origin::signed(Bob), KSM, 1, KICO, 150 * DOLLARS, None, None, None, None),
+			Error::<Test>::PaymentTokenCapReached
+		);
+
+		// 1 KICO is 2 USD, which is 2 DOT.
+		assert_ok!(IcoTest::join_with_payment_token(
+			Origin::signed(Bob),
+			KSM,
+			1,
+			KICO,
+			100 * DOLLARS,
+			None,
+			None,
+			None,
+			None
+		));
+		assert_eq!(Tokens::reserved_balance(KSM, &Bob), 200 * DOLLARS);
+		assert_eq!(Tokens::reserved_balance(KICO, &Alice), 100 * DOLLARS);
+		assert_eq!(Ico::<Test>::get(KSM, 1).unwrap().total_usdt, 200 * DOLLARS);
+		assert_eq!(
+			IcoTest::payment_raised_of((KSM, 1), KICO),
+			PaymentRaised {
+				raised: 100 * DOLLARS,
+				equivalent: 200 * DOLLARS,
+				refund: 0,
+				released: 0,
+			}
+		);
+
+		System::set_block_number(start_time + NewDAYS);
+		assert_ok!(IcoTest::request_release(
+			Origin::signed(Alice),
+			KSM,
+			1,
+			Percent::from_percent(50u8)
+		));
+		assert_ok!(IcoTest::permit_release(Origin::root(), KSM, 1));
+		assert_ok!(IcoTest::release_payment_token(Origin::signed(Alice), KSM, 1, KICO));
+		// 10% of the released tokens go to the protection fund.
+		assert_eq!(Tokens::free_balance(KICO, &Alice), 45 * DOLLARS);
+		assert_eq!(
+			Tokens::free_balance(KICO, &IcoTest::protection_fund_account()),
+			5 * DOLLARS
+		);
+		assert_noop!(
+			IcoTest::release_payment_token(Origin::signed(Alice), KSM, 1, KICO),
+			Error::<Test>::UnreleaseAmountIsZero
+		);
+	});
+}
+
+#[test]
+fn join_guards_should_work() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		Currencies::create_asset(
+			Origin::signed(Bob),
+			KICO,
+			100_000 * DOLLARS,
+			Some(DicoAssetMetadata {
+				name: b"KICO".to_vec(),
+				symbol: b"KICO".to_vec(),
+				decimals: 12,
+			}),
+		)
+		.unwrap();
+		let info = IcoParameters {
+			payment_tokens: vec![PaymentToken {
+				currency_id: KICO,
+				cap: 1000 * DOLLARS,
+			}],
+			..ico_parameters()
+		};
+		assert_ok!(IcoTest::initiate_ico(Origin::signed(Alice), info));
+		assert_ok!(IcoTest::permit_ico(Origin::root(), KSM));
+		let start_time = Ico::<Test>::get(KSM, 1).unwrap().start_time.unwrap();
+		System::set_block_number(start_time);
+
+		// 200 DOT is 200 USD, and one KSM is sold for 1 CENTS of DOT.
+		assert_noop!(
+			IcoTest::join(
+				Origin::signed(Bob),
+				KSM,
+				1,
+				200 * DOLLARS,
+				None,
+				None,
+				Some(201 * DOLLARS),
+				None
+			),
+			Error::<Test>::PowerLessThanMin
+		);
+		assert_noop!(
+			IcoTest::join(
+				Origin::signed(Bob),
+				KSM,
+				1,
+				200 * DOLLARS,
+				None,
+				None,
+				None,
+				Some(CENTS - 1)
+			),
+			Error::<Test>::PriceMoreThanMax
+		);
+		assert_ok!(IcoTest::join(
+			Origin::signed(Bob),
+			KSM,
+			1,
+			200 * DOLLARS,
+			None,
+			None,
+			Some(200 * DOLLARS),
+			Some(CENTS)
+		));
+
+		// The oracle price of KICO is fed at 0.
+		Timestamp::set_timestamp(MaxPriceAge::get() as u32 + 1);
+		assert_noop!(
+			IcoTest::join_with_payment_token(Origin::signed(Bob), KSM, 1, KICO, 100 * DOLLARS, None, None, None, None),
+			Error::<Test>::PriceTooOld
+		);
+		Timestamp::set_timestamp(MaxPriceAge::get() as u32);
+
+		// The oracle price of KICO is 2 USD, but it is 1 USD in the swap.
+		assert_ok!(AMM::add_liquidity(
+			Origin::signed(Bob),
+			KICO,
+			DOT,
+			1000 * DOLLARS,
+			1000 * DOLLARS,
+			0,
+			0
+		));
+		System::set_block_number(start_time + 1);
+		assert_eq!(PriceDao::get_twap_price(KICO, kUSD), Some(USD));
+		assert_noop!(
+			IcoTest::join_with_payment_token(Origin::signed(Bob), KSM, 1, KICO, 100 * DOLLARS, None, None, None, None),
+			Error::<Test>::PriceDeviationTooLarge
+		);
+	});
+}
+
+#[test]
+fn referral_should_work() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		Currencies::transfer(Origin::signed(Bob), DAVE, DOT, 1000 * DOLLARS).unwrap();
+		let info = ico_parameters();
+		assert_ok!(IcoTest::initiate_ico(Origin::signed(Alice), info));
+		assert_ok!(IcoTest::permit_ico(Origin::root(), KSM));
+
+		assert_noop!(
+			IcoTest::register_invite_code(Origin::signed(Alice), vec![]),
+			Error::<Test>::InvalidInviteCode
+		);
+		assert_noop!(
+			IcoTest::register_invite_code(Origin::signed(Alice), vec![b'A'; 17]),
+			Error::<Test>::InvalidInviteCode
+		);
+		assert_ok!(IcoTest::register_invite_code(Origin::signed(Alice), b"ALICE".to_vec()));
+		assert_noop!(
+			IcoTest::register_invite_code(Origin::signed(Alice), b"ALICE2".to_vec()),
+			Error::<Test>::InviteCodeAlreadyRegistered
+		);
+		assert_noop!(
+			IcoTest::register_invite_code(Origin::signed(DAVE), b"ALICE".to_vec()),
+			Error::<Test>::InviteCodeAlreadyTaken
+		);
+		assert_ok!(IcoTest::register_invite_code(Origin::signed(DAVE), b"DAVE".to_vec()));
+
+		// Bob is referred by DAVE, who is referred by Alice.
+		assert_noop!(
+			IcoTest::bind_invite_code(Origin::signed(Bob), b"BOB".to_vec()),
+			Error::<Test>::InviteCodeNotExists
+		);
+		assert_noop!(
+			IcoTest::bind_invite_code(Origin::signed(DAVE), b"DAVE".to_vec()),
+			Error::<Test>::CanNotInviteYouself
+		);
+		assert_ok!(IcoTest::bind_invite_code(Origin::signed(DAVE), b"ALICE".to_vec()));
+		assert_ok!(IcoTest::bind_invite_code(Origin::signed(Bob), b"DAVE".to_vec()));
+		assert_noop!(
+			IcoTest::bind_invite_code(Origin::signed(Bob), b"ALICE".to_vec()),
+			Error::<Test>::ReferrerAlreadyBound
+		);
+		assert_noop!(
+			IcoTest::bind_invite_code(Origin::signed(Alice), b"DAVE".to_vec()),
+			Error::<Test>::ReferralCycle
+		);
+		assert_eq!(IcoTest::referrer_of(Bob), Some(DAVE));
+
+		assert_noop!(
+			IcoTest::set_referral_reward_proportions(Origin::signed(Alice), vec![Percent::from_percent(10u8)]),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			IcoTest::set_referral_reward_proportions(Origin::root(), vec![Percent::from_percent(10u8); 4]),
+			Error::<Test>::TooManyReferralLevels
+		);
+		assert_noop!(
+			IcoTest::set_referral_reward_proportions(
+				Origin::root(),
+				vec![Percent::from_percent(60u8), Percent::from_percent(41u8)]
+			),
+			Error::<Test>::ReferralRewardTooHigh
+		);
+		assert_ok!(IcoTest::set_referral_reward_proportions(
+			Origin::root(),
+			vec![Percent::from_percent(10u8), Percent::from_percent(5u8)]
+		));
+
+		let start_time = Ico::<Test>::get(KSM, 1).unwrap().start_time.unwrap();
+		System::set_block_number(start_time);
+		assert_ok!(IcoTest::join(
+			Origin::signed(DAVE),
+			KSM,
+			1,
+			100 * DOLLARS,
+			None,
+			None,
+			None,
+			None
+		));
+		// The referrer is the inviter of Bob.
+		assert_ok!(IcoTest::join(
+			Origin::signed(Bob),
+			KSM,
+			1,
+			200 * DOLLARS,
+			None,
+			None,
+			None,
+			None
+		));
+		assert_eq!(
+			IcoTest::ico_assets_info(Bob)
+				.into_iter()
+				.find(|h| h.currency_id == KSM && h.index == 1)
+				.unwrap()
+				.inviter,
+			Some(DAVE)
+		);
+
+		let reward = IcoTest::get_reward_amount(Bob, KSM, 1);
+		assert!(reward > 0);
+		assert_ok!(IcoTest::get_reward(Origin::signed(Bob), KSM, 1, None));
+		assert_eq!(
+			IcoTest::get_referral_earnings(DAVE),
+			vec![(KSM, 1, Percent::from_percent(10u8) * reward)]
+		);
+		assert_eq!(
+			IcoTest::get_referral_earnings(Alice),
+			vec![(KSM, 1, Percent::from_percent(5u8) * reward)]
+		);
+		assert_eq!(IcoTest::get_referral_earnings(Bob), vec![]);
+	});
+}
+
+#[test]
+fn cancel_and_amend_ico_should_work() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		let mut economics = IcoTest::reward_economics();
+		economics.initiator_pledge = 5 * DOLLARS;
+		assert_ok!(IcoTest::set_reward_economics(Origin::root(), economics.clone()));
+		let info = ico_parameters();
+		assert_ok!(IcoTest::initiate_ico(Origin::signed(Alice), info));
+
+		let mut amendment = IcoAmendment {
+			desc: b"new desc".to_vec(),
+			official_website: b"dico.io".to_vec(),
+			ico_duration: 2 * NewDAYS,
+			lock_proportion: Percent::from_percent(50u8),
+			unlock_duration: 0,
+			per_duration_unlock_amount: 0,
+			vesting: Some(VestingParameters {
+				cliff: 10,
+				period: 0,
+				period_count: 10,
+			}),
+		};
+		assert_noop!(
+			IcoTest::amend_ico(Origin::signed(Alice), KSM, amendment.clone()),
+			Error::<Test>::InvalidVesting
+		);
+		amendment.vesting = Some(VestingParameters {
+			cliff: 10,
+			period: 10,
+			period_count: 10,
+		});
+		assert_noop!(
+			IcoTest::amend_ico(Origin::signed(Bob), KSM, amendment.clone()),
+			Error::<Test>::NotInitiator
+		);
+		assert_noop!(
+			IcoTest::amend_ico(Origin::signed(Alice), DOT, amendment.clone()),
+			Error::<Test>::PendingIcoNotExists123
+		);
+		assert_ok!(IcoTest::amend_ico(Origin::signed(Alice), KSM, amendment.clone()));
+		let ico = IcoTest::pending_ico(KSM).unwrap().ico.clone();
+		assert_eq!(ico.desc, b"new desc".to_vec());
+		assert_eq!(ico.ico_duration, 2 * NewDAYS);
+		assert_eq!(ico.lock_proportion, Percent::from_percent(50u8));
+		assert_eq!(VestingOf::<Test>::get(KSM, 1), amendment.vesting);
+
+		assert_ok!(IcoTest::permit_ico(Origin::root(), KSM));
+		// The pledge of an ico without soft cap is kept too.
+		assert_eq!(IcoTest::initiator_pledge_of(KSM, 1), 5 * DOLLARS);
+		// The fee is charged on the pledge of the ico, not on the current economics.
+		economics.initiator_pledge = DOLLARS;
+		assert_ok!(IcoTest::set_reward_economics(Origin::root(), economics));
+		// Only the pending ico can be amended.
+		assert_noop!(
+			IcoTest::amend_ico(Origin::signed(Alice), KSM, amendment),
+			Error::<Test>::PendingIcoNotExists123
+		);
+
+		assert_noop!(
+			IcoTest::cancel_ico(Origin::signed(Bob), KSM, 1),
+			Error::<Test>::NotInitiator
+		);
+		let start_time = Ico::<Test>::get(KSM, 1).unwrap().start_time.unwrap();
+		System::set_block_number(start_time);
+		assert_noop!(
+			IcoTest::cancel_ico(Origin::signed(Alice), KSM, 1),
+			Error::<Test>::IcoAlreadyStarted
+		);
+		System::set_block_number(start_time - 1);
+
+		let free = Balances::free_balance(Alice);
+		let reserved = Balances::reserved_balance(Alice);
+		let treasury = DicoTreasury::get_treasury_account_id();
+		let treasury_free = Balances::free_balance(treasury);
+		assert_ok!(IcoTest::cancel_ico(Origin::signed(Alice), KSM, 1));
+		assert_eq!(Balances::free_balance(Alice), free + 5 * DOLLARS - DOLLARS / 2);
+		assert_eq!(Balances::reserved_balance(Alice), reserved - 5 * DOLLARS);
+		assert_eq!(Balances::free_balance(treasury), treasury_free + DOLLARS / 2);
+		assert_eq!(IcoTest::initiator_pledge_of(KSM, 1), 0);
+		assert_eq!(Tokens::reserved_balance(KSM, &Alice), 0);
+		assert!(Ico::<Test>::get(KSM, 1).is_none());
+		assert!(Indexs::<Test>::get(KSM).is_empty());
+		assert_eq!(VestingOf::<Test>::get(KSM, 1), None);
+	});
+}
+
 #[test]
 pub fn calculate_total_reward_should_work() {
 	new_test_ext().execute_with(|| {
@@ -186,3 +1041,912 @@ fn main_test() {
 	let a = split(45u64, 45u64, 20);
 	println!("{:?}", a);
 }
+
+#[test]
+fn list_icoes_and_portfolio_should_work() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		let info = ico_parameters();
+		assert_ok!(IcoTest::initiate_ico(Origin::signed(Alice), info));
+		assert_eq!(IcoTest::get_icoes(Some(IcoStage::Pending), 0, 10).len(), 1);
+		assert_eq!(IcoTest::get_ico_detail(KSM, 1).unwrap().stage, IcoStage::Pending);
+
+		assert_ok!(IcoTest::permit_ico(Origin::root(), KSM));
+		assert!(IcoTest::get_icoes(Some(IcoStage::Pending), 0, 10).is_empty());
+		assert_eq!(IcoTest::get_icoes(Some(IcoStage::Upcoming), 0, 10).len(), 1);
+		assert!(IcoTest::get_icoes(None, 1, 10).is_empty());
+
+		let start_time = Ico::<Test>::get(KSM, 1).unwrap().start_time.unwrap();
+		System::set_block_number(start_time);
+		assert_ok!(IcoTest::join(
+			Origin::signed(Bob),
+			KSM,
+			1,
+			200 * DOLLARS,
+			None,
+			None,
+			None,
+			None
+		));
+		let detail = IcoTest::get_ico_detail(KSM, 1).unwrap();
+		assert_eq!(detail.stage, IcoStage::Ongoing);
+		assert_eq!(detail.participant_count, 1);
+		assert_eq!(detail.raised_amount, 200 * DOLLARS);
+
+		let portfolio = IcoTest::get_portfolio(Bob);
+		assert_eq!(portfolio.len(), 1);
+		assert_eq!((portfolio[0].currency_id, portfolio[0].index), (KSM, 1));
+		assert_eq!(portfolio[0].joined_usdt, detail.raised_usdt);
+		assert!(IcoTest::get_portfolio(Alice).is_empty());
+
+		System::set_block_number(start_time + NewDAYS + 1);
+		assert_eq!(IcoTest::get_icoes(Some(IcoStage::Finished), 0, 10).len(), 1);
+	});
+}
+
+#[test]
+fn power_decay_and_delegation_should_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(<IcoTest as PowerHandler<_, _, _>>::add_user_power(&Bob, 1000 * DOLLARS));
+		assert_eq!(IcoTest::power_of(&Bob), 1000 * DOLLARS);
+		// The power halves every 10_000 blocks and decays linearly in between.
+		System::set_block_number(10_001);
+		assert_eq!(IcoTest::power_of(&Bob), 500 * DOLLARS);
+		System::set_block_number(15_001);
+		assert_eq!(IcoTest::power_of(&Bob), 375 * DOLLARS);
+
+		assert_noop!(
+			IcoTest::delegate_power(Origin::signed(Bob), Bob),
+			Error::<Test>::DelegateToYourself
+		);
+		assert_ok!(IcoTest::delegate_power(Origin::signed(Bob), DAVE));
+		assert_noop!(
+			IcoTest::delegate_power(Origin::signed(Bob), Alice),
+			Error::<Test>::PowerAlreadyDelegated
+		);
+		assert_noop!(
+			IcoTest::delegate_power(Origin::signed(DAVE), Alice),
+			Error::<Test>::PowerDelegationChain
+		);
+		assert_noop!(
+			IcoTest::delegate_power(Origin::signed(Alice), Bob),
+			Error::<Test>::PowerDelegationChain
+		);
+		assert_ok!(IcoTest::delegate_power(Origin::signed(Alice), DAVE));
+		assert_noop!(
+			IcoTest::delegate_power(Origin::signed(4), DAVE),
+			Error::<Test>::TooManyPowerDelegators
+		);
+		assert_eq!(IcoTest::usable_power_of(&Bob), 0);
+		assert_eq!(IcoTest::usable_power_of(&DAVE), 375 * DOLLARS);
+
+		assert_noop!(
+			<IcoTest as PowerHandler<_, _, _>>::sub_user_power(&DAVE, 400 * DOLLARS),
+			Error::<Test>::PowerNotEnough
+		);
+		assert_ok!(<IcoTest as PowerHandler<_, _, _>>::sub_user_power(&DAVE, 100 * DOLLARS));
+		assert_eq!(IcoTest::power_of(&Bob), 275 * DOLLARS);
+
+		assert_ok!(IcoTest::revoke_power_delegation(Origin::signed(Bob)));
+		assert_noop!(
+			IcoTest::revoke_power_delegation(Origin::signed(Bob)),
+			Error::<Test>::PowerNotDelegated
+		);
+		assert_eq!(IcoTest::usable_power_of(&Bob), 275 * DOLLARS);
+		assert_eq!(IcoTest::power_delegators_of(&DAVE), vec![Alice]);
+	});
+}
+
+#[test]
+fn power_weighted_reward_should_work() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		assert_ok!(IcoTest::initiate_ico(Origin::signed(Alice), ico_parameters()));
+		assert_ok!(IcoTest::permit_ico(Origin::root(), KSM));
+		System::set_block_number(Ico::<Test>::get(KSM, 1).unwrap().start_time.unwrap());
+		assert_ok!(IcoTest::join(
+			Origin::signed(Bob),
+			KSM,
+			1,
+			200 * DOLLARS,
+			None,
+			None,
+			None,
+			None
+		));
+		let usdt = UnReleaseAssets::<Test>::get(Bob)[0].total_usdt;
+		assert_eq!(IcoTest::usable_power_of(&Bob), usdt);
+		let base = IcoTest::get_reward_amount(Bob, KSM, 1);
+		assert!(base > 0);
+
+		// The reward is raised by the boost times power / (power + usdt).
+		PowerRewardBoost::set(Percent::from_percent(30u8));
+		let boost = Percent::from_percent(30u8) * base;
+		assert_eq!(IcoTest::get_reward_amount(Bob, KSM, 1), base + boost / 2);
+
+		// The delegated power counts too.
+		assert_ok!(<IcoTest as PowerHandler<_, _, _>>::add_user_power(&DAVE, usdt));
+		assert_ok!(IcoTest::delegate_power(Origin::signed(DAVE), Bob));
+		assert_eq!(IcoTest::get_reward_amount(Bob, KSM, 1), base + boost * 2 / 3);
+
+		// The user that delegates its power away gets no boost.
+		assert_ok!(IcoTest::revoke_power_delegation(Origin::signed(DAVE)));
+		assert_ok!(IcoTest::delegate_power(Origin::signed(Bob), Alice));
+		assert_eq!(IcoTest::get_reward_amount(Bob, KSM, 1), base);
+
+		assert_ok!(IcoTest::revoke_power_delegation(Origin::signed(Bob)));
+		let free = Balances::free_balance(Bob);
+		assert_ok!(IcoTest::get_reward(Origin::signed(Bob), KSM, 1, None));
+		assert_eq!(Balances::free_balance(Bob), free + base + boost / 2);
+	});
+}
+
+#[test]
+fn ico_liquidity_should_work() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		let mut info = IcoParameters {
+			liquidity: Some(LiquidityParameters {
+				proportion: Percent::from_percent(0u8),
+				lock_duration: 100,
+			}),
+			..ico_parameters()
+		};
+		assert_noop!(
+			IcoTest::initiate_ico(Origin::signed(Alice), info.clone()),
+			Error::<Test>::InvalidLiquidity
+		);
+		info.liquidity = Some(LiquidityParameters {
+			proportion: Percent::from_percent(20u8),
+			lock_duration: 100,
+		});
+		assert_ok!(IcoTest::initiate_ico(Origin::signed(Alice), info));
+		// The project tokens added to the amm are reserved with the ico amount.
+		assert_eq!(Tokens::reserved_balance(KSM, &Alice), 1200 * DOLLARS);
+		assert_ok!(IcoTest::permit_ico(Origin::root(), KSM));
+
+		let start_time = Ico::<Test>::get(KSM, 1).unwrap().start_time.unwrap();
+		System::set_block_number(start_time);
+		assert_ok!(IcoTest::join(
+			Origin::signed(Bob),
+			KSM,
+			1,
+			200 * DOLLARS,
+			None,
+			None,
+			None,
+			None
+		));
+
+		let release_time = start_time + NewDAYS;
+		System::set_block_number(release_time);
+		assert_ok!(IcoTest::request_release(
+			Origin::signed(Alice),
+			KSM,
+			1,
+			Percent::from_percent(100u8)
+		));
+		assert_ok!(IcoTest::permit_release(Origin::root(), KSM, 1));
+		assert_ok!(IcoTest::user_release_ico_amount(Origin::signed(Bob), KSM, 1, None));
+
+		// 20% of the raised tokens and of the sold tokens are added to the amm.
+		let liquidity = IcoTest::ico_liquidity_of(KSM, 1).unwrap();
+		assert_eq!(liquidity.exchange_token_amount, 40 * DOLLARS);
+		assert_eq!(liquidity.project_token_amount, 40 * DOLLARS);
+		assert_eq!(liquidity.unlock_block, release_time + 100);
+		assert!(liquidity.liquidity_amount > 0);
+		assert_eq!(
+			Tokens::reserved_balance(liquidity.liquidity_id, &Alice),
+			liquidity.liquidity_amount
+		);
+		assert_eq!(Tokens::reserved_balance(DOT, &Alice), 160 * DOLLARS);
+
+		// The initiator only releases the exchange tokens that are not in the amm.
+		assert_ok!(IcoTest::user_release_ico_amount(Origin::signed(Alice), KSM, 1, None));
+		assert_eq!(Tokens::reserved_balance(DOT, &Alice), 0);
+
+		assert_noop!(
+			IcoTest::unlock_ico_liquidity(Origin::signed(Bob), KSM, 1),
+			Error::<Test>::NotInitiator
+		);
+		assert_noop!(
+			IcoTest::unlock_ico_liquidity(Origin::signed(Alice), KSM, 1),
+			Error::<Test>::IcoLiquidityLocked
+		);
+		System::set_block_number(release_time + 100);
+		assert_ok!(IcoTest::unlock_ico_liquidity(Origin::signed(Alice), KSM, 1));
+		assert_eq!(Tokens::reserved_balance(liquidity.liquidity_id, &Alice), 0);
+		assert_eq!(
+			Tokens::free_balance(liquidity.liquidity_id, &Alice),
+			liquidity.liquidity_amount
+		);
+		assert_noop!(
+			IcoTest::unlock_ico_liquidity(Origin::signed(Alice), KSM, 1),
+			Error::<Test>::IcoLiquidityNotExists
+		);
+	});
+}
+
+#[test]
+fn release_request_expiry_should_work() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		let info = ico_parameters();
+		assert_ok!(IcoTest::initiate_ico(Origin::signed(Alice), info));
+		assert!(IcoTest::pending_ico(KSM).is_some());
+		assert_ok!(IcoTest::permit_ico(Origin::root(), KSM));
+		assert!(IcoTest::pending_ico(KSM).is_none());
+
+		let start_time = Ico::<Test>::get(KSM, 1).unwrap().start_time.unwrap();
+		System::set_block_number(start_time);
+		assert_ok!(IcoTest::join(
+			Origin::signed(Bob),
+			KSM,
+			1,
+			200 * DOLLARS,
+			None,
+			None,
+			None,
+			None
+		));
+
+		let release_time = start_time + NewDAYS;
+		System::set_block_number(release_time);
+		let reserved = Balances::reserved_balance(Alice);
+		assert_ok!(IcoTest::request_release(
+			Origin::signed(Alice),
+			KSM,
+			1,
+			Percent::from_percent(10u8)
+		));
+		assert_eq!(Balances::reserved_balance(Alice), reserved + DOLLARS);
+		assert_eq!(
+			IcoTest::request_expiry_queue(release_time + 100).into_inner(),
+			vec![(KSM, 1)]
+		);
+
+		// The request made again after canceling expires at its own time.
+		System::set_block_number(release_time + 10);
+		assert_ok!(IcoTest::cancel_request(Origin::signed(Alice), KSM, 1));
+		let reserved = Balances::reserved_balance(Alice);
+		assert_ok!(IcoTest::request_release(
+			Origin::signed(Alice),
+			KSM,
+			1,
+			Percent::from_percent(10u8)
+		));
+		IcoTest::on_initialize(release_time + 100);
+		assert!(IcoTest::release_info(KSM, 1).is_some());
+		assert_eq!(IcoTest::request_expiry_queue(release_time + 100).len(), 0);
+
+		IcoTest::on_initialize(release_time + 110);
+		assert!(IcoTest::release_info(KSM, 1).is_none());
+		assert_eq!(Balances::reserved_balance(Alice), reserved);
+
+		// The expiries of a full block are put into the next block.
+		for index in 0..10 {
+			assert_ok!(IcoTest::schedule_request_expiry(DOT, index, 1000));
+		}
+		assert_ok!(IcoTest::schedule_request_expiry(DOT, 10, 1000));
+		assert_eq!(IcoTest::request_expiry_queue(1000).len(), 10);
+		assert_eq!(IcoTest::request_expiry_queue(1001).into_inner(), vec![(DOT, 10)]);
+	});
+}
+
+#[test]
+fn ico_receipts_should_work() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		let info = IcoParameters {
+			mint_receipts: true,
+			..ico_parameters()
+		};
+		assert_ok!(IcoTest::initiate_ico(Origin::signed(Alice), info));
+		assert_ok!(IcoTest::permit_ico(Origin::root(), KSM));
+
+		let start_time = Ico::<Test>::get(KSM, 1).unwrap().start_time.unwrap();
+		System::set_block_number(start_time);
+		assert_ok!(IcoTest::join(
+			Origin::signed(Bob),
+			KSM,
+			1,
+			200 * DOLLARS,
+			None,
+			None,
+			None,
+			None
+		));
+		let receipt = IcoTest::receipt_of((KSM, 1), Bob).unwrap();
+		assert_eq!(IcoTest::receipt_position_of(receipt), Some((KSM, 1, Bob)));
+		assert_eq!(MockReceipts::owner_of(&receipt), Some(Bob));
+		assert_eq!(MockReceipts::unreleased_of(receipt), 200 * DOLLARS);
+
+		// The participation is controlled by the owner of the receipt after it is transferred.
+		MockReceipts::transfer(receipt, DAVE);
+		assert_noop!(
+			IcoTest::join(Origin::signed(Bob), KSM, 1, 100 * DOLLARS, None, None, None, None),
+			Error::<Test>::NotReceiptOwner
+		);
+
+		let release_time = start_time + NewDAYS;
+		System::set_block_number(release_time);
+		assert_ok!(IcoTest::request_release(
+			Origin::signed(Alice),
+			KSM,
+			1,
+			Percent::from_percent(100u8)
+		));
+		assert_ok!(IcoTest::permit_release(Origin::root(), KSM, 1));
+
+		assert_noop!(
+			IcoTest::user_release_ico_amount(Origin::signed(Bob), KSM, 1, None),
+			Error::<Test>::NotReceiptOwner
+		);
+		assert_noop!(
+			IcoTest::user_release_ico_amount(Origin::signed(Bob), KSM, 1, Some(receipt)),
+			Error::<Test>::NotReceiptOwner
+		);
+		assert_noop!(
+			IcoTest::user_release_ico_amount(Origin::signed(DAVE), KSM, 2, Some(receipt)),
+			Error::<Test>::ReceiptNotMatchIco
+		);
+		let bob_ksm = Tokens::free_balance(KSM, &Bob);
+		assert_ok!(IcoTest::user_release_ico_amount(
+			Origin::signed(DAVE),
+			KSM,
+			1,
+			Some(receipt)
+		));
+		assert_eq!(Tokens::free_balance(KSM, &DAVE), 200 * DOLLARS);
+		assert_eq!(Tokens::free_balance(KSM, &Bob), bob_ksm);
+		assert_eq!(Tokens::reserved_balance(KSM, &Bob), 0);
+		assert_eq!(MockReceipts::unreleased_of(receipt), 0);
+
+		let reward = IcoTest::get_reward_amount(Bob, KSM, 1);
+		assert!(reward > 0);
+		assert_noop!(
+			IcoTest::get_reward(Origin::signed(Bob), KSM, 1, None),
+			Error::<Test>::NotReceiptOwner
+		);
+		assert_ok!(IcoTest::get_reward(Origin::signed(DAVE), KSM, 1, Some(receipt)));
+		assert_eq!(Balances::free_balance(DAVE), reward);
+	});
+}
+
+#[test]
+fn ico_receipts_with_vesting_should_work() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		let info = IcoParameters {
+			mint_receipts: true,
+			lock_proportion: Percent::from_percent(50u8),
+			vesting: Some(VestingParameters {
+				cliff: 10,
+				period: 10,
+				period_count: 2,
+			}),
+			..ico_parameters()
+		};
+		assert_ok!(IcoTest::initiate_ico(Origin::signed(Alice), info));
+		assert_ok!(IcoTest::permit_ico(Origin::root(), KSM));
+
+		let start_time = Ico::<Test>::get(KSM, 1).unwrap().start_time.unwrap();
+		System::set_block_number(start_time);
+		assert_ok!(IcoTest::join(
+			Origin::signed(Bob),
+			KSM,
+			1,
+			200 * DOLLARS,
+			None,
+			None,
+			None,
+			None
+		));
+		let total = Tokens::reserved_balance(KSM, &Bob);
+		let receipt = IcoTest::receipt_of((KSM, 1), Bob).unwrap();
+		MockReceipts::transfer(receipt, DAVE);
+
+		let release_time = start_time + NewDAYS;
+		System::set_block_number(release_time);
+		assert_ok!(IcoTest::request_release(
+			Origin::signed(Alice),
+			KSM,
+			1,
+			Percent::from_percent(100u8)
+		));
+		assert_ok!(IcoTest::permit_release(Origin::root(), KSM, 1));
+
+		// The locked tokens vest on the account of the holder of the receipt.
+		let bob_ksm = Tokens::free_balance(KSM, &Bob);
+		assert_ok!(IcoTest::user_release_ico_amount(
+			Origin::signed(DAVE),
+			KSM,
+			1,
+			Some(receipt)
+		));
+		let locked = Percent::from_percent(50u8) * total;
+		assert_eq!(Tokens::free_balance(KSM, &DAVE), total);
+		assert_eq!(Tokens::accounts(DAVE, KSM).frozen, locked);
+		assert_eq!(Tokens::free_balance(KSM, &Bob), bob_ksm);
+		assert_eq!(Tokens::reserved_balance(KSM, &Bob), 0);
+		assert_eq!(Tokens::accounts(Bob, KSM).frozen, 0);
+		assert!(IcoTest::vesting_schedules(Bob, KSM).is_empty());
+
+		// The holder unlocks the tokens as they vest.
+		System::set_block_number(release_time + 20);
+		assert_ok!(IcoTest::unlock(Origin::signed(DAVE), KSM, 1, Some(receipt)));
+		assert_eq!(Tokens::accounts(DAVE, KSM).frozen, locked / 2);
+		System::set_block_number(release_time + 30);
+		assert_ok!(IcoTest::unlock(Origin::signed(DAVE), KSM, 1, Some(receipt)));
+		assert_eq!(Tokens::accounts(DAVE, KSM).frozen, 0);
+		assert_ok!(Tokens::transfer(Origin::signed(DAVE), Alice, KSM, total));
+	});
+}
+
+#[test]
+fn area_and_kyc_level_caps_should_work() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		let mut info = IcoParameters {
+			area_caps: vec![(AreaCode::AF, 700 * DOLLARS), (AreaCode::AF, 100 * DOLLARS)],
+			kyc_level_caps: vec![(KycLevel::Full, 800 * DOLLARS), (KycLevel::Unverified, 0)],
+			..ico_parameters()
+		};
+		assert_noop!(
+			IcoTest::initiate_ico(Origin::signed(Alice), info.clone()),
+			Error::<Test>::InvalidAreaCaps
+		);
+		info.area_caps = vec![(AreaCode::AF, 700 * DOLLARS)];
+		info.kyc_level_caps.push((KycLevel::Full, 100 * DOLLARS));
+		assert_noop!(
+			IcoTest::initiate_ico(Origin::signed(Alice), info.clone()),
+			Error::<Test>::InvalidKycLevelCaps
+		);
+		info.kyc_level_caps.pop();
+		assert_ok!(IcoTest::initiate_ico(Origin::signed(Alice), info));
+		assert_ok!(IcoTest::permit_ico(Origin::root(), KSM));
+
+		MockKyc::set_kyc(Bob, AreaCode::AF, KycLevel::Full);
+		MockKyc::set_kyc(DAVE, AreaCode::AF, KycLevel::Basic);
+		assert_ok!(Currencies::transfer(Origin::signed(Bob), DAVE, DOT, 1000 * DOLLARS));
+		let start_time = Ico::<Test>::get(KSM, 1).unwrap().start_time.unwrap();
+		System::set_block_number(start_time);
+
+		// The unverified users have no allocation.
+		assert_noop!(
+			IcoTest::join(Origin::signed(4), KSM, 1, 100 * DOLLARS, None, None, None, None),
+			Error::<Test>::AmountNotMeetProjectRequirement
+		);
+		// The fully verified users join more than `user_max_amount`.
+		assert_ok!(IcoTest::join(
+			Origin::signed(Bob),
+			KSM,
+			1,
+			600 * DOLLARS,
+			None,
+			None,
+			None,
+			None
+		));
+		assert_eq!(IcoTest::area_raised_of((KSM, 1), AreaCode::AF), 600 * DOLLARS);
+		assert_noop!(
+			IcoTest::join(Origin::signed(DAVE), KSM, 1, 200 * DOLLARS, None, None, None, None),
+			Error::<Test>::AreaCapExceeded
+		);
+		assert_ok!(IcoTest::join(
+			Origin::signed(DAVE),
+			KSM,
+			1,
+			100 * DOLLARS,
+			None,
+			None,
+			None,
+			None
+		));
+		assert_eq!(IcoTest::area_raised_of((KSM, 1), AreaCode::AF), 700 * DOLLARS);
+	});
+}
+
+#[test]
+fn lottery_should_work() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		let mut info = IcoParameters {
+			mode: IcoMode::Lottery,
+			..ico_parameters()
+		};
+		assert_noop!(
+			IcoTest::initiate_ico(Origin::signed(Alice), info.clone()),
+			Error::<Test>::InvalidLottery
+		);
+		info.lottery = Some(LotteryParameters {
+			registration_duration: 100,
+			ticket_power: 100 * DOLLARS,
+			max_tickets_per_user: 2,
+			winner_count: 1,
+		});
+		assert_ok!(IcoTest::initiate_ico(Origin::signed(Alice), info));
+		assert_ok!(IcoTest::permit_ico(Origin::root(), KSM));
+		assert_noop!(
+			IcoTest::register_lottery(Origin::signed(Bob), KSM, 1),
+			Error::<Test>::NotInLotteryRegistration
+		);
+
+		// The random seed is taken when the registration ends.
+		let start_time = Ico::<Test>::get(KSM, 1).unwrap().start_time.unwrap();
+		assert_eq!(IcoTest::lottery_seed_queue(start_time + 100).to_vec(), vec![(KSM, 1)]);
+		System::set_block_number(start_time);
+		assert_ok!(<IcoTest as PowerHandler<_, _, _>>::add_user_power(&Bob, 300 * DOLLARS));
+		assert_ok!(<IcoTest as PowerHandler<_, _, _>>::add_user_power(&DAVE, 100 * DOLLARS));
+		assert_noop!(
+			IcoTest::register_lottery(Origin::signed(Alice), KSM, 1),
+			Error::<Test>::InitiatorIsYourself
+		);
+		assert_noop!(
+			IcoTest::register_lottery(Origin::signed(4), KSM, 1),
+			Error::<Test>::NoLotteryTickets
+		);
+		// The tickets are capped by `max_tickets_per_user`.
+		assert_ok!(IcoTest::register_lottery(Origin::signed(Bob), KSM, 1));
+		assert_noop!(
+			IcoTest::register_lottery(Origin::signed(Bob), KSM, 1),
+			Error::<Test>::AlreadyRegisteredLottery
+		);
+		assert_ok!(IcoTest::register_lottery(Origin::signed(DAVE), KSM, 1));
+		assert_eq!(IcoTest::lottery_entrants_of(KSM, 1), vec![(Bob, 2), (DAVE, 1)]);
+		assert_eq!(IcoTest::lottery_of(KSM, 1).unwrap().total_tickets, 3);
+
+		assert_noop!(
+			IcoTest::join(Origin::signed(Bob), KSM, 1, 200 * DOLLARS, None, None, None, None),
+			Error::<Test>::LotteryNotDrawn
+		);
+		assert_noop!(
+			IcoTest::draw_lottery(Origin::signed(Alice), KSM, 1),
+			Error::<Test>::LotteryRegistrationNotEnd
+		);
+
+		System::set_block_number(start_time + 100);
+		assert_noop!(
+			IcoTest::register_lottery(Origin::signed(Bob), KSM, 1),
+			Error::<Test>::NotInLotteryRegistration
+		);
+		// The seed is taken in the next block when the randomness is not available.
+		MockRandomness::set_available(false);
+		IcoTest::on_initialize(start_time + 100);
+		assert_eq!(IcoTest::lottery_of(KSM, 1).unwrap().random_seed, None);
+		assert_noop!(
+			IcoTest::draw_lottery(Origin::signed(Alice), KSM, 1),
+			Error::<Test>::LotteryRandomnessNotExists
+		);
+		MockRandomness::set_available(true);
+		System::set_block_number(start_time + 101);
+		IcoTest::on_initialize(start_time + 101);
+		assert!(IcoTest::lottery_of(KSM, 1).unwrap().random_seed.is_some());
+		assert!(IcoTest::lottery_seed_queue(start_time + 101).is_empty());
+
+		assert_ok!(IcoTest::draw_lottery(Origin::signed(Alice), KSM, 1));
+		assert_noop!(
+			IcoTest::draw_lottery(Origin::signed(Alice), KSM, 1),
+			Error::<Test>::LotteryAlreadyDrawn
+		);
+		assert!(IcoTest::lottery_of(KSM, 1).unwrap().is_drawn);
+
+		let (winner, loser) = if IcoTest::is_lottery_winner((KSM, 1), Bob) {
+			(Bob, DAVE)
+		} else {
+			(DAVE, Bob)
+		};
+		assert!(!IcoTest::is_lottery_winner((KSM, 1), loser));
+		assert_noop!(
+			IcoTest::join(Origin::signed(loser), KSM, 1, 200 * DOLLARS, None, None, None, None),
+			Error::<Test>::NotLotteryWinner
+		);
+		assert_ok!(IcoTest::join(
+			Origin::signed(winner),
+			KSM,
+			1,
+			200 * DOLLARS,
+			None,
+			None,
+			None,
+			None
+		));
+		// The winners join like the first-come-first-served ico.
+		assert!(Tokens::reserved_balance(KSM, &winner) > 0);
+		assert_noop!(
+			IcoTest::claim_allocation(Origin::signed(winner), KSM, 1),
+			Error::<Test>::NotProRataIco
+		);
+	});
+}
+
+#[test]
+fn lottery_should_be_seeded_when_drawn() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		let info = IcoParameters {
+			mode: IcoMode::Lottery,
+			lottery: Some(LotteryParameters {
+				registration_duration: 100,
+				ticket_power: 100 * DOLLARS,
+				max_tickets_per_user: 2,
+				winner_count: 1,
+			}),
+			..ico_parameters()
+		};
+		assert_ok!(IcoTest::initiate_ico(Origin::signed(Alice), info));
+		assert_ok!(IcoTest::permit_ico(Origin::root(), KSM));
+		let end = Ico::<Test>::get(KSM, 1).unwrap().start_time.unwrap() + 100;
+
+		// The blocks after the registration are full.
+		for index in 0..100 {
+			assert_ok!(IcoTest::schedule_lottery_seed(DOT, index, end + 1));
+		}
+		System::set_block_number(end);
+		MockRandomness::set_available(false);
+		IcoTest::on_initialize(end);
+		assert_eq!(IcoTest::lottery_of(KSM, 1).unwrap().random_seed, None);
+		assert!((end + 1..=end + 10).all(|n| !IcoTest::lottery_seed_queue(n).contains(&(KSM, 1))));
+		System::assert_last_event(crate::Event::<Test>::LotterySeedDeferred(KSM, 1).into());
+
+		// The draw takes the seed.
+		assert_noop!(
+			IcoTest::draw_lottery(Origin::signed(Bob), KSM, 1),
+			Error::<Test>::LotteryRandomnessNotExists
+		);
+		MockRandomness::set_available(true);
+		assert_ok!(IcoTest::draw_lottery(Origin::signed(Bob), KSM, 1));
+		let lottery = IcoTest::lottery_of(KSM, 1).unwrap();
+		assert!(lottery.is_drawn);
+		assert!(lottery.random_seed.is_some());
+	});
+}
+
+#[test]
+fn protection_fund_should_work() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		let info = ico_parameters();
+		assert_ok!(IcoTest::initiate_ico(Origin::signed(Alice), info));
+		assert_ok!(IcoTest::permit_ico(Origin::root(), KSM));
+		System::set_block_number(Ico::<Test>::get(KSM, 1).unwrap().start_time.unwrap());
+		assert_ok!(IcoTest::join(
+			Origin::signed(Bob),
+			KSM,
+			1,
+			200 * DOLLARS,
+			None,
+			None,
+			None,
+			None
+		));
+		assert_ok!(IcoTest::join(
+			Origin::signed(DAVE),
+			KSM,
+			1,
+			300 * DOLLARS,
+			None,
+			None,
+			None,
+			None
+		));
+
+		let fund = IcoTest::protection_fund_account();
+		assert_ok!(Tokens::transfer(Origin::signed(DAVE), fund, DOT, 100 * DOLLARS));
+		assert_noop!(
+			IcoTest::terminate_fraud_ico(Origin::signed(Bob), KSM, 1, 100 * DOLLARS),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			IcoTest::terminate_fraud_ico(Origin::root(), KSM, 1, 200 * DOLLARS),
+			Error::<Test>::ProtectionFundNotEnough
+		);
+		assert_eq!(IcoTest::initiator_pledge_of(KSM, 1), DOLLARS);
+		assert_eq!(Balances::free_balance(fund), 0);
+		let reserved = Balances::reserved_balance(Alice);
+		assert_ok!(IcoTest::terminate_fraud_ico(Origin::root(), KSM, 1, 100 * DOLLARS));
+		assert_noop!(
+			IcoTest::terminate_fraud_ico(Origin::root(), KSM, 1, 0),
+			Error::<Test>::AlreadyFraudIco
+		);
+		// The pledge of the initiator is slashed to the fund.
+		assert!(Ico::<Test>::get(KSM, 1).unwrap().is_terminated);
+		assert_eq!(IcoTest::initiator_pledge_of(KSM, 1), 0);
+		assert_eq!(Balances::reserved_balance(Alice), reserved - DOLLARS);
+		assert_eq!(Balances::free_balance(fund), DOLLARS);
+		assert_eq!(IcoTest::protection_fund_committed(DICO), DOLLARS);
+		assert_eq!(IcoTest::ico_compensation_of(KSM, 1).unwrap().pledge, DOLLARS);
+		assert_eq!(
+			IcoTest::ico_compensation_of(KSM, 1).unwrap().total_unreleased,
+			500 * DOLLARS
+		);
+		assert_eq!(IcoTest::total_allocation_of(KSM, 1), 500 * DOLLARS);
+
+		assert_noop!(
+			IcoTest::claim_compensation(Origin::signed(Alice), KSM, 1),
+			Error::<Test>::InitiatorIsYourself
+		);
+		assert_noop!(
+			IcoTest::claim_compensation(Origin::signed(4), KSM, 1),
+			Error::<Test>::NotIcoMember
+		);
+		// The compensation is pro-rata to the unreleased amount.
+		let dot = Tokens::free_balance(DOT, &Bob);
+		assert_ok!(IcoTest::claim_compensation(Origin::signed(Bob), KSM, 1));
+		assert_eq!(Tokens::free_balance(DOT, &Bob), dot + 40 * DOLLARS);
+		assert_noop!(
+			IcoTest::claim_compensation(Origin::signed(Bob), KSM, 1),
+			Error::<Test>::CompensationAlreadyClaimed
+		);
+		assert_ok!(IcoTest::claim_compensation(Origin::signed(DAVE), KSM, 1));
+		assert_eq!(Tokens::free_balance(DOT, &fund), 0);
+		assert_eq!(Balances::free_balance(fund), 0);
+		assert_eq!(IcoTest::protection_fund_committed(DOT), 0);
+		assert_eq!(IcoTest::protection_fund_committed(DICO), 0);
+	});
+}
+
+#[test]
+fn reward_economics_and_pool_should_work() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		Currencies::transfer(Origin::signed(Bob), DAVE, DOT, 1000 * DOLLARS).unwrap();
+
+		let economics = RewardEconomics {
+			total_reward: 4_0000_0000 * USD,
+			inviter_reward_proportion: Percent::from_percent(20u8),
+			invitee_reward_proportion: Percent::from_percent(5u8),
+			initiator_pledge: 2 * DOLLARS,
+		};
+		assert_noop!(
+			IcoTest::set_reward_economics(Origin::signed(Alice), economics.clone()),
+			DispatchError::BadOrigin
+		);
+		assert_eq!(IcoTest::reward_economics().initiator_pledge, DOLLARS);
+		assert_ok!(IcoTest::set_reward_economics(Origin::root(), economics.clone()));
+		assert_noop!(
+			IcoTest::set_reward_economics(Origin::root(), economics.clone()),
+			Error::<Test>::DuplicateSet
+		);
+		assert_eq!(IcoTest::reward_economics(), economics);
+
+		let info = ico_parameters();
+		assert_ok!(IcoTest::initiate_ico(Origin::signed(Alice), info));
+		assert_eq!(IcoTest::pending_ico(KSM).unwrap().pledge_dico, 2 * DOLLARS);
+		assert_ok!(IcoTest::permit_ico(Origin::root(), KSM));
+
+		let start_time = Ico::<Test>::get(KSM, 1).unwrap().start_time.unwrap();
+		System::set_block_number(start_time);
+		assert_ok!(IcoTest::join(
+			Origin::signed(DAVE),
+			KSM,
+			1,
+			100 * DOLLARS,
+			None,
+			None,
+			None,
+			None
+		));
+		assert_ok!(IcoTest::join(
+			Origin::signed(Bob),
+			KSM,
+			1,
+			300 * DOLLARS,
+			None,
+			None,
+			None,
+			None
+		));
+		// The user that got the DICO reward before the pool is funded gets its share later.
+		assert_ok!(IcoTest::get_reward(Origin::signed(DAVE), KSM, 1, None));
+		assert_noop!(
+			IcoTest::get_pool_reward(Origin::signed(DAVE), KSM, 1, None),
+			Error::<Test>::RewardPoolNotExists
+		);
+
+		assert_noop!(
+			IcoTest::fund_reward_pool(Origin::signed(Bob), KSM, 1, 100 * DOLLARS),
+			Error::<Test>::NotInitiator
+		);
+		assert_noop!(
+			IcoTest::fund_reward_pool(Origin::signed(Alice), KSM, 1, 0),
+			Error::<Test>::AmountIsZero
+		);
+		assert_ok!(IcoTest::fund_reward_pool(Origin::signed(Alice), KSM, 1, 100 * DOLLARS));
+
+		// The participants get the pool in proportion to their usdt.
+		let bob_ksm = Tokens::free_balance(KSM, &Bob);
+		assert_ok!(IcoTest::get_reward(Origin::signed(Bob), KSM, 1, None));
+		assert_eq!(Tokens::free_balance(KSM, &Bob), bob_ksm + 75 * DOLLARS);
+		assert_noop!(
+			IcoTest::get_pool_reward(Origin::signed(Bob), KSM, 1, None),
+			Error::<Test>::PoolRewardAlreadyClaimed
+		);
+		assert_noop!(
+			IcoTest::reclaim_reward_pool(Origin::signed(Alice), KSM, 1),
+			Error::<Test>::RewardPoolClaimNotEnd
+		);
+
+		assert_noop!(
+			IcoTest::get_reward(Origin::signed(DAVE), KSM, 1, None),
+			Error::<Test>::AlreadyGetReward
+		);
+		let dave_ksm = Tokens::free_balance(KSM, &DAVE);
+		assert_ok!(IcoTest::get_pool_reward(Origin::signed(DAVE), KSM, 1, None));
+		assert_eq!(Tokens::free_balance(KSM, &DAVE), dave_ksm + 25 * DOLLARS);
+		assert_noop!(
+			IcoTest::get_pool_reward(Origin::signed(DAVE), KSM, 1, None),
+			Error::<Test>::PoolRewardAlreadyClaimed
+		);
+		assert_eq!(
+			IcoTest::reward_pool_of(KSM, 1),
+			Some(RewardPool {
+				total: 100 * DOLLARS,
+				claimed: 100 * DOLLARS,
+				claimed_usdt: Ico::<Test>::get(KSM, 1).unwrap().total_usdt,
+			})
+		);
+
+		// The pool is reclaimed when all the participants have got their share.
+		assert_noop!(
+			IcoTest::reclaim_reward_pool(Origin::signed(Bob), KSM, 1),
+			Error::<Test>::NotInitiator
+		);
+		assert_ok!(IcoTest::reclaim_reward_pool(Origin::signed(Alice), KSM, 1));
+		assert_eq!(IcoTest::reward_pool_of(KSM, 1), None);
+
+		System::set_block_number(start_time + NewDAYS + 1);
+		assert_noop!(
+			IcoTest::fund_reward_pool(Origin::signed(Alice), KSM, 1, 100 * DOLLARS),
+			Error::<Test>::IcoExpire
+		);
+	});
+}
+
+#[test]
+fn total_allocation_migration_should_work() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		let info = ico_parameters();
+		assert_ok!(IcoTest::initiate_ico(Origin::signed(Alice), info));
+		assert_ok!(IcoTest::permit_ico(Origin::root(), KSM));
+		System::set_block_number(Ico::<Test>::get(KSM, 1).unwrap().start_time.unwrap());
+		assert_ok!(IcoTest::join(
+			Origin::signed(Bob),
+			KSM,
+			1,
+			200 * DOLLARS,
+			None,
+			None,
+			None,
+			None
+		));
+		assert_eq!(IcoTest::total_allocation_of(KSM, 1), 200 * DOLLARS);
+
+		// The participants of the icoes before the upgrade are summed.
+		TotalAllocationOf::<Test>::remove(KSM, 1);
+		frame_support::traits::StorageVersion::new(2).put::<IcoTest>();
+		migrations::v3::migrate::<Test>();
+		assert_eq!(IcoTest::total_allocation_of(KSM, 1), 200 * DOLLARS);
+		assert_eq!(frame_support::traits::StorageVersion::get::<IcoTest>(), 3);
+	});
+}
+
+#[test]
+fn power_checkpoint_migration_should_work() {
+	new_test_ext().execute_with(|| {
+		frame_support::traits::StorageVersion::new(1).put::<IcoTest>();
+		TotalPowerOf::<Test>::insert(Alice, 100 * DOLLARS);
+		TotalPowerOf::<Test>::insert(Bob, 100 * DOLLARS);
+		PowerCheckpointOf::<Test>::insert(Bob, 5);
+		System::set_block_number(1_000_000);
+
+		migrations::v2::migrate::<Test>();
+		// The power gained before the upgrade starts to decay from it.
+		assert_eq!(PowerCheckpointOf::<Test>::get(Alice), 1_000_000);
+		assert_eq!(IcoTest::power_of(&Alice), 100 * DOLLARS);
+		assert_eq!(PowerCheckpointOf::<Test>::get(Bob), 5);
+		assert_eq!(frame_support::traits::StorageVersion::get::<IcoTest>(), 2);
+	});
+}
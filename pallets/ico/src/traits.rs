@@ -13,8 +13,9 @@ pub trait IcoHandler<CurrencyId, MulBalanceOf, AccountId, DispathErr, BlockNumbe
 	fn get_user_total_amount(currency_id: CurrencyId, index: u32, who: &AccountId) -> MulBalanceOf;
 	fn get_project_total_ico_amount(currency_id: CurrencyId, index: u32) -> result::Result<MulBalanceOf, DispathErr>;
 }
+use dico_primitives::{AssetId, Balance};
 use frame_support::Parameter;
-use sp_runtime::DispatchResult;
+use sp_runtime::{DispatchError, DispatchResult, Percent};
 // pub use dico_primitives::{Balance, AccountId};
 
 impl<
@@ -64,3 +65,94 @@ impl<AccountId: Default + Parameter, Balance: Default + Parameter> PowerHandler<
 		Ok(())
 	}
 }
+
+pub trait IcoProposalHandler<CurrencyId> {
+	/// Propose to permit the release of the ico funds, the ico members vote with `threshold`.
+	fn propose_permit_release(
+		currency_id: CurrencyId,
+		index: u32,
+		threshold: Percent,
+		reason: Vec<u8>,
+	) -> DispatchResult;
+}
+
+impl<CurrencyId> IcoProposalHandler<CurrencyId> for () {
+	fn propose_permit_release(_: CurrencyId, _: u32, _: Percent, _: Vec<u8>) -> DispatchResult {
+		Ok(())
+	}
+}
+
+pub trait LiquidityHandler<AccountId, CurrencyId, Balance> {
+	/// Add the liquidity of the pair at the price of the amounts, returns the liquidity asset and
+	/// the liquidity minted.
+	fn add_liquidity(
+		who: &AccountId,
+		asset_a: CurrencyId,
+		asset_b: CurrencyId,
+		amount_a: Balance,
+		amount_b: Balance,
+	) -> result::Result<(CurrencyId, Balance), DispatchError>;
+}
+
+impl<AccountId, CurrencyId, Balance> LiquidityHandler<AccountId, CurrencyId, Balance> for () {
+	fn add_liquidity(
+		_: &AccountId,
+		_: CurrencyId,
+		_: CurrencyId,
+		_: Balance,
+		_: Balance,
+	) -> result::Result<(CurrencyId, Balance), DispatchError> {
+		Err(DispatchError::Other("no amm"))
+	}
+}
+
+impl<T: pallet_amm::Config> LiquidityHandler<T::AccountId, AssetId, Balance> for pallet_amm::Pallet<T> {
+	fn add_liquidity(
+		who: &T::AccountId,
+		asset_a: AssetId,
+		asset_b: AssetId,
+		amount_a: Balance,
+		amount_b: Balance,
+	) -> result::Result<(AssetId, Balance), DispatchError> {
+		pallet_amm::Pallet::<T>::do_add_liquidity(who, asset_a, asset_b, amount_a, amount_b, amount_a, amount_b)
+			.map_err(|e| e.error)
+	}
+}
+
+pub trait IcoReceiptHandler<AccountId, CurrencyId, Balance> {
+	type ReceiptId: Parameter;
+
+	/// Mint the receipt of the ico participation of `owner`, issued by the initiator of the ico.
+	fn mint_receipt(
+		issuer: &AccountId,
+		owner: &AccountId,
+		currency_id: CurrencyId,
+		index: u32,
+		unreleased: Balance,
+	) -> result::Result<Self::ReceiptId, DispatchError>;
+	/// Update the unreleased amount carried by the receipt.
+	fn update_receipt(receipt: &Self::ReceiptId, unreleased: Balance) -> DispatchResult;
+	fn owner_of(receipt: &Self::ReceiptId) -> Option<AccountId>;
+}
+
+impl<AccountId, CurrencyId, Balance> IcoReceiptHandler<AccountId, CurrencyId, Balance> for () {
+	type ReceiptId = ();
+
+	fn mint_receipt(
+		_: &AccountId,
+		_: &AccountId,
+		_: CurrencyId,
+		_: u32,
+		_: Balance,
+	) -> result::Result<Self::ReceiptId, DispatchError> {
+		Err(DispatchError::Other("no nft"))
+	}
+
+	fn update_receipt(_: &Self::ReceiptId, _: Balance) -> DispatchResult {
+		Ok(())
+	}
+
+	fn owner_of(_: &Self::ReceiptId) -> Option<AccountId> {
+		None
+	}
+}
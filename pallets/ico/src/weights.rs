@@ -36,6 +36,28 @@ pub trait WeightInfo {
 	fn initiator_set_ico_max_times() -> Weight;
 	fn get_reward() -> Weight;
 	fn set_asset_power_multiple() -> Weight;
+	fn refund() -> Weight;
+	fn claim_allocation() -> Weight;
+	fn join_with_payment_token() -> Weight;
+	fn release_payment_token() -> Weight;
+	fn register_invite_code() -> Weight;
+	fn bind_invite_code() -> Weight;
+	fn set_referral_reward_proportions() -> Weight;
+	fn cancel_ico() -> Weight;
+	fn amend_ico() -> Weight;
+	fn delegate_power() -> Weight;
+	fn revoke_power_delegation() -> Weight;
+	fn unlock_ico_liquidity() -> Weight;
+	fn register_lottery() -> Weight;
+	fn draw_lottery() -> Weight;
+	fn terminate_fraud_ico() -> Weight;
+	fn claim_compensation() -> Weight;
+	fn expire_requests(n: u32) -> Weight;
+	fn seed_lotteries(n: u32) -> Weight;
+	fn set_reward_economics() -> Weight;
+	fn fund_reward_pool() -> Weight;
+	fn get_pool_reward() -> Weight;
+	fn reclaim_reward_pool() -> Weight;
 }
 
 /// Weights for pallet_ico using the Substrate node and recommended hardware.
@@ -79,16 +101,28 @@ impl<T: frame_system::Config> WeightInfo for DicoWeight<T> {
 	// Storage: System Account (r:1 w:1)
 	// Storage: Ico IcoesOf (r:1 w:1)
 	// Storage: Ico TotalPowerOf (r:1 w:1)
+	// Storage: Ico PowerCheckpointOf (r:1 w:1)
+	// Storage: DicoOracle RawValues (r:1 w:0)
+	// Storage: AMM PriceObservations (r:1 w:0)
+	// Storage: AMM PriceCumulative (r:1 w:0)
+	// Storage: AMM Liquidity (r:1 w:0)
 	fn join() -> Weight {
 		Weight::from_ref_time(20_0000_0000)
 	}
 	// Storage: Ico Ico (r:1 w:1)
 	// Storage: Ico PassedIcoes (r:1 w:1)
+	// Storage: Ico MilestonesOf (r:1 w:0)
 	fn terminate_ico() -> Weight {
 		Weight::from_ref_time(20_0000_0000)
 	}
 	// Storage: Ico Ico (r:1 w:0)
 	// Storage: Ico RequestReleaseInfo (r:1 w:1)
+	// Storage: Ico RequestExpiryQueue (r:1 w:1)
+	// Storage: Ico MilestonesOf (r:1 w:0)
+	// Storage: Dao ProposalOf (r:1 w:1)
+	// Storage: Dao Proposals (r:1 w:1)
+	// Storage: Dao ProposalCount (r:1 w:1)
+	// Storage: Dao Voting (r:0 w:1)
 	fn request_release() -> Weight {
 		Weight::from_ref_time(20_0000_0000)
 	}
@@ -99,17 +133,26 @@ impl<T: frame_system::Config> WeightInfo for DicoWeight<T> {
 	}
 	// Storage: Ico Ico (r:1 w:1)
 	// Storage: Ico RequestReleaseInfo (r:1 w:1)
+	// Storage: Ico RequestExpiryQueue (r:1 w:1)
+	// Storage: Ico MilestonesOf (r:1 w:0)
 	fn permit_release() -> Weight {
 		Weight::from_ref_time(20_0000_0000)
 	}
 	// Storage: Ico Ico (r:1 w:0)
 	// Storage: Ico IsUnservePledge (r:1 w:1)
+	// Storage: Ico LiquidityOf (r:1 w:0)
+	// Storage: Ico IcoLiquidityOf (r:1 w:1)
 	// Storage: Ico UnReleaseAssets (r:2 w:1)
 	// Storage: System Account (r:1 w:1)
 	// Storage: Ico IcoLocks (r:1 w:1)
+	// Storage: Ico VestingOf (r:1 w:0)
+	// Storage: Ico VestingSchedules (r:1 w:1)
+	// Storage: Tokens Locks (r:1 w:1)
 	fn user_release_ico_amount() -> Weight {
 		Weight::from_ref_time(20_0000_0000)
 	}
+	// Storage: Ico VestingSchedules (r:1 w:1)
+	// Storage: Tokens Locks (r:1 w:1)
 	// Storage: Ico IcoLocks (r:1 w:1)
 	fn unlock() -> Weight {
 		Weight::from_ref_time(20_0000_0000)
@@ -133,6 +176,9 @@ impl<T: frame_system::Config> WeightInfo for DicoWeight<T> {
 	// Storage: Ico TotalUsdt (r:1 w:1)
 	// Storage: Ico UnReleaseAssets (r:1 w:1)
 	// Storage: System Account (r:1 w:1)
+	// Storage: Ico ReferralRewardProportions (r:1 w:0)
+	// Storage: Ico ReferrerOf (r:3 w:0)
+	// Storage: Ico InviteInfoOf (r:3 w:3)
 	fn get_reward() -> Weight {
 		Weight::from_ref_time(20_0000_0000)
 	}
@@ -140,6 +186,170 @@ impl<T: frame_system::Config> WeightInfo for DicoWeight<T> {
 	fn set_asset_power_multiple() -> Weight {
 		Weight::from_ref_time(20_0000_0000)
 	}
+	// Storage: Ico Ico (r:1 w:0)
+	// Storage: Ico SoftCapOf (r:1 w:0)
+	// Storage: Ico UnReleaseAssets (r:2 w:2)
+	// Storage: Tokens Accounts (r:4 w:4)
+	// Storage: Ico TotalPowerOf (r:1 w:1)
+	// Storage: Ico PowerCheckpointOf (r:1 w:1)
+	// Storage: Ico IsUnservePledge (r:1 w:1)
+	// Storage: Ico LiquidityOf (r:1 w:0)
+	// Storage: Ico IcoLiquidityOf (r:1 w:1)
+	// Storage: Ico InitiatorPledgeOf (r:1 w:1)
+	// Storage: Ico PassedIcoes (r:1 w:1)
+	fn refund() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico Ico (r:1 w:0)
+	// Storage: Ico IcoModeOf (r:1 w:0)
+	// Storage: Ico SoftCapOf (r:1 w:0)
+	// Storage: Ico AllocationClaimedOf (r:1 w:1)
+	// Storage: Ico UnReleaseAssets (r:2 w:1)
+	// Storage: Tokens Accounts (r:4 w:4)
+	fn claim_allocation() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico Ico (r:1 w:1)
+	// Storage: Ico PaymentTokensOf (r:1 w:0)
+	// Storage: Ico PaymentRaisedOf (r:1 w:1)
+	// Storage: Ico UserPaymentsOf (r:1 w:1)
+	// Storage: Ico UnReleaseAssets (r:1 w:1)
+	// Storage: Ico IcoesOf (r:1 w:1)
+	// Storage: Tokens Accounts (r:4 w:4)
+	// Storage: DicoOracle RawValues (r:2 w:0)
+	// Storage: AMM PriceObservations (r:2 w:0)
+	// Storage: AMM PriceCumulative (r:2 w:0)
+	// Storage: AMM Liquidity (r:2 w:0)
+	fn join_with_payment_token() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico Ico (r:1 w:0)
+	// Storage: Ico PaymentRaisedOf (r:1 w:1)
+	// Storage: Tokens Accounts (r:1 w:1)
+	fn release_payment_token() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico InviteCodeOf (r:1 w:1)
+	// Storage: Ico InviteCodeOwnerOf (r:1 w:1)
+	fn register_invite_code() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico InviteCodeOwnerOf (r:1 w:0)
+	// Storage: Ico ReferrerOf (r:4 w:1)
+	fn bind_invite_code() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico ReferralRewardProportions (r:0 w:1)
+	fn set_referral_reward_proportions() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico Ico (r:1 w:1)
+	// Storage: Ico InitiatorPledgeOf (r:1 w:1)
+	// Storage: Tokens Accounts (r:3 w:3)
+	// Storage: Ico Indexs (r:1 w:1)
+	// Storage: Ico PassedIcoes (r:1 w:1)
+	// Storage: Ico InitiatedIcoesOf (r:1 w:1)
+	fn cancel_ico() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico PendingIco (r:1 w:1)
+	// Storage: Ico VestingOf (r:0 w:1)
+	// Storage: Ico InitiatedIcoesOf (r:1 w:1)
+	fn amend_ico() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico PowerDelegateOf (r:2 w:1)
+	// Storage: Ico PowerDelegatorsOf (r:2 w:1)
+	fn delegate_power() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico PowerDelegateOf (r:1 w:1)
+	// Storage: Ico PowerDelegatorsOf (r:1 w:1)
+	fn revoke_power_delegation() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico Ico (r:1 w:0)
+	// Storage: Ico IcoLiquidityOf (r:1 w:1)
+	// Storage: Tokens Accounts (r:1 w:1)
+	fn unlock_ico_liquidity() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico Ico (r:1 w:0)
+	// Storage: Ico LotteryOf (r:1 w:1)
+	// Storage: Ico TotalPowerOf (r:1 w:0)
+	// Storage: Ico PowerCheckpointOf (r:1 w:0)
+	// Storage: Ico PowerDelegateOf (r:1 w:0)
+	// Storage: Ico PowerDelegatorsOf (r:1 w:0)
+	// Storage: Tier Locks (r:1 w:0)
+	// Storage: Tier Tiers (r:1 w:0)
+	// Storage: Ico LotteryEntrantsOf (r:1 w:1)
+	fn register_lottery() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico Ico (r:1 w:0)
+	// Storage: Ico LotteryOf (r:1 w:1)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: Ico LotteryEntrantsOf (r:1 w:0)
+	// Storage: Ico LotteryWinnerOf (r:0 w:1)
+	fn draw_lottery() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico Ico (r:1 w:1)
+	// Storage: Ico IcoCompensationOf (r:1 w:1)
+	// Storage: Ico ProtectionFundCommitted (r:2 w:2)
+	// Storage: Tokens Accounts (r:2 w:2)
+	// Storage: Ico TotalAllocationOf (r:1 w:0)
+	// Storage: Ico InitiatorPledgeOf (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Ico PassedIcoes (r:1 w:1)
+	fn terminate_fraud_ico() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico Ico (r:1 w:0)
+	// Storage: Ico IcoCompensationOf (r:1 w:1)
+	// Storage: Ico CompensationClaimedOf (r:1 w:1)
+	// Storage: Ico UnReleaseAssets (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Tokens Accounts (r:2 w:2)
+	// Storage: Ico ProtectionFundCommitted (r:2 w:2)
+	fn claim_compensation() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico RequestExpiryQueue (r:1 w:1)
+	// Storage: Ico RequestReleaseInfo (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn expire_requests(n: u32) -> Weight {
+		Weight::from_ref_time(1_0000_0000).saturating_add(Weight::from_ref_time(2_0000_0000).saturating_mul(n as u64))
+	}
+	// Storage: Ico LotterySeedQueue (r:1 w:1)
+	// Storage: Ico LotteryOf (r:1 w:1)
+	fn seed_lotteries(n: u32) -> Weight {
+		Weight::from_ref_time(1_0000_0000).saturating_add(Weight::from_ref_time(2_0000_0000).saturating_mul(n as u64))
+	}
+	// Storage: Ico IcoRewardEconomics (r:1 w:1)
+	fn set_reward_economics() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico Ico (r:1 w:0)
+	// Storage: Tokens Accounts (r:1 w:1)
+	// Storage: Ico RewardPoolOf (r:1 w:1)
+	fn fund_reward_pool() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico Ico (r:1 w:0)
+	// Storage: Ico RewardPoolOf (r:1 w:1)
+	// Storage: Ico PoolRewardClaimedOf (r:1 w:1)
+	// Storage: Ico UnReleaseAssets (r:1 w:0)
+	// Storage: Tokens Accounts (r:2 w:2)
+	fn get_pool_reward() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico Ico (r:1 w:0)
+	// Storage: Ico RewardPoolOf (r:1 w:1)
+	// Storage: Tokens Accounts (r:1 w:1)
+	fn reclaim_reward_pool() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
 }
 
 // For backwards compatibility and tests
@@ -182,16 +392,28 @@ impl WeightInfo for () {
 	// Storage: System Account (r:1 w:1)
 	// Storage: Ico IcoesOf (r:1 w:1)
 	// Storage: Ico TotalPowerOf (r:1 w:1)
+	// Storage: Ico PowerCheckpointOf (r:1 w:1)
+	// Storage: DicoOracle RawValues (r:1 w:0)
+	// Storage: AMM PriceObservations (r:1 w:0)
+	// Storage: AMM PriceCumulative (r:1 w:0)
+	// Storage: AMM Liquidity (r:1 w:0)
 	fn join() -> Weight {
 		Weight::from_ref_time(20_0000_0000)
 	}
 	// Storage: Ico Ico (r:1 w:1)
 	// Storage: Ico PassedIcoes (r:1 w:1)
+	// Storage: Ico MilestonesOf (r:1 w:0)
 	fn terminate_ico() -> Weight {
 		Weight::from_ref_time(20_0000_0000)
 	}
 	// Storage: Ico Ico (r:1 w:0)
 	// Storage: Ico RequestReleaseInfo (r:1 w:1)
+	// Storage: Ico RequestExpiryQueue (r:1 w:1)
+	// Storage: Ico MilestonesOf (r:1 w:0)
+	// Storage: Dao ProposalOf (r:1 w:1)
+	// Storage: Dao Proposals (r:1 w:1)
+	// Storage: Dao ProposalCount (r:1 w:1)
+	// Storage: Dao Voting (r:0 w:1)
 	fn request_release() -> Weight {
 		Weight::from_ref_time(20_0000_0000)
 	}
@@ -202,17 +424,26 @@ impl WeightInfo for () {
 	}
 	// Storage: Ico Ico (r:1 w:1)
 	// Storage: Ico RequestReleaseInfo (r:1 w:1)
+	// Storage: Ico RequestExpiryQueue (r:1 w:1)
+	// Storage: Ico MilestonesOf (r:1 w:0)
 	fn permit_release() -> Weight {
 		Weight::from_ref_time(20_0000_0000)
 	}
 	// Storage: Ico Ico (r:1 w:0)
 	// Storage: Ico IsUnservePledge (r:1 w:1)
+	// Storage: Ico LiquidityOf (r:1 w:0)
+	// Storage: Ico IcoLiquidityOf (r:1 w:1)
 	// Storage: Ico UnReleaseAssets (r:2 w:1)
 	// Storage: System Account (r:1 w:1)
 	// Storage: Ico IcoLocks (r:1 w:1)
+	// Storage: Ico VestingOf (r:1 w:0)
+	// Storage: Ico VestingSchedules (r:1 w:1)
+	// Storage: Tokens Locks (r:1 w:1)
 	fn user_release_ico_amount() -> Weight {
 		Weight::from_ref_time(20_0000_0000)
 	}
+	// Storage: Ico VestingSchedules (r:1 w:1)
+	// Storage: Tokens Locks (r:1 w:1)
 	// Storage: Ico IcoLocks (r:1 w:1)
 	fn unlock() -> Weight {
 		Weight::from_ref_time(20_0000_0000)
@@ -236,6 +467,9 @@ impl WeightInfo for () {
 	// Storage: Ico TotalUsdt (r:1 w:1)
 	// Storage: Ico UnReleaseAssets (r:1 w:1)
 	// Storage: System Account (r:1 w:1)
+	// Storage: Ico ReferralRewardProportions (r:1 w:0)
+	// Storage: Ico ReferrerOf (r:3 w:0)
+	// Storage: Ico InviteInfoOf (r:3 w:3)
 	fn get_reward() -> Weight {
 		Weight::from_ref_time(20_0000_0000)
 	}
@@ -243,4 +477,168 @@ impl WeightInfo for () {
 	fn set_asset_power_multiple() -> Weight {
 		Weight::from_ref_time(20_0000_0000)
 	}
+	// Storage: Ico Ico (r:1 w:0)
+	// Storage: Ico SoftCapOf (r:1 w:0)
+	// Storage: Ico UnReleaseAssets (r:2 w:2)
+	// Storage: Tokens Accounts (r:4 w:4)
+	// Storage: Ico TotalPowerOf (r:1 w:1)
+	// Storage: Ico PowerCheckpointOf (r:1 w:1)
+	// Storage: Ico IsUnservePledge (r:1 w:1)
+	// Storage: Ico LiquidityOf (r:1 w:0)
+	// Storage: Ico IcoLiquidityOf (r:1 w:1)
+	// Storage: Ico InitiatorPledgeOf (r:1 w:1)
+	// Storage: Ico PassedIcoes (r:1 w:1)
+	fn refund() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico Ico (r:1 w:0)
+	// Storage: Ico IcoModeOf (r:1 w:0)
+	// Storage: Ico SoftCapOf (r:1 w:0)
+	// Storage: Ico AllocationClaimedOf (r:1 w:1)
+	// Storage: Ico UnReleaseAssets (r:2 w:1)
+	// Storage: Tokens Accounts (r:4 w:4)
+	fn claim_allocation() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico Ico (r:1 w:1)
+	// Storage: Ico PaymentTokensOf (r:1 w:0)
+	// Storage: Ico PaymentRaisedOf (r:1 w:1)
+	// Storage: Ico UserPaymentsOf (r:1 w:1)
+	// Storage: Ico UnReleaseAssets (r:1 w:1)
+	// Storage: Ico IcoesOf (r:1 w:1)
+	// Storage: Tokens Accounts (r:4 w:4)
+	// Storage: DicoOracle RawValues (r:2 w:0)
+	// Storage: AMM PriceObservations (r:2 w:0)
+	// Storage: AMM PriceCumulative (r:2 w:0)
+	// Storage: AMM Liquidity (r:2 w:0)
+	fn join_with_payment_token() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico Ico (r:1 w:0)
+	// Storage: Ico PaymentRaisedOf (r:1 w:1)
+	// Storage: Tokens Accounts (r:1 w:1)
+	fn release_payment_token() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico InviteCodeOf (r:1 w:1)
+	// Storage: Ico InviteCodeOwnerOf (r:1 w:1)
+	fn register_invite_code() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico InviteCodeOwnerOf (r:1 w:0)
+	// Storage: Ico ReferrerOf (r:4 w:1)
+	fn bind_invite_code() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico ReferralRewardProportions (r:0 w:1)
+	fn set_referral_reward_proportions() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico Ico (r:1 w:1)
+	// Storage: Ico InitiatorPledgeOf (r:1 w:1)
+	// Storage: Tokens Accounts (r:3 w:3)
+	// Storage: Ico Indexs (r:1 w:1)
+	// Storage: Ico PassedIcoes (r:1 w:1)
+	// Storage: Ico InitiatedIcoesOf (r:1 w:1)
+	fn cancel_ico() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico PendingIco (r:1 w:1)
+	// Storage: Ico VestingOf (r:0 w:1)
+	// Storage: Ico InitiatedIcoesOf (r:1 w:1)
+	fn amend_ico() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico PowerDelegateOf (r:2 w:1)
+	// Storage: Ico PowerDelegatorsOf (r:2 w:1)
+	fn delegate_power() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico PowerDelegateOf (r:1 w:1)
+	// Storage: Ico PowerDelegatorsOf (r:1 w:1)
+	fn revoke_power_delegation() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico Ico (r:1 w:0)
+	// Storage: Ico IcoLiquidityOf (r:1 w:1)
+	// Storage: Tokens Accounts (r:1 w:1)
+	fn unlock_ico_liquidity() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico Ico (r:1 w:0)
+	// Storage: Ico LotteryOf (r:1 w:1)
+	// Storage: Ico TotalPowerOf (r:1 w:0)
+	// Storage: Ico PowerCheckpointOf (r:1 w:0)
+	// Storage: Ico PowerDelegateOf (r:1 w:0)
+	// Storage: Ico PowerDelegatorsOf (r:1 w:0)
+	// Storage: Tier Locks (r:1 w:0)
+	// Storage: Tier Tiers (r:1 w:0)
+	// Storage: Ico LotteryEntrantsOf (r:1 w:1)
+	fn register_lottery() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico Ico (r:1 w:0)
+	// Storage: Ico LotteryOf (r:1 w:1)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: Ico LotteryEntrantsOf (r:1 w:0)
+	// Storage: Ico LotteryWinnerOf (r:0 w:1)
+	fn draw_lottery() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico Ico (r:1 w:1)
+	// Storage: Ico IcoCompensationOf (r:1 w:1)
+	// Storage: Ico ProtectionFundCommitted (r:2 w:2)
+	// Storage: Tokens Accounts (r:2 w:2)
+	// Storage: Ico TotalAllocationOf (r:1 w:0)
+	// Storage: Ico InitiatorPledgeOf (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Ico PassedIcoes (r:1 w:1)
+	fn terminate_fraud_ico() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico Ico (r:1 w:0)
+	// Storage: Ico IcoCompensationOf (r:1 w:1)
+	// Storage: Ico CompensationClaimedOf (r:1 w:1)
+	// Storage: Ico UnReleaseAssets (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Tokens Accounts (r:2 w:2)
+	// Storage: Ico ProtectionFundCommitted (r:2 w:2)
+	fn claim_compensation() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico RequestExpiryQueue (r:1 w:1)
+	// Storage: Ico RequestReleaseInfo (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn expire_requests(n: u32) -> Weight {
+		Weight::from_ref_time(1_0000_0000).saturating_add(Weight::from_ref_time(2_0000_0000).saturating_mul(n as u64))
+	}
+	// Storage: Ico LotterySeedQueue (r:1 w:1)
+	// Storage: Ico LotteryOf (r:1 w:1)
+	fn seed_lotteries(n: u32) -> Weight {
+		Weight::from_ref_time(1_0000_0000).saturating_add(Weight::from_ref_time(2_0000_0000).saturating_mul(n as u64))
+	}
+	// Storage: Ico IcoRewardEconomics (r:1 w:1)
+	fn set_reward_economics() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico Ico (r:1 w:0)
+	// Storage: Tokens Accounts (r:1 w:1)
+	// Storage: Ico RewardPoolOf (r:1 w:1)
+	fn fund_reward_pool() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico Ico (r:1 w:0)
+	// Storage: Ico RewardPoolOf (r:1 w:1)
+	// Storage: Ico PoolRewardClaimedOf (r:1 w:1)
+	// Storage: Ico UnReleaseAssets (r:1 w:0)
+	// Storage: Tokens Accounts (r:2 w:2)
+	fn get_pool_reward() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Ico Ico (r:1 w:0)
+	// Storage: Ico RewardPoolOf (r:1 w:1)
+	// Storage: Tokens Accounts (r:1 w:1)
+	fn reclaim_reward_pool() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
 }
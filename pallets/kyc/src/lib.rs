@@ -1404,4 +1404,17 @@ impl<T: Config> KycHandler<T::AccountId, AreaCode> for Pallet<T> {
 			None => None,
 		}
 	}
+
+	fn get_user_level(user: &T::AccountId) -> KycLevel {
+		match <KYCOf<T>>::get(user) {
+			Some(info) => {
+				let jg = info.judgements;
+				if jg.len() == 1 && jg[0].3 == Authentication::Success {
+					return jg[0].2.kyc_level();
+				}
+				KycLevel::Unverified
+			}
+			None => KycLevel::Unverified,
+		}
+	}
 }
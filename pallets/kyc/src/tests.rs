@@ -247,3 +247,26 @@ fn sword_holder_provide_judgement_should_work() {
 		);
 	})
 }
+
+#[test]
+fn get_user_level_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(KYC::get_user_level(&10), KycLevel::Unverified);
+
+		let mut registration = Registration {
+			judgements: vec![(KYCFields::Area, 1, Judgement::LowQuality, Authentication::Pending)],
+			deposit: 0,
+			info: alice_kyc(),
+		};
+		KYCOf::<Test>::insert(10, registration.clone());
+		assert_eq!(KYC::get_user_level(&10), KycLevel::Unverified);
+
+		registration.judgements[0].3 = Authentication::Success;
+		KYCOf::<Test>::insert(10, registration.clone());
+		assert_eq!(KYC::get_user_level(&10), KycLevel::Basic);
+
+		registration.judgements[0].2 = Judgement::PASS;
+		KYCOf::<Test>::insert(10, registration);
+		assert_eq!(KYC::get_user_level(&10), KycLevel::Full);
+	})
+}
@@ -1,3 +1,6 @@
+use crate::types::KycLevel;
+
 pub trait KycHandler<AccountId, AreaCode> {
 	fn get_user_area(user: &AccountId) -> Option<AreaCode>;
+	fn get_user_level(user: &AccountId) -> KycLevel;
 }
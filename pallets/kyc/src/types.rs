@@ -60,6 +60,26 @@ impl<Balance: Encode + Decode + Copy + Clone + Debug + Eq + PartialEq> Judgement
 			_ => false,
 		}
 	}
+
+	/// The kyc level of the user authenticated with this judgement.
+	pub(crate) fn kyc_level(&self) -> KycLevel {
+		match self {
+			Judgement::PASS => KycLevel::Full,
+			Judgement::OutOfDate | Judgement::LowQuality => KycLevel::Basic,
+			_ => KycLevel::Unverified,
+		}
+	}
+}
+
+/// The level of the kyc of the user, by the judgement of the IAS
+#[derive(Copy, Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum KycLevel {
+	/// No kyc is authenticated
+	Unverified,
+	/// The data is authenticated but out of date or of low quality
+	Basic,
+	/// The data is authenticated and passed
+	Full,
 }
 
 /// kyc information authentication by the `SwordHolder`
@@ -0,0 +1,76 @@
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::{account, benchmarks};
+use frame_system::{Pallet as System, RawOrigin};
+use sp_std::prelude::*;
+
+use crate::Pallet as MerkleDistributor;
+
+const SEED: u32 = 1;
+const DOT: AssetId = 2000;
+const TOTAL_AMOUNT: Balance = 1_000_000_000_000;
+
+fn fund_treasury<T: Config>() {
+	let treasury = T::TreasuryHandler::get_treasury_account_id();
+	T::Currency::update_balance(DOT, &treasury, 1_000_000_000_000_000).unwrap();
+}
+
+benchmarks! {
+	create_campaign {
+		fund_treasury::<T>();
+		let merkle_root = H256::repeat_byte(1);
+	}: _(RawOrigin::Root, DOT, TOTAL_AMOUNT, merkle_root, T::BlockNumber::from(1000u32), None)
+	verify {
+		assert_eq!(Campaigns::<T>::get(T::CampaignId::zero()).unwrap().total_amount, TOTAL_AMOUNT);
+	}
+
+	claim {
+		fund_treasury::<T>();
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let other: T::AccountId = account("other", 0, SEED);
+		let leaf = MerkleDistributor::<T>::leaf_hash(&caller, TOTAL_AMOUNT / 2);
+		let sibling = MerkleDistributor::<T>::leaf_hash(&other, TOTAL_AMOUNT / 2);
+		let pair = if leaf <= sibling {
+			[leaf.as_bytes(), sibling.as_bytes()].concat()
+		} else {
+			[sibling.as_bytes(), leaf.as_bytes()].concat()
+		};
+		let merkle_root = H256::from(blake2_256(&pair));
+		MerkleDistributor::<T>::create_campaign(
+			RawOrigin::Root.into(),
+			DOT,
+			TOTAL_AMOUNT,
+			merkle_root,
+			T::BlockNumber::from(1000u32),
+			None,
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()), T::CampaignId::zero(), TOTAL_AMOUNT / 2, vec![sibling])
+	verify {
+		assert_eq!(T::Currency::free_balance(DOT, &caller), TOTAL_AMOUNT / 2);
+	}
+
+	reclaim {
+		let n in 0 .. T::MaxClaimedRemovals::get();
+		fund_treasury::<T>();
+		let caller: T::AccountId = account("caller", 0, SEED);
+		MerkleDistributor::<T>::create_campaign(
+			RawOrigin::Root.into(),
+			DOT,
+			TOTAL_AMOUNT,
+			H256::repeat_byte(1),
+			T::BlockNumber::from(1000u32),
+			None,
+		)?;
+		for i in 0..n {
+			let who: T::AccountId = account("claimer", i, SEED);
+			ClaimedOf::<T>::insert(T::CampaignId::zero(), who, true);
+		}
+		System::<T>::set_block_number(T::BlockNumber::from(1001u32));
+	}: _(RawOrigin::Signed(caller), T::CampaignId::zero())
+	verify {
+		assert!(Campaigns::<T>::get(T::CampaignId::zero()).is_none());
+		assert!(ReclaimCursorOf::<T>::get(T::CampaignId::zero()).is_none());
+	}
+}
@@ -0,0 +1,364 @@
+//! # Merkle Distributor Pallet
+//!
+//! ## Overview
+//!
+//! Merkle distributor pallet provides functionality for distributing assets to a whitelist of
+//! accounts with merkle proofs.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+#![allow(clippy::upper_case_acronyms)]
+
+use codec::{Decode, Encode};
+use dico_primitives::{Amount, AssetId, Balance};
+use frame_support::{
+	ensure,
+	pallet_prelude::*,
+	sp_runtime::traits::{AtLeast32Bit, CheckedAdd, One, Zero},
+	traits::{EnsureOrigin, Get},
+	transactional, PalletId,
+};
+use frame_system::pallet_prelude::*;
+use orml_traits::{MultiCurrency, MultiCurrencyExtended};
+use pallet_dico_treasury::traits::DicoTreasuryHandler;
+use scale_info::TypeInfo;
+use sp_core::H256;
+use sp_io::hashing::blake2_256;
+use sp_runtime::{traits::AccountIdConversion, ArithmeticError};
+use sp_std::vec::Vec;
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+mod benchmarking;
+pub mod weights;
+
+use weights::WeightInfo;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct CampaignInfo<AccountId, BlockNumber> {
+	pub currency_id: AssetId,
+	pub total_amount: Balance,
+	pub claimed_amount: Balance,
+	pub merkle_root: H256,
+	/// The account that funded the campaign, the unclaimed assets are returned to it.
+	pub funder: AccountId,
+	/// After this block, nobody can claim and the unclaimed assets can be returned.
+	pub expiry: BlockNumber,
+}
+
+impl<AccountId, BlockNumber> CampaignInfo<AccountId, BlockNumber> {
+	pub fn new(
+		currency_id: AssetId,
+		total_amount: Balance,
+		merkle_root: H256,
+		funder: AccountId,
+		expiry: BlockNumber,
+	) -> Self {
+		Self {
+			currency_id,
+			total_amount,
+			claimed_amount: Balance::zero(),
+			merkle_root,
+			funder,
+			expiry,
+		}
+	}
+}
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The campaign id
+		type CampaignId: Parameter + Member + AtLeast32Bit + Default + Copy + MaybeSerializeDeserialize;
+
+		/// Multi currency for transfer of currencies
+		type Currency: MultiCurrencyExtended<Self::AccountId, CurrencyId = AssetId, Balance = Balance, Amount = Amount>;
+
+		/// The origin that is allowed to create campaigns.
+		type CreateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The funder of the campaigns.
+		type TreasuryHandler: DicoTreasuryHandler<Self::AccountId>;
+
+		/// The distributor's module id, keep all assets of the campaigns.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The maximum length of a merkle proof.
+		#[pallet::constant]
+		type MaxProofLength: Get<u32>;
+
+		/// The maximum number of claim records removed by one `reclaim`, the remaining records
+		/// are removed by the following calls.
+		#[pallet::constant]
+		type MaxClaimedRemovals: Get<u32>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The campaign does not exist.
+		CampaignNotFind,
+		/// No campaign id available.
+		NoCampaignIdAvailable,
+		/// The expiry block must be later than the current block.
+		InvalidExpiry,
+		/// The amount of the campaign is zero.
+		InvalidAmount,
+		/// The merkle proof is too long.
+		ProofTooLong,
+		/// The merkle proof does not match the merkle root of the campaign.
+		InvalidProof,
+		/// The account has already claimed.
+		AlreadyClaimed,
+		/// The campaign has expired.
+		CampaignExpired,
+		/// The campaign has not expired yet.
+		CampaignNotExpired,
+		/// The campaign does not have enough assets left.
+		InsufficientCampaignBalance,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub (crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The campaign was created. [campaign id, funder, currency id, total amount, merkle root,
+		/// expiry]
+		CampaignCreated(T::CampaignId, T::AccountId, AssetId, Balance, H256, T::BlockNumber),
+		/// The account claimed from the campaign. [campaign id, who, amount]
+		Claimed(T::CampaignId, T::AccountId, Balance),
+		/// The unclaimed assets of the campaign were returned to the funder. [campaign id, funder,
+		/// amount]
+		CampaignReclaimed(T::CampaignId, T::AccountId, Balance),
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_campaign_id)]
+	pub type NextCampaignId<T: Config> = StorageValue<_, T::CampaignId, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn get_campaign)]
+	pub type Campaigns<T: Config> =
+		StorageMap<_, Twox64Concat, T::CampaignId, CampaignInfo<T::AccountId, T::BlockNumber>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn is_claimed)]
+	pub type ClaimedOf<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::CampaignId, Blake2_128Concat, T::AccountId, bool, ValueQuery>;
+
+	/// The cursor of the claim records of a reclaimed campaign that are not removed yet.
+	#[pallet::storage]
+	#[pallet::getter(fn reclaim_cursor_of)]
+	pub type ReclaimCursorOf<T: Config> = StorageMap<_, Twox64Concat, T::CampaignId, Vec<u8>, OptionQuery>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Create a campaign that distributes `total_amount` of `currency_id` to the entries of
+		/// the merkle tree.
+		///
+		/// The assets are transferred from `funder` to the module account, `funder` must sign the
+		/// call. Without `funder`, the treasury funds the campaign and `CreateOrigin` must be the
+		/// origin.
+		///
+		/// Emits `CampaignCreated` event when successful.
+		#[pallet::weight(< T as Config >::WeightInfo::create_campaign())]
+		#[transactional]
+		pub fn create_campaign(
+			origin: OriginFor<T>,
+			currency_id: AssetId,
+			total_amount: Balance,
+			merkle_root: H256,
+			expiry: T::BlockNumber,
+			funder: Option<T::AccountId>,
+		) -> DispatchResultWithPostInfo {
+			let funder = match funder {
+				Some(funder) => {
+					let who = ensure_signed(origin)?;
+					ensure!(who == funder, DispatchError::BadOrigin);
+					funder
+				}
+				None => {
+					T::CreateOrigin::ensure_origin(origin)?;
+					T::TreasuryHandler::get_treasury_account_id()
+				}
+			};
+
+			ensure!(total_amount > Balance::zero(), Error::<T>::InvalidAmount);
+			let block_number = frame_system::pallet::Pallet::<T>::block_number();
+			ensure!(expiry > block_number, Error::<T>::InvalidExpiry);
+
+			T::Currency::transfer(currency_id, &funder, &Self::account_id(), total_amount)?;
+
+			let campaign_id = Self::get_next_campaign_id()?;
+			Campaigns::<T>::insert(
+				campaign_id,
+				CampaignInfo::new(currency_id, total_amount, merkle_root, funder.clone(), expiry),
+			);
+
+			Self::deposit_event(Event::CampaignCreated(
+				campaign_id,
+				funder,
+				currency_id,
+				total_amount,
+				merkle_root,
+				expiry,
+			));
+
+			Ok(().into())
+		}
+
+		/// Claim `amount` from the campaign.
+		///
+		/// `proof` proves that `(who, amount)` is an entry of the merkle tree of the campaign.
+		///
+		/// Emits `Claimed` event when successful.
+		#[pallet::weight(< T as Config >::WeightInfo::claim())]
+		#[transactional]
+		pub fn claim(
+			origin: OriginFor<T>,
+			campaign_id: T::CampaignId,
+			amount: Balance,
+			proof: Vec<H256>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			ensure!(
+				proof.len() <= T::MaxProofLength::get() as usize,
+				Error::<T>::ProofTooLong
+			);
+
+			let mut campaign = Campaigns::<T>::get(campaign_id).ok_or(Error::<T>::CampaignNotFind)?;
+			let block_number = frame_system::pallet::Pallet::<T>::block_number();
+			ensure!(block_number <= campaign.expiry, Error::<T>::CampaignExpired);
+			ensure!(!Self::is_claimed(campaign_id, &who), Error::<T>::AlreadyClaimed);
+
+			let leaf = Self::leaf_hash(&who, amount);
+			ensure!(
+				Self::verify_proof(campaign.merkle_root, leaf, &proof),
+				Error::<T>::InvalidProof
+			);
+
+			campaign.claimed_amount = campaign
+				.claimed_amount
+				.checked_add(amount)
+				.ok_or(ArithmeticError::Overflow)?;
+			ensure!(
+				campaign.claimed_amount <= campaign.total_amount,
+				Error::<T>::InsufficientCampaignBalance
+			);
+
+			T::Currency::transfer(campaign.currency_id, &Self::account_id(), &who, amount)?;
+
+			Campaigns::<T>::insert(campaign_id, campaign);
+			ClaimedOf::<T>::insert(campaign_id, &who, true);
+			Self::deposit_event(Event::Claimed(campaign_id, who, amount));
+
+			Ok(().into())
+		}
+
+		/// Return the unclaimed assets of an expired campaign to its funder.
+		///
+		/// Anyone can call this method once the campaign has expired. At most
+		/// `MaxClaimedRemovals` claim records are removed per call, if some are left, calling it
+		/// again with the same campaign id removes the next ones.
+		///
+		/// Emits `CampaignReclaimed` event when the assets are returned.
+		#[pallet::weight(< T as Config >::WeightInfo::reclaim(T::MaxClaimedRemovals::get()))]
+		#[transactional]
+		pub fn reclaim(origin: OriginFor<T>, campaign_id: T::CampaignId) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			let cursor = match Campaigns::<T>::get(campaign_id) {
+				Some(campaign) => {
+					let block_number = frame_system::pallet::Pallet::<T>::block_number();
+					ensure!(block_number > campaign.expiry, Error::<T>::CampaignNotExpired);
+
+					let unclaimed_amount = campaign.total_amount.saturating_sub(campaign.claimed_amount);
+					if unclaimed_amount > Balance::zero() {
+						T::Currency::transfer(
+							campaign.currency_id,
+							&Self::account_id(),
+							&campaign.funder,
+							unclaimed_amount,
+						)?;
+					}
+
+					Campaigns::<T>::remove(campaign_id);
+					Self::deposit_event(Event::CampaignReclaimed(campaign_id, campaign.funder, unclaimed_amount));
+					None
+				}
+				None => Some(ReclaimCursorOf::<T>::take(campaign_id).ok_or(Error::<T>::CampaignNotFind)?),
+			};
+
+			let result = ClaimedOf::<T>::clear_prefix(campaign_id, T::MaxClaimedRemovals::get(), cursor.as_deref());
+			if let Some(cursor) = result.maybe_cursor {
+				ReclaimCursorOf::<T>::insert(campaign_id, cursor);
+			}
+
+			Ok(Some(<T as Config>::WeightInfo::reclaim(result.unique)).into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account_truncating()
+	}
+
+	fn get_next_campaign_id() -> sp_std::result::Result<T::CampaignId, DispatchError> {
+		let next_campaign_id = Self::next_campaign_id();
+		let new_campaign_id = next_campaign_id
+			.checked_add(&One::one())
+			.ok_or(Error::<T>::NoCampaignIdAvailable)?;
+		NextCampaignId::<T>::put(new_campaign_id);
+
+		Ok(next_campaign_id)
+	}
+
+	/// The leaf of the `(account, amount)` entry in the merkle tree.
+	pub fn leaf_hash(account: &T::AccountId, amount: Balance) -> H256 {
+		H256::from(blake2_256(&(account, amount).encode()))
+	}
+
+	/// Check that `leaf` is in the merkle tree of `root`.
+	///
+	/// The two children of a node are hashed in ascending order, so the proof does not need to
+	/// carry the position of each node.
+	pub fn verify_proof(root: H256, leaf: H256, proof: &[H256]) -> bool {
+		let computed_root = proof.iter().fold(leaf, |node, sibling| {
+			let pair = if node <= *sibling {
+				[node.as_bytes(), sibling.as_bytes()].concat()
+			} else {
+				[sibling.as_bytes(), node.as_bytes()].concat()
+			};
+			H256::from(blake2_256(&pair))
+		});
+
+		computed_root == root
+	}
+}
@@ -0,0 +1,156 @@
+//! Mocks for the merkle distributor module.
+
+#![cfg(test)]
+
+use crate as merkle_distributor;
+use crate::Config;
+use dico_primitives::{AssetId, Balance};
+use frame_support::traits::{ConstU32, Contains, Everything, GenesisBuild};
+use frame_support::{ord_parameter_types, parameter_types, PalletId};
+use frame_system as system;
+use frame_system::EnsureSignedBy;
+use orml_traits::parameter_type_with_key;
+use pallet_dico_treasury::traits::DicoTreasuryHandler;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup, Zero},
+};
+
+pub type Amount = i128;
+pub type AccountId = u64;
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const DAVE: AccountId = 3;
+pub const TREASURY_ACCOUNT: AccountId = 100;
+
+pub const DOT: AssetId = 2000;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+	 Block = Block,
+	 NodeBlock = Block,
+	 UncheckedExtrinsic = UncheckedExtrinsic,
+	 {
+		 System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		 MerkleDistributor: merkle_distributor::{Pallet, Call, Storage, Event<T>},
+		 Currency: orml_tokens::{Pallet, Event<T>},
+	 }
+);
+
+pub struct MockDustRemovalWhitelist;
+impl Contains<AccountId> for MockDustRemovalWhitelist {
+	fn contains(a: &AccountId) -> bool {
+		*a == DAVE
+	}
+}
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MerkleDistributorPalletId: PalletId = PalletId(*b"dico/mkd");
+	pub const MaxProofLength: u32 = 4;
+	pub const MaxClaimedRemovals: u32 = 2;
+}
+
+impl system::Config for Test {
+	type BaseCallFilter = Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = BlockHashCount;
+	type DbWeight = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: AssetId| -> Balance {
+		Zero::zero()
+	};
+}
+
+impl orml_tokens::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = Balance;
+	type Amount = Amount;
+	type CurrencyId = AssetId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type CurrencyHooks = ();
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type DustRemovalWhitelist = MockDustRemovalWhitelist;
+}
+
+ord_parameter_types! {
+	pub const One: u64 = 1;
+}
+
+pub struct Treasury;
+impl DicoTreasuryHandler<AccountId> for Treasury {
+	fn get_treasury_account_id() -> AccountId {
+		TREASURY_ACCOUNT
+	}
+}
+
+impl Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type CampaignId = u32;
+	type Currency = Currency;
+	type CreateOrigin = EnsureSignedBy<One, u64>;
+	type TreasuryHandler = Treasury;
+	type PalletId = MerkleDistributorPalletId;
+	type MaxProofLength = MaxProofLength;
+	type MaxClaimedRemovals = MaxClaimedRemovals;
+	type WeightInfo = ();
+}
+
+pub struct ExtBuilder {
+	endowed_accounts: Vec<(AccountId, AssetId, Balance)>,
+}
+
+pub const DEFAULT_ASSET_AMOUNT: Balance = 1000_000_000_000_000;
+
+// Returns default values for genesis config
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		Self {
+			endowed_accounts: vec![(TREASURY_ACCOUNT, DOT, DEFAULT_ASSET_AMOUNT)],
+		}
+	}
+}
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+
+		orml_tokens::GenesisConfig::<Test> {
+			balances: self.endowed_accounts,
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		t.into()
+	}
+}
@@ -0,0 +1,250 @@
+//! Unit tests for the merkle distributor module.
+
+#![cfg(test)]
+
+use super::*;
+pub use crate::mock::{
+	Currency, ExtBuilder, MerkleDistributor, RuntimeEvent as TestEvent, RuntimeOrigin as Origin, System, Test, ALICE,
+	BOB, DAVE, DEFAULT_ASSET_AMOUNT, DOT, TREASURY_ACCOUNT,
+};
+use frame_support::{assert_noop, assert_ok};
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext = ExtBuilder::default().build();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+fn last_events(n: usize) -> Vec<TestEvent> {
+	frame_system::Pallet::<Test>::events()
+		.into_iter()
+		.rev()
+		.take(n)
+		.rev()
+		.map(|e| e.event)
+		.collect()
+}
+
+fn expect_events(e: Vec<TestEvent>) {
+	assert_eq!(last_events(e.len()), e);
+}
+
+fn hash_pair(a: H256, b: H256) -> H256 {
+	if a <= b {
+		H256::from(blake2_256(&[a.as_bytes(), b.as_bytes()].concat()))
+	} else {
+		H256::from(blake2_256(&[b.as_bytes(), a.as_bytes()].concat()))
+	}
+}
+
+/// The merkle tree of (ALICE, 100), (BOB, 200) and (DAVE, 300).
+fn merkle_tree() -> (H256, H256, H256, H256) {
+	let alice = MerkleDistributor::leaf_hash(&ALICE, 100);
+	let bob = MerkleDistributor::leaf_hash(&BOB, 200);
+	let dave = MerkleDistributor::leaf_hash(&DAVE, 300);
+	let root = hash_pair(hash_pair(alice, bob), dave);
+
+	(root, alice, bob, dave)
+}
+
+#[test]
+fn create_campaign_should_work() {
+	new_test_ext().execute_with(|| {
+		let (root, ..) = merkle_tree();
+
+		assert_noop!(
+			MerkleDistributor::create_campaign(Origin::signed(BOB), DOT, 600, root, 100, None),
+			sp_runtime::traits::BadOrigin
+		);
+		assert_noop!(
+			MerkleDistributor::create_campaign(Origin::signed(ALICE), DOT, 600, root, 1, None),
+			Error::<Test>::InvalidExpiry
+		);
+
+		assert_ok!(MerkleDistributor::create_campaign(
+			Origin::signed(ALICE),
+			DOT,
+			600,
+			root,
+			100,
+			None
+		));
+
+		assert_eq!(
+			Campaigns::<Test>::get(0),
+			Some(CampaignInfo::new(DOT, 600, root, TREASURY_ACCOUNT, 100))
+		);
+		assert_eq!(
+			Currency::free_balance(DOT, &TREASURY_ACCOUNT),
+			DEFAULT_ASSET_AMOUNT - 600
+		);
+		assert_eq!(Currency::free_balance(DOT, &MerkleDistributor::account_id()), 600);
+		expect_events(vec![
+			Event::CampaignCreated(0, TREASURY_ACCOUNT, DOT, 600, root, 100).into()
+		]);
+	});
+}
+
+#[test]
+fn create_campaign_with_funder_should_work() {
+	new_test_ext().execute_with(|| {
+		let (root, ..) = merkle_tree();
+		assert_ok!(Currency::deposit(DOT, &BOB, 1000));
+
+		assert_noop!(
+			MerkleDistributor::create_campaign(Origin::signed(ALICE), DOT, 600, root, 100, Some(BOB)),
+			sp_runtime::traits::BadOrigin
+		);
+		assert_noop!(
+			MerkleDistributor::create_campaign(Origin::root(), DOT, 600, root, 100, Some(BOB)),
+			sp_runtime::traits::BadOrigin
+		);
+
+		assert_ok!(MerkleDistributor::create_campaign(
+			Origin::signed(BOB),
+			DOT,
+			600,
+			root,
+			100,
+			Some(BOB)
+		));
+
+		assert_eq!(
+			Campaigns::<Test>::get(0),
+			Some(CampaignInfo::new(DOT, 600, root, BOB, 100))
+		);
+		assert_eq!(Currency::free_balance(DOT, &BOB), 400);
+		assert_eq!(Currency::free_balance(DOT, &TREASURY_ACCOUNT), DEFAULT_ASSET_AMOUNT);
+		expect_events(vec![Event::CampaignCreated(0, BOB, DOT, 600, root, 100).into()]);
+
+		System::set_block_number(101);
+		assert_ok!(MerkleDistributor::reclaim(Origin::signed(DAVE), 0));
+		expect_events(vec![Event::CampaignReclaimed(0, BOB, 600).into()]);
+		assert_eq!(Currency::free_balance(DOT, &BOB), 1000);
+	});
+}
+
+#[test]
+fn claim_should_work() {
+	new_test_ext().execute_with(|| {
+		let (root, alice, bob, dave) = merkle_tree();
+		assert_ok!(MerkleDistributor::create_campaign(
+			Origin::signed(ALICE),
+			DOT,
+			600,
+			root,
+			100,
+			None
+		));
+
+		assert_noop!(
+			MerkleDistributor::claim(Origin::signed(ALICE), 0, 200, vec![bob, dave]),
+			Error::<Test>::InvalidProof
+		);
+		assert_noop!(
+			MerkleDistributor::claim(Origin::signed(BOB), 0, 200, vec![bob, dave]),
+			Error::<Test>::InvalidProof
+		);
+
+		assert_ok!(MerkleDistributor::claim(Origin::signed(ALICE), 0, 100, vec![bob, dave]));
+		assert_ok!(MerkleDistributor::claim(
+			Origin::signed(DAVE),
+			0,
+			300,
+			vec![hash_pair(alice, bob)]
+		));
+		expect_events(vec![Event::Claimed(0, DAVE, 300).into()]);
+
+		assert_eq!(Currency::free_balance(DOT, &ALICE), 100);
+		assert_eq!(Currency::free_balance(DOT, &DAVE), 300);
+		assert_eq!(Campaigns::<Test>::get(0).unwrap().claimed_amount, 400);
+
+		assert_noop!(
+			MerkleDistributor::claim(Origin::signed(ALICE), 0, 100, vec![bob, dave]),
+			Error::<Test>::AlreadyClaimed
+		);
+
+		System::set_block_number(101);
+		assert_noop!(
+			MerkleDistributor::claim(Origin::signed(BOB), 0, 200, vec![alice, dave]),
+			Error::<Test>::CampaignExpired
+		);
+	});
+}
+
+#[test]
+fn reclaim_should_work() {
+	new_test_ext().execute_with(|| {
+		let (root, _, bob, dave) = merkle_tree();
+		assert_ok!(MerkleDistributor::create_campaign(
+			Origin::signed(ALICE),
+			DOT,
+			600,
+			root,
+			100,
+			None
+		));
+		assert_ok!(MerkleDistributor::claim(Origin::signed(ALICE), 0, 100, vec![bob, dave]));
+
+		assert_noop!(
+			MerkleDistributor::reclaim(Origin::signed(BOB), 0),
+			Error::<Test>::CampaignNotExpired
+		);
+
+		System::set_block_number(101);
+		assert_ok!(MerkleDistributor::reclaim(Origin::signed(BOB), 0));
+		expect_events(vec![Event::CampaignReclaimed(0, TREASURY_ACCOUNT, 500).into()]);
+
+		assert_eq!(Campaigns::<Test>::get(0), None);
+		assert!(!MerkleDistributor::is_claimed(0, ALICE));
+		assert_eq!(
+			Currency::free_balance(DOT, &TREASURY_ACCOUNT),
+			DEFAULT_ASSET_AMOUNT - 100
+		);
+		assert_eq!(Currency::free_balance(DOT, &MerkleDistributor::account_id()), 0);
+	});
+}
+
+#[test]
+fn reclaim_should_remove_claims_in_batches() {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		let (root, alice, bob, dave) = merkle_tree();
+		assert_ok!(MerkleDistributor::create_campaign(
+			Origin::signed(ALICE),
+			DOT,
+			600,
+			root,
+			100,
+			None
+		));
+		assert_ok!(MerkleDistributor::claim(Origin::signed(ALICE), 0, 100, vec![bob, dave]));
+		assert_ok!(MerkleDistributor::claim(Origin::signed(BOB), 0, 200, vec![alice, dave]));
+		assert_ok!(MerkleDistributor::claim(
+			Origin::signed(DAVE),
+			0,
+			300,
+			vec![hash_pair(alice, bob)]
+		));
+	});
+	// Only the claim records in the backend are removed in batches.
+	ext.commit_all().unwrap();
+
+	ext.execute_with(|| {
+		System::set_block_number(101);
+		assert_ok!(MerkleDistributor::reclaim(Origin::signed(BOB), 0));
+		expect_events(vec![Event::CampaignReclaimed(0, TREASURY_ACCOUNT, 0).into()]);
+		assert_eq!(Campaigns::<Test>::get(0), None);
+		assert!(MerkleDistributor::reclaim_cursor_of(0).is_some());
+		assert_eq!(ClaimedOf::<Test>::iter_prefix(0).count(), 1);
+
+		assert_ok!(MerkleDistributor::reclaim(Origin::signed(BOB), 0));
+		assert_eq!(MerkleDistributor::reclaim_cursor_of(0), None);
+		assert_eq!(ClaimedOf::<Test>::iter_prefix(0).count(), 0);
+
+		assert_noop!(
+			MerkleDistributor::reclaim(Origin::signed(BOB), 0),
+			Error::<Test>::CampaignNotFind
+		);
+	});
+}
@@ -0,0 +1,84 @@
+//! Autogenerated weights for pallet_merkle_distributor
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! DATE: 2022-04-15, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("kico"), DB CACHE: 1024
+
+// Executed Command:
+// target/release/dico
+// benchmark
+// --chain=kico
+// --execution=wasm
+// --wasm-execution=compiled
+// --pallet=pallet_merkle_distributor
+// --extrinsic=*
+// --steps=50
+// --repeat=20
+// --template=./.maintain/pallet-weight-template.hbs
+// --output
+// ./pallets/merkle-distributor/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_merkle_distributor.
+pub trait WeightInfo {
+	fn create_campaign() -> Weight;
+	fn claim() -> Weight;
+	fn reclaim(n: u32) -> Weight;
+}
+
+/// Weights for pallet_merkle_distributor using the Substrate node and recommended hardware.
+pub struct DicoWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for DicoWeight<T> {
+	// Storage: Tokens Accounts (r:2 w:2)
+	// Storage: System Account (r:2 w:1)
+	// Storage: MerkleDistributor NextCampaignId (r:1 w:1)
+	// Storage: MerkleDistributor Campaigns (r:0 w:1)
+	fn create_campaign() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: MerkleDistributor Campaigns (r:1 w:1)
+	// Storage: MerkleDistributor ClaimedOf (r:1 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	fn claim() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: MerkleDistributor Campaigns (r:1 w:1)
+	// Storage: MerkleDistributor ReclaimCursorOf (r:1 w:1)
+	// Storage: MerkleDistributor ClaimedOf (r:0 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	fn reclaim(n: u32) -> Weight {
+		Weight::from_ref_time(20_0000_0000).saturating_add(Weight::from_ref_time(2000_0000).saturating_mul(n as u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: Tokens Accounts (r:2 w:2)
+	// Storage: System Account (r:2 w:1)
+	// Storage: MerkleDistributor NextCampaignId (r:1 w:1)
+	// Storage: MerkleDistributor Campaigns (r:0 w:1)
+	fn create_campaign() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: MerkleDistributor Campaigns (r:1 w:1)
+	// Storage: MerkleDistributor ClaimedOf (r:1 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	fn claim() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: MerkleDistributor Campaigns (r:1 w:1)
+	// Storage: MerkleDistributor ReclaimCursorOf (r:1 w:1)
+	// Storage: MerkleDistributor ClaimedOf (r:0 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	fn reclaim(n: u32) -> Weight {
+		Weight::from_ref_time(20_0000_0000).saturating_add(Weight::from_ref_time(2000_0000).saturating_mul(n as u64))
+	}
+}
@@ -29,6 +29,7 @@ use daos_create_dao::AccountIdConversion;
 pub mod weights;
 use codec::{Decode, Encode};
 use daos_create_dao::{self as dao};
+use dico_primitives::{AssetId, Balance};
 use frame_support::{
 	ensure,
 	pallet_prelude::*,
@@ -36,11 +37,13 @@ use frame_support::{
 	BoundedVec, Parameter,
 };
 use orml_traits::{MultiCurrency, MultiReservableCurrency};
-use pallet_ico::traits::PowerHandler;
+use pallet_ico::traits::{IcoReceiptHandler, PowerHandler};
 use pallet_vc::{self, Fee};
 use scale_info::TypeInfo;
 use sp_runtime::{
-	traits::{AtLeast32BitUnsigned, CheckedAdd, CheckedSub, Hash, MaybeSerializeDeserialize, Member, One, Zero},
+	traits::{
+		AtLeast32BitUnsigned, Bounded, CheckedAdd, CheckedSub, Hash, MaybeSerializeDeserialize, Member, One, Zero,
+	},
 	ArithmeticError, DispatchError, DispatchResult, RuntimeDebug,
 };
 use sp_std::vec;
@@ -254,6 +257,7 @@ pub mod module {
 		DaoExists,
 		FeeErr,
 		ConvertErr,
+		IcoReceipt,
 	}
 
 	/// Next available class ID.
@@ -311,6 +315,16 @@ pub mod module {
 	#[pallet::getter(fn locks)]
 	pub type Locks<T: Config> = StorageValue<_, BTreeSet<(T::ClassId, T::TokenId)>, ValueQuery>;
 
+	/// The class of the receipts of the ico participations.
+	#[pallet::storage]
+	#[pallet::getter(fn ico_receipt_class_of)]
+	pub type IcoReceiptClassOf<T: Config> = StorageDoubleMap<_, Twox64Concat, AssetId, Twox64Concat, u32, T::ClassId>;
+
+	/// The ico of the receipt class.
+	#[pallet::storage]
+	#[pallet::getter(fn ico_of_receipt_class)]
+	pub type IcoOfReceiptClass<T: Config> = StorageMap<_, Twox64Concat, T::ClassId, (AssetId, u32)>;
+
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
@@ -642,6 +656,8 @@ impl<T: Config> Pallet<T> {
 			ensure!(t.owner == Some(owner.clone()), Error::<T>::NotOwner);
 			ensure!(!Self::is_in_sale(token.0, token.1), Error::<T>::InSale);
 			ensure!(!t.data.status.is_active_image, Error::<T>::ActiveNft);
+			// The receipt can not be claimed by others after it is burned.
+			ensure!(!IcoOfReceiptClass::<T>::contains_key(token.0), Error::<T>::IcoReceipt);
 
 			T::PowerHandler::add_user_power(&owner, t.data.power_threshold)?;
 			Self::remove_token_ownership(owner, token.0, token.1)?;
@@ -872,3 +888,118 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 }
+
+impl<T: Config> Pallet<T> {
+	fn ico_receipt_class(issuer: &T::AccountId, currency_id: AssetId, index: u32) -> Result<T::ClassId, DispatchError> {
+		if let Some(class_id) = IcoReceiptClassOf::<T>::get(currency_id, index) {
+			return Ok(class_id);
+		}
+		let metadata: ClassMetadataOf<T> = (currency_id, index)
+			.encode()
+			.try_into()
+			.map_err(|_| Error::<T>::MaxMetadataExceeded)?;
+		let class_id = NextClassId::<T>::try_mutate(|id| -> Result<T::ClassId, DispatchError> {
+			let current_id = *id;
+			*id = id.checked_add(&One::one()).ok_or(Error::<T>::NoAvailableClassId)?;
+			Ok(current_id)
+		})?;
+		Classes::<T>::insert(
+			class_id,
+			ClassInfo {
+				metadata,
+				total_issuance: Default::default(),
+				issuer: issuer.clone(),
+				data: ClassData {
+					level: NftLevel::Other(b"ico receipt".to_vec()),
+					power_threshold: BalanceOf::<T>::from(0u32),
+					claim_payment: BalanceOf::<T>::from(0u32),
+					images_hash: None,
+					maximum_quantity: T::TokenId::max_value(),
+				},
+			},
+		);
+		IcoReceiptClassOf::<T>::insert(currency_id, index, class_id);
+		IcoOfReceiptClass::<T>::insert(class_id, (currency_id, index));
+		Self::deposit_event(Event::<T>::CreateClass(issuer.clone(), class_id));
+		Ok(class_id)
+	}
+
+	fn receipt_attribute(
+		currency_id: AssetId,
+		index: u32,
+		unreleased: Balance,
+	) -> Result<AttributeOf<T>, DispatchError> {
+		(currency_id, index, unreleased)
+			.encode()
+			.try_into()
+			.map_err(|_| Error::<T>::MaxAttributeExceeded.into())
+	}
+}
+
+impl<T: Config> IcoReceiptHandler<T::AccountId, AssetId, Balance> for Pallet<T> {
+	type ReceiptId = (T::ClassId, T::TokenId);
+
+	fn mint_receipt(
+		issuer: &T::AccountId,
+		owner: &T::AccountId,
+		currency_id: AssetId,
+		index: u32,
+		unreleased: Balance,
+	) -> Result<Self::ReceiptId, DispatchError> {
+		let class_id = Self::ico_receipt_class(issuer, currency_id, index)?;
+		let token_id = NextTokenId::<T>::try_mutate(class_id, |id| -> Result<T::TokenId, DispatchError> {
+			let current_id = *id;
+			*id = id.checked_add(&One::one()).ok_or(Error::<T>::NoAvailableTokenId)?;
+			Ok(current_id)
+		})?;
+		Classes::<T>::try_mutate(class_id, |class_info| -> DispatchResult {
+			let info = class_info.as_mut().ok_or(Error::<T>::ClassNotFound)?;
+			info.total_issuance = info
+				.total_issuance
+				.checked_add(&One::one())
+				.ok_or(ArithmeticError::Overflow)?;
+			Ok(())
+		})?;
+
+		let data = TokenData {
+			class_id,
+			hash: Self::get_hash(class_id, token_id),
+			power_threshold: BalanceOf::<T>::from(0u32),
+			claim_payment: BalanceOf::<T>::from(0u32),
+			attribute: Self::receipt_attribute(currency_id, index, unreleased)?,
+			image_hash: vec![],
+			sell_records: vec![],
+			status: NftStatus {
+				is_in_sale: false,
+				is_active_image: false,
+				is_claimed: true,
+			},
+		};
+		Tokens::<T>::insert(
+			class_id,
+			token_id,
+			TokenInfo {
+				metadata: Default::default(),
+				owner: Some(owner.clone()),
+				data,
+			},
+		);
+		Self::get_token_ownership(owner, class_id, token_id);
+		Self::deposit_event(Event::<T>::Mint(class_id, token_id));
+		Ok((class_id, token_id))
+	}
+
+	fn update_receipt(receipt: &Self::ReceiptId, unreleased: Balance) -> DispatchResult {
+		let (currency_id, index) = IcoOfReceiptClass::<T>::get(receipt.0).ok_or(Error::<T>::ClassNotFound)?;
+		let attribute = Self::receipt_attribute(currency_id, index, unreleased)?;
+		Tokens::<T>::try_mutate(receipt.0, receipt.1, |token_info| -> DispatchResult {
+			let info = token_info.as_mut().ok_or(Error::<T>::TokenNotFound)?;
+			info.data.attribute = attribute;
+			Ok(())
+		})
+	}
+
+	fn owner_of(receipt: &Self::ReceiptId) -> Option<T::AccountId> {
+		Tokens::<T>::get(receipt.0, receipt.1).and_then(|h| h.owner)
+	}
+}
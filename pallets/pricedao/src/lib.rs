@@ -5,12 +5,12 @@ use dico_currencies;
 use frame_support::{log, pallet_prelude::*, traits::Get, PalletId};
 use frame_support::{
 	sp_runtime::traits::{AccountIdConversion, Zero},
-	traits::{Currency, EnsureOrigin, ExistenceRequirement, ReservableCurrency},
+	traits::{Currency, EnsureOrigin, ExistenceRequirement, ReservableCurrency, UnixTime},
 };
 use frame_system::{self as system, ensure_signed, pallet_prelude::*};
-use orml_traits::{DataFeeder, DataProvider};
+use orml_traits::{DataFeeder, DataProvider, DataProviderExtended};
 use pallet_amm::Pair;
-use pallet_oracle::UpdateOraclesStorgage;
+use pallet_oracle::{TimestampedValue, UpdateOraclesStorgage};
 use sp_runtime::RuntimeDebug;
 use sp_std::prelude::*;
 // use frame_support::log;
@@ -52,7 +52,11 @@ pub mod pallet {
 	pub trait Config: frame_system::Config + pallet_amm::Config {
 		// 	type Event: From<Event> + Into<<Self as system::Trait>::Event>;
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
-		type Source: DataProvider<CurrencyId, Price> + DataFeeder<CurrencyId, Price, Self::AccountId>;
+		type Source: DataProvider<CurrencyId, Price>
+			+ DataProviderExtended<CurrencyId, TimestampedValue<Price, Moment>>
+			+ DataFeeder<CurrencyId, Price, Self::AccountId>;
+		/// The time to measure the age of the oracle prices.
+		type UnixTime: UnixTime;
 		type FeedOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 		type UpdateOraclesStorgage: UpdateOraclesStorgage<Self::AccountId, CurrencyId>;
 		type BaseCurrency: Currency<Self::AccountId> + ReservableCurrency<Self::AccountId>;
@@ -312,6 +316,22 @@ impl<T: Config> PriceData<CurrencyId> for Pallet<T> {
 		<Self as PriceProvider<CurrencyId>>::get_price_from_oracle(currency_id)
 			.or_else(|| Self::get_price_from_swap(currency_id, stable_coin))
 	}
+
+	fn get_oracle_price_age(currency_id: CurrencyId) -> Option<Moment> {
+		let timestamped_price = T::Source::get_no_op(&currency_id)?;
+		let now = T::UnixTime::now().as_millis() as Moment;
+		Some(now.saturating_sub(timestamped_price.timestamp))
+	}
+
+	fn get_twap_price(currency_id: CurrencyId, stable_coin: CurrencyId) -> Option<Price> {
+		// the price of one whole currency in the smallest units of the stable coin
+		let query_currency_uint = Self::get_uint(currency_id)?;
+		let twap = pallet_amm::Pallet::<T>::get_twap(currency_id, stable_coin)?;
+		let price = twap
+			.checked_mul(query_currency_uint)?
+			.checked_div(U256::from(pallet_amm::math::PRICE_PRECISION))?;
+		Balance::checked_from(price)
+	}
 }
 
 impl<T: Config> PriceProvider<CurrencyId> for Pallet<T> {
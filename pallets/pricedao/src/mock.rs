@@ -1,8 +1,9 @@
 #![cfg(test)]
 
-use super::{Balance, Config, CurrencyId, DataFeeder, DataProvider, Price, Zero};
+use super::{Balance, Config, CurrencyId, DataFeeder, DataProvider, DataProviderExtended, Moment, Price, Zero};
 use crate as pallet_price;
 use frame_system::EnsureSignedBy;
+use pallet_oracle::TimestampedValue;
 use sp_core::H256;
 use std::cell::RefCell;
 
@@ -15,8 +16,8 @@ use sp_runtime::{
 use dico_currencies::BasicCurrencyAdapter;
 use frame_support::{
 	construct_runtime, ord_parameter_types, parameter_types,
-	traits::Time,
 	traits::{ConstU32, Contains, GenesisBuild},
+	traits::{Time, UnixTime},
 	PalletId,
 };
 use frame_system as system;
@@ -136,6 +137,7 @@ impl pallet_oracle::Config for Test {
 impl Config for Test {
 	type Event = Event;
 	type Source = MockDataProvider;
+	type UnixTime = MockUnixTime;
 	type FeedOrigin = EnsureSignedBy<One, AccountId>;
 	type UpdateOraclesStorgage = DicoOracle;
 	type BaseCurrency = Balances;
@@ -164,6 +166,23 @@ impl DataFeeder<CurrencyId, Price, AccountId> for MockDataProvider {
 	}
 }
 
+impl DataProviderExtended<CurrencyId, TimestampedValue<Price, Moment>> for MockDataProvider {
+	fn get_no_op(currency_id: &CurrencyId) -> Option<TimestampedValue<Price, Moment>> {
+		Self::get(currency_id).map(|value| TimestampedValue { value, timestamp: 0 })
+	}
+
+	fn get_all_values() -> Vec<(CurrencyId, Option<TimestampedValue<Price, Moment>>)> {
+		vec![]
+	}
+}
+
+pub struct MockUnixTime;
+impl UnixTime for MockUnixTime {
+	fn now() -> core::time::Duration {
+		core::time::Duration::from_millis(<Timestamp as Time>::now() as u64)
+	}
+}
+
 parameter_types! {
 	pub const AMMPalletId: PalletId = PalletId(*b"dico/amm");
 	pub const AmmLiquidityAssetIdBase: AssetId = 20000000;
@@ -174,6 +193,7 @@ impl pallet_amm::Config for Test {
 	type LiquidityAssetIdBase = AmmLiquidityAssetIdBase;
 	type Currency = Currency;
 	type PalletId = AMMPalletId;
+	type TwapPeriod = frame_support::traits::ConstU64<10>;
 	type WeightInfo = ();
 	type CurrenciesHandler = Currency;
 }
@@ -1,7 +1,7 @@
 #![cfg(test)]
 
 use crate::Balance;
-use crate::{mock::*, Error};
+use crate::{mock::*, Error, PriceData};
 use frame_support::pallet_prelude::DispatchError::BadOrigin;
 use frame_support::traits::Currency;
 use frame_support::{assert_noop, assert_ok, log};
@@ -125,3 +125,12 @@ fn unlock_price_should_work() {
 		assert_ok!(PriceDao::unlock_price(Origin::signed(ALICE), 1));
 	});
 }
+
+#[test]
+fn get_oracle_price_age_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(PriceDao::get_oracle_price_age(1), Some(12345));
+		assert_eq!(PriceDao::get_oracle_price_age(100), None);
+		assert_eq!(PriceDao::get_twap_price(1, 2), None);
+	});
+}
@@ -1,3 +1,4 @@
+use primitives::Moment;
 use sp_core::U256;
 
 pub trait PriceProvider<CurrencyId> {
@@ -10,4 +11,8 @@ pub trait PriceProvider<CurrencyId> {
 pub trait PriceData<CurrencyId> {
 	type Price;
 	fn get_price(currency_id: CurrencyId, stable_coin: CurrencyId) -> Option<Self::Price>;
+	/// How long ago the oracle price was fed, in milliseconds.
+	fn get_oracle_price_age(currency_id: CurrencyId) -> Option<Moment>;
+	/// The time weighted average price from the swap.
+	fn get_twap_price(currency_id: CurrencyId, stable_coin: CurrencyId) -> Option<Self::Price>;
 }
@@ -0,0 +1,56 @@
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::{account, benchmarks};
+use frame_system::{Pallet as System, RawOrigin};
+use sp_runtime::traits::Bounded;
+use sp_std::prelude::*;
+
+use crate::Pallet as TierPallet;
+
+const SEED: u32 = 1;
+
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let caller: T::AccountId = account(name, index, SEED);
+	T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+	caller
+}
+
+benchmarks! {
+	lock {
+		let caller = funded_account::<T>("caller", 0);
+		let amount = BalanceOf::<T>::from(1_000_000u32);
+	}: _(RawOrigin::Signed(caller.clone()), amount)
+	verify {
+		assert_eq!(Locks::<T>::get(&caller).unwrap().amount, amount);
+	}
+
+	unlock {
+		let caller = funded_account::<T>("caller", 0);
+		TierPallet::<T>::lock(RawOrigin::Signed(caller.clone()).into(), BalanceOf::<T>::from(1_000_000u32))?;
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(Locks::<T>::get(&caller).unwrap().unlock_at.is_some());
+	}
+
+	withdraw {
+		let caller = funded_account::<T>("caller", 0);
+		TierPallet::<T>::lock(RawOrigin::Signed(caller.clone()).into(), BalanceOf::<T>::from(1_000_000u32))?;
+		TierPallet::<T>::unlock(RawOrigin::Signed(caller.clone()).into())?;
+		System::<T>::set_block_number(System::<T>::block_number() + T::UnlockCooldown::get());
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(Locks::<T>::get(&caller).is_none());
+	}
+
+	set_tier {
+		let tier_info = TierInfo {
+			min_locked_amount: BalanceOf::<T>::from(1_000_000u32),
+			user_max_amount_percent: 200,
+		};
+	}: _(RawOrigin::Root, Tier::Gold, Some(tier_info.clone()))
+	verify {
+		assert_eq!(Tiers::<T>::get(Tier::Gold), Some(tier_info));
+	}
+}
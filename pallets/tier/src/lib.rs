@@ -0,0 +1,275 @@
+//! # Tier Pallet
+//!
+//! ## Overview
+//!
+//! Tier pallet provides functionality for users to lock DICO and get a tier, which gives them a
+//! larger allocation in ICOs.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+#![allow(clippy::upper_case_acronyms)]
+
+use codec::{Decode, Encode};
+use frame_support::{
+	ensure,
+	pallet_prelude::*,
+	traits::{Currency, EnsureOrigin, Get, LockIdentifier, LockableCurrency, WithdrawReasons},
+	transactional,
+};
+use frame_system::pallet_prelude::*;
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{CheckedAdd, Saturating, Zero},
+	ArithmeticError,
+};
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+mod benchmarking;
+pub mod traits;
+pub mod weights;
+
+pub use traits::TierHandler;
+use weights::WeightInfo;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+pub const TIER_ID: LockIdentifier = *b"dicotier";
+
+type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+#[derive(Encode, Decode, Eq, PartialEq, Ord, PartialOrd, Copy, Clone, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Tier {
+	Bronze,
+	Silver,
+	Gold,
+	Platinum,
+}
+
+impl Tier {
+	/// All the tiers, from the highest to the lowest.
+	pub const ALL: [Tier; 4] = [Tier::Platinum, Tier::Gold, Tier::Silver, Tier::Bronze];
+}
+
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct TierInfo<Balance> {
+	/// The minimum amount of DICO a user locks to get the tier.
+	pub min_locked_amount: Balance,
+	/// The `user_max_amount` of ICOs is multiplied by this percent, e.g. 150 means 1.5 times.
+	pub user_max_amount_percent: u32,
+}
+
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct LockInfo<Balance, BlockNumber> {
+	pub amount: Balance,
+	/// The user can withdraw after this block, `None` if the user has not unlocked.
+	pub unlock_at: Option<BlockNumber>,
+}
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The native currency, DICO.
+		type Currency: LockableCurrency<Self::AccountId, Moment = Self::BlockNumber>;
+
+		/// The origin that is allowed to set the tiers.
+		type SetTierOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// How long the user waits to withdraw after unlocking.
+		#[pallet::constant]
+		type UnlockCooldown: Get<Self::BlockNumber>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The amount is zero.
+		InvalidAmount,
+		/// The user does not have enough free balance to lock.
+		InsufficientBalance,
+		/// The user has not locked.
+		NotLocked,
+		/// The user has unlocked and is in the cooldown.
+		AlreadyUnlocked,
+		/// The user has not unlocked.
+		NotUnlocked,
+		/// The cooldown has not ended yet.
+		CooldownNotEnded,
+		/// The ICO multiplier of the tier is less than 100 percent.
+		InvalidPercent,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub (crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The user locked DICO. [who, amount, total locked amount]
+		Locked(T::AccountId, BalanceOf<T>, BalanceOf<T>),
+		/// The user unlocked, and can withdraw after the block. [who, amount, unlock at]
+		Unlocked(T::AccountId, BalanceOf<T>, T::BlockNumber),
+		/// The user withdrew the DICO. [who, amount]
+		Withdrawn(T::AccountId, BalanceOf<T>),
+		/// The tier was set. [tier, tier info]
+		TierSet(Tier, Option<TierInfo<BalanceOf<T>>>),
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn tier_info)]
+	pub type Tiers<T: Config> = StorageMap<_, Twox64Concat, Tier, TierInfo<BalanceOf<T>>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn lock_info)]
+	pub type Locks<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, LockInfo<BalanceOf<T>, T::BlockNumber>, OptionQuery>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Lock `amount` more DICO.
+		///
+		/// Emits `Locked` event when successful.
+		#[pallet::weight(< T as Config >::WeightInfo::lock())]
+		#[transactional]
+		pub fn lock(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
+
+			let mut lock_info = Self::lock_info(&who).unwrap_or(LockInfo {
+				amount: Zero::zero(),
+				unlock_at: None,
+			});
+			ensure!(lock_info.unlock_at.is_none(), Error::<T>::AlreadyUnlocked);
+
+			lock_info.amount = lock_info.amount.checked_add(&amount).ok_or(ArithmeticError::Overflow)?;
+			ensure!(
+				T::Currency::free_balance(&who) >= lock_info.amount,
+				Error::<T>::InsufficientBalance
+			);
+
+			T::Currency::set_lock(TIER_ID, &who, lock_info.amount, WithdrawReasons::all());
+			let total = lock_info.amount;
+			Locks::<T>::insert(&who, lock_info);
+			Self::deposit_event(Event::Locked(who, amount, total));
+
+			Ok(().into())
+		}
+
+		/// Start the cooldown of the locked DICO, the user loses the tier at once.
+		///
+		/// Emits `Unlocked` event when successful.
+		#[pallet::weight(< T as Config >::WeightInfo::unlock())]
+		#[transactional]
+		pub fn unlock(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let mut lock_info = Self::lock_info(&who).ok_or(Error::<T>::NotLocked)?;
+			ensure!(lock_info.unlock_at.is_none(), Error::<T>::AlreadyUnlocked);
+
+			let unlock_at = frame_system::pallet::Pallet::<T>::block_number().saturating_add(T::UnlockCooldown::get());
+			lock_info.unlock_at = Some(unlock_at);
+			let amount = lock_info.amount;
+			Locks::<T>::insert(&who, lock_info);
+			Self::deposit_event(Event::Unlocked(who, amount, unlock_at));
+
+			Ok(().into())
+		}
+
+		/// Remove the lock after the cooldown.
+		///
+		/// Emits `Withdrawn` event when successful.
+		#[pallet::weight(< T as Config >::WeightInfo::withdraw())]
+		#[transactional]
+		pub fn withdraw(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let lock_info = Self::lock_info(&who).ok_or(Error::<T>::NotLocked)?;
+			let unlock_at = lock_info.unlock_at.ok_or(Error::<T>::NotUnlocked)?;
+			ensure!(
+				frame_system::pallet::Pallet::<T>::block_number() >= unlock_at,
+				Error::<T>::CooldownNotEnded
+			);
+
+			T::Currency::remove_lock(TIER_ID, &who);
+			Locks::<T>::remove(&who);
+			Self::deposit_event(Event::Withdrawn(who, lock_info.amount));
+
+			Ok(().into())
+		}
+
+		/// Set the minimum locked amount and the ICO multiplier of `tier`, or remove the tier
+		/// if `tier_info` is `None`.
+		///
+		/// Emits `TierSet` event when successful.
+		#[pallet::weight(< T as Config >::WeightInfo::set_tier())]
+		pub fn set_tier(
+			origin: OriginFor<T>,
+			tier: Tier,
+			tier_info: Option<TierInfo<BalanceOf<T>>>,
+		) -> DispatchResultWithPostInfo {
+			T::SetTierOrigin::ensure_origin(origin)?;
+
+			match tier_info.as_ref() {
+				Some(info) => {
+					ensure!(!info.min_locked_amount.is_zero(), Error::<T>::InvalidAmount);
+					ensure!(info.user_max_amount_percent >= 100, Error::<T>::InvalidPercent);
+					Tiers::<T>::insert(tier, info);
+				}
+				None => Tiers::<T>::remove(tier),
+			}
+			Self::deposit_event(Event::TierSet(tier, tier_info));
+
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The highest tier whose minimum locked amount the user reaches.
+	pub fn get_tier(who: &T::AccountId) -> Option<Tier> {
+		let lock_info = Self::lock_info(who)?;
+		if lock_info.unlock_at.is_some() {
+			return None;
+		}
+
+		Tier::ALL.iter().copied().find(|tier| match Self::tier_info(tier) {
+			Some(info) => lock_info.amount >= info.min_locked_amount,
+			None => false,
+		})
+	}
+}
+
+impl<T: Config> TierHandler<T::AccountId> for Pallet<T> {
+	fn get_tier(who: &T::AccountId) -> Option<Tier> {
+		Self::get_tier(who)
+	}
+
+	fn get_user_max_amount_percent(who: &T::AccountId) -> u32 {
+		Self::get_tier(who)
+			.and_then(|tier| Self::tier_info(tier))
+			.map(|info| info.user_max_amount_percent)
+			.unwrap_or(100)
+	}
+}
@@ -0,0 +1,130 @@
+//! Unit tests for the tier module.
+
+#![cfg(test)]
+
+use super::*;
+pub use crate::mock::{
+	Balances, ExtBuilder, RuntimeEvent as TestEvent, RuntimeOrigin as Origin, System, Test, Tier as TierPallet, ALICE,
+	BOB, DEFAULT_BALANCE,
+};
+use frame_support::{assert_noop, assert_ok};
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext = ExtBuilder::default().build();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+fn set_tiers() {
+	assert_ok!(TierPallet::set_tier(
+		Origin::root(),
+		Tier::Bronze,
+		Some(TierInfo {
+			min_locked_amount: 100,
+			user_max_amount_percent: 100,
+		})
+	));
+	assert_ok!(TierPallet::set_tier(
+		Origin::root(),
+		Tier::Gold,
+		Some(TierInfo {
+			min_locked_amount: 1000,
+			user_max_amount_percent: 200,
+		})
+	));
+}
+
+#[test]
+fn set_tier_should_work() {
+	new_test_ext().execute_with(|| {
+		let tier_info = TierInfo {
+			min_locked_amount: 1000,
+			user_max_amount_percent: 200,
+		};
+		assert_noop!(
+			TierPallet::set_tier(Origin::signed(ALICE), Tier::Gold, Some(tier_info.clone())),
+			sp_runtime::traits::BadOrigin
+		);
+		assert_noop!(
+			TierPallet::set_tier(
+				Origin::root(),
+				Tier::Gold,
+				Some(TierInfo {
+					min_locked_amount: 1000,
+					user_max_amount_percent: 50,
+				})
+			),
+			Error::<Test>::InvalidPercent
+		);
+
+		assert_ok!(TierPallet::set_tier(
+			Origin::root(),
+			Tier::Gold,
+			Some(tier_info.clone())
+		));
+		assert_eq!(TierPallet::tier_info(Tier::Gold), Some(tier_info.clone()));
+		System::assert_last_event(TestEvent::Tier(crate::Event::TierSet(Tier::Gold, Some(tier_info))));
+
+		assert_ok!(TierPallet::set_tier(Origin::root(), Tier::Gold, None));
+		assert_eq!(TierPallet::tier_info(Tier::Gold), None);
+	});
+}
+
+#[test]
+fn lock_should_work() {
+	new_test_ext().execute_with(|| {
+		set_tiers();
+		assert_noop!(TierPallet::lock(Origin::signed(ALICE), 0), Error::<Test>::InvalidAmount);
+		assert_noop!(
+			TierPallet::lock(Origin::signed(ALICE), DEFAULT_BALANCE + 1),
+			Error::<Test>::InsufficientBalance
+		);
+
+		assert_ok!(TierPallet::lock(Origin::signed(ALICE), 500));
+		assert_eq!(TierPallet::get_tier(&ALICE), Some(Tier::Bronze));
+		assert_eq!(<TierPallet as TierHandler<_>>::get_user_max_amount_percent(&ALICE), 100);
+
+		assert_ok!(TierPallet::lock(Origin::signed(ALICE), 500));
+		System::assert_last_event(TestEvent::Tier(crate::Event::Locked(ALICE, 500, 1000)));
+		assert_eq!(TierPallet::get_tier(&ALICE), Some(Tier::Gold));
+		assert_eq!(<TierPallet as TierHandler<_>>::get_user_max_amount_percent(&ALICE), 200);
+
+		// The locked DICO can not be transferred.
+		assert!(Balances::transfer(Origin::signed(ALICE), BOB, DEFAULT_BALANCE - 999).is_err());
+		assert_eq!(TierPallet::get_tier(&BOB), None);
+		assert_eq!(<TierPallet as TierHandler<_>>::get_user_max_amount_percent(&BOB), 100);
+	});
+}
+
+#[test]
+fn unlock_and_withdraw_should_work() {
+	new_test_ext().execute_with(|| {
+		set_tiers();
+		assert_noop!(TierPallet::unlock(Origin::signed(ALICE)), Error::<Test>::NotLocked);
+		assert_ok!(TierPallet::lock(Origin::signed(ALICE), 1000));
+		assert_noop!(TierPallet::withdraw(Origin::signed(ALICE)), Error::<Test>::NotUnlocked);
+
+		assert_ok!(TierPallet::unlock(Origin::signed(ALICE)));
+		System::assert_last_event(TestEvent::Tier(crate::Event::Unlocked(ALICE, 1000, 101)));
+		// The user loses the tier during the cooldown.
+		assert_eq!(TierPallet::get_tier(&ALICE), None);
+		assert_noop!(
+			TierPallet::unlock(Origin::signed(ALICE)),
+			Error::<Test>::AlreadyUnlocked
+		);
+		assert_noop!(
+			TierPallet::lock(Origin::signed(ALICE), 100),
+			Error::<Test>::AlreadyUnlocked
+		);
+		assert_noop!(
+			TierPallet::withdraw(Origin::signed(ALICE)),
+			Error::<Test>::CooldownNotEnded
+		);
+
+		System::set_block_number(101);
+		assert_ok!(TierPallet::withdraw(Origin::signed(ALICE)));
+		assert_eq!(TierPallet::lock_info(&ALICE), None);
+		assert_ok!(Balances::transfer(Origin::signed(ALICE), BOB, DEFAULT_BALANCE - 1));
+		assert_eq!(Balances::free_balance(BOB), 2 * DEFAULT_BALANCE - 1);
+	});
+}
@@ -0,0 +1,17 @@
+use crate::Tier;
+
+pub trait TierHandler<AccountId> {
+	fn get_tier(who: &AccountId) -> Option<Tier>;
+	/// The percent the `user_max_amount` of ICOs is multiplied by, 100 if the user has no tier.
+	fn get_user_max_amount_percent(who: &AccountId) -> u32;
+}
+
+impl<AccountId> TierHandler<AccountId> for () {
+	fn get_tier(_: &AccountId) -> Option<Tier> {
+		None
+	}
+
+	fn get_user_max_amount_percent(_: &AccountId) -> u32 {
+		100
+	}
+}
@@ -0,0 +1,83 @@
+//! Autogenerated weights for pallet_tier
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! DATE: 2022-04-15, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("kico"), DB CACHE: 1024
+
+// Executed Command:
+// target/release/dico
+// benchmark
+// --chain=kico
+// --execution=wasm
+// --wasm-execution=compiled
+// --pallet=pallet_tier
+// --extrinsic=*
+// --steps=50
+// --repeat=20
+// --template=./.maintain/pallet-weight-template.hbs
+// --output
+// ./pallets/tier/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_tier.
+pub trait WeightInfo {
+	fn lock() -> Weight;
+	fn unlock() -> Weight;
+	fn withdraw() -> Weight;
+	fn set_tier() -> Weight;
+}
+
+/// Weights for pallet_tier using the Substrate node and recommended hardware.
+pub struct DicoWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for DicoWeight<T> {
+	// Storage: System Account (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: Tier Locks (r:1 w:1)
+	fn lock() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Tier Locks (r:1 w:1)
+	fn unlock() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Tier Locks (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn withdraw() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Tier Tiers (r:0 w:1)
+	fn set_tier() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: System Account (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: Tier Locks (r:1 w:1)
+	fn lock() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Tier Locks (r:1 w:1)
+	fn unlock() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Tier Locks (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn withdraw() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+	// Storage: Tier Tiers (r:0 w:1)
+	fn set_tier() -> Weight {
+		Weight::from_ref_time(20_0000_0000)
+	}
+}
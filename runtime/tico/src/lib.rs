@@ -80,13 +80,16 @@ pub use pallet_farm;
 pub use pallet_farm_extend;
 pub use pallet_kyc;
 pub use pallet_lbp;
+pub use pallet_merkle_distributor;
 pub use pallet_pricedao;
+pub use pallet_tier;
 pub use pallet_vc;
 pub use vc::*;
 
 use crate::constants::*;
 use crate::migrations::*;
 use crate::parachains::*;
+use pallet_farm_extend_rpc_runtime_api as farm_extend_rpc;
 use pallet_farm_rpc_runtime_api as farm_rpc;
 
 mod constants;
@@ -254,6 +257,8 @@ construct_runtime!(
 		PriceDao: pallet_pricedao::{Pallet, Call, Storage, Event<T>} = 85,
 		Currencies: pallet_currencies::{Pallet, Event<T>, Call, Storage, Config<T>} = 86,
 		DicoOracle: pallet_oracle::<Instance1>::{Pallet, Storage, Call, Event<T>}= 87,
+		MerkleDistributor: pallet_merkle_distributor::{Pallet, Call, Storage, Event<T>} = 88,
+		Tier: pallet_tier::{Pallet, Call, Storage, Event<T>} = 89,
 	}
 );
 
@@ -735,9 +740,15 @@ parameter_types! {
 	pub const KYCBasicDeposit: Balance = 100 * DOLLARS;
 	pub const KYCServiceDeposit: Balance = 200000 * DOLLARS;
 	pub const AmmPalletId: PalletId = PalletId(*b"dico/amm");
+	pub const AmmTwapPeriod: BlockNumber = 1 * HOURS;
 	pub const FarmPalletId: PalletId = PalletId(*b"dico/fam");
+	pub const MaxEmissionEpochs: u32 = 50;
 	pub const LBPPalletId: PalletId = PalletId(*b"dico/lbp");
 	pub const FarmExtendPalletId: PalletId = PalletId(*b"dico/fme");
+	pub const MerkleDistributorPalletId: PalletId = PalletId(*b"dico/mkd");
+	pub const IcoProtectionFundId: PalletId = PalletId(*b"dico/ipf");
+	pub const MaxProofLength: u32 = 32;
+	pub const MaxClaimedRemovals: u32 = 1000;
 }
 
 /// Configure the pallet template in pallets/template.
@@ -763,6 +774,7 @@ impl pallet_amm::Config for Runtime {
 	type CurrenciesHandler = Currencies;
 	type LiquidityAssetIdBase = MaxCreatableCurrencyId;
 	type PalletId = AmmPalletId;
+	type TwapPeriod = AmmTwapPeriod;
 	type WeightInfo = pallet_amm::weights::DicoWeight<Runtime>;
 }
 
@@ -783,6 +795,7 @@ impl pallet_farm::Config for Runtime {
 	type FounderSetOrigin = EnsureRootOrMoreThanHalfCouncil;
 	type NativeAssetId = DICOAssetId;
 	type PalletId = FarmPalletId;
+	type MaxEmissionEpochs = MaxEmissionEpochs;
 	type WeightInfo = pallet_farm::weights::DicoWeight<Runtime>;
 }
 
@@ -794,6 +807,18 @@ impl pallet_farm_extend::Config for Runtime {
 	type WeightInfo = pallet_farm_extend::weights::DicoWeight<Runtime>;
 }
 
+impl pallet_merkle_distributor::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type CampaignId = u32;
+	type Currency = Currencies;
+	type CreateOrigin = EnsureRootOrMoreThanHalfCouncil;
+	type TreasuryHandler = DicoTreasury;
+	type PalletId = MerkleDistributorPalletId;
+	type MaxProofLength = MaxProofLength;
+	type MaxClaimedRemovals = MaxClaimedRemovals;
+	type WeightInfo = pallet_merkle_distributor::weights::DicoWeight<Runtime>;
+}
+
 parameter_types! {
 	pub TreasuryAccount: AccountId = TreasuryPalletId::get().into_account_truncating();
 	pub const TokensMaxReserves: u32 = 50;
@@ -820,6 +845,8 @@ pub fn get_all_module_accounts() -> Vec<AccountId> {
 		FarmPalletId::get().into_account_truncating(),
 		LBPPalletId::get().into_account_truncating(),
 		FarmExtendPalletId::get().into_account_truncating(),
+		MerkleDistributorPalletId::get().into_account_truncating(),
+		IcoProtectionFundId::get().into_account_truncating(),
 	]
 }
 
@@ -945,6 +972,7 @@ type EnsureRootOrTwoThirdsGeneralCouncil = EnsureOneOf<
 impl pallet_pricedao::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Source = AggregatedDataProvider;
+	type UnixTime = Timestamp;
 	type FeedOrigin = EnsureRootOrTwoThirdsGeneralCouncil;
 	type UpdateOraclesStorgage = DicoOracle;
 	type DicoTreasuryModuleId = DicoTreasuryModuleId;
@@ -990,15 +1018,62 @@ parameter_types! {
 	pub const ChillDuration: BlockNumber = 10 * MINUTES;
 	pub const InviterRewardProportion: Percent = Percent::from_percent(10u8);
 	pub const InviteeRewardProportion: Percent = Percent::from_percent(5u8);
+	pub const MaxAllowlistProofLength: u32 = 32;
+	pub const IcoMaxPriceAge: Moment = 10 * 60 * 1000;
+	pub const IcoMaxTwapDeviation: Percent = Percent::from_percent(10);
+	pub const MaxInviteCodeLength: u32 = 16;
+	pub const MaxReferralLevels: u32 = 3;
+	pub const IcoCancelFee: Percent = Percent::from_percent(10);
+	pub const IcoPowerHalfLife: BlockNumber = 180 * DAYS;
+	pub const MaxPowerDelegators: u32 = 32;
+	pub const IcoPowerRewardBoost: Percent = Percent::from_percent(20);
+	pub const MaxLotteryEntrants: u32 = 1000;
+	pub const MaxLotteryDrawsPerBlock: u32 = 50;
+	pub const MaxRequestExpiriesPerBlock: u32 = 50;
+	pub const IcoRewardPoolClaimDuration: BlockNumber = 30 * DAYS;
+	pub const IcoProtectionFundProportion: Percent = Percent::from_percent(2);
+	pub const TierUnlockCooldown: BlockNumber = 7 * DAYS;
 	pub const USDCurrencyId: CurrencyId = AUSD;
 }
 
+/// The output of the BABE VRF of the relay parent block, read from the relay chain state proof.
+/// `None` when the state proof does not have it.
+pub struct RelayChainRandomness;
+impl frame_support::traits::Randomness<Option<Hash>, BlockNumber> for RelayChainRandomness {
+	fn random(subject: &[u8]) -> (Option<Hash>, BlockNumber) {
+		let relay_randomness = ParachainSystem::validation_data()
+			.zip(ParachainSystem::relay_state_proof())
+			.and_then(|(data, proof)| {
+				cumulus_pallet_parachain_system::RelayChainStateProof::new(
+					ParachainInfo::parachain_id(),
+					data.relay_parent_storage_root,
+					proof,
+				)
+				.ok()
+			})
+			.and_then(|proof| {
+				proof
+					.read_optional_entry::<Option<[u8; 32]>>(
+						cumulus_primitives_core::relay_chain::v2::well_known_keys::CURRENT_BLOCK_RANDOMNESS,
+					)
+					.ok()
+					.flatten()
+					.flatten()
+			});
+		(
+			relay_randomness.map(|h| <BlakeTwo256 as sp_runtime::traits::Hash>::hash_of(&(h, subject))),
+			System::block_number(),
+		)
+	}
+}
+
 impl pallet_ico::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type PermitIcoOrigin = pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 1u32, 2u32>;
 	type RejectIcoOrigin = pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 1u32, 2u32>;
 	type PermitReleaseOrigin = pallet_dao::EnsureProportionAtLeast<Runtime, AccountId, 1, 2>;
 	type TerminateIcoOrigin = pallet_dao::EnsureProportionAtLeast<Runtime, AccountId, 1, 2>;
+	type SetRewardEconomicsOrigin = EnsureRootOrHalfCouncil;
 	type OnSlash = ();
 	type MultiCurrency = Currencies;
 	type NativeCurrency = Balances;
@@ -1017,8 +1092,37 @@ impl pallet_ico::Config for Runtime {
 	type InviteeRewardProportion = InviteeRewardProportion;
 	type PriceData = PriceDao;
 	type KycHandler = Kyc;
+	type TierHandler = Tier;
 	type WeightInfo = pallet_ico::weights::DicoWeight<Runtime>;
 	type USDCurrencyId = USDCurrencyId;
+	type MaxAllowlistProofLength = MaxAllowlistProofLength;
+	type MaxPriceAge = IcoMaxPriceAge;
+	type MaxTwapDeviation = IcoMaxTwapDeviation;
+	type MaxInviteCodeLength = MaxInviteCodeLength;
+	type MaxReferralLevels = MaxReferralLevels;
+	type CancelIcoFee = IcoCancelFee;
+	type PowerHalfLife = IcoPowerHalfLife;
+	type MaxPowerDelegators = MaxPowerDelegators;
+	type PowerRewardBoost = IcoPowerRewardBoost;
+	type MaxLotteryEntrants = MaxLotteryEntrants;
+	type MaxLotteryDrawsPerBlock = MaxLotteryDrawsPerBlock;
+	type MaxRequestExpiriesPerBlock = MaxRequestExpiriesPerBlock;
+	type RewardPoolClaimDuration = IcoRewardPoolClaimDuration;
+	type ProtectionFundId = IcoProtectionFundId;
+	type ProtectionFundProportion = IcoProtectionFundProportion;
+	type IcoProposalHandler = Dao;
+	type LiquidityHandler = AMM;
+	type ReceiptHandler = Nft;
+	type Randomness = RelayChainRandomness;
+	type IcoMembersOrigin = pallet_dao::EnsureIcoMembers<Runtime, AccountId>;
+}
+
+impl pallet_tier::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type SetTierOrigin = EnsureRootOrMoreThanHalfCouncil;
+	type UnlockCooldown = TierUnlockCooldown;
+	type WeightInfo = pallet_tier::weights::DicoWeight<Runtime>;
 }
 
 parameter_types! {
@@ -1122,6 +1226,28 @@ impl_runtime_apis! {
 
 			reward
 		}
+
+		fn get_account_positions(account: AccountId) -> Vec<(PoolId, pallet_farm::Participant, Balance)> {
+			Farm::get_account_positions(account)
+		}
+
+		fn get_pool_reward_infos() -> Vec<(PoolId, pallet_farm::PoolRewardInfo)> {
+			Farm::get_pool_reward_infos()
+		}
+	}
+
+	impl farm_extend_rpc::FarmExtendApi<Block, AccountId, PoolId, Balance> for Runtime {
+		fn get_pending_reward(account: AccountId, pid: PoolId) -> Balance {
+			FarmExtend::get_pending_reward(account, pid)
+		}
+
+		fn get_active_pools() -> Vec<(PoolId, pallet_farm_extend::PoolExtendInfo<AccountId>)> {
+			FarmExtend::get_active_pools()
+		}
+
+		fn get_account_positions(account: AccountId) -> Vec<(PoolId, pallet_farm_extend::ParticipantExtend, Balance)> {
+			FarmExtend::get_account_positions(account)
+		}
 	}
 
 	impl sp_session::SessionKeys<Block> for Runtime {
@@ -1153,7 +1279,7 @@ impl_runtime_apis! {
 		}
 	}
 
-	impl pallet_ico_rpc_runtime_api::IcoAmountApi<Block, AccountId, CurrencyId, Index, Balance> for Runtime {
+	impl pallet_ico_rpc_runtime_api::IcoAmountApi<Block, AccountId, CurrencyId, Index, Balance, BlockNumber> for Runtime {
 		fn can_release_amount(account: AccountId, currency_id: CurrencyId, index: Index) -> Balance {
 			Ico::can_release_amount(account, currency_id, index)
 		}
@@ -1170,6 +1296,22 @@ impl_runtime_apis! {
 		fn get_token_price(currency_id: CurrencyId) -> Balance {
 			Ico::get_token_price(currency_id)
 		}
+
+		fn get_referral_earnings(account: AccountId) -> Vec<(CurrencyId, Index, Balance)> {
+			Ico::get_referral_earnings(account)
+		}
+
+		fn get_icoes(stage: Option<pallet_ico::IcoStage>, start: u32, limit: u32) -> Vec<pallet_ico::IcoDetail<AccountId, BlockNumber, Balance>> {
+			Ico::get_icoes(stage, start, limit)
+		}
+
+		fn get_ico_detail(currency_id: CurrencyId, index: Index) -> Option<pallet_ico::IcoDetail<AccountId, BlockNumber, Balance>> {
+			Ico::get_ico_detail(currency_id, index)
+		}
+
+		fn get_portfolio(account: AccountId) -> Vec<pallet_ico::IcoPosition<Balance>> {
+			Ico::get_portfolio(account)
+		}
 	}
 
 	impl frame_system_rpc_runtime_api::AccountNonceApi<Block, AccountId, Index> for Runtime {